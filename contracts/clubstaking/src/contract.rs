@@ -1,22 +1,35 @@
 #[cfg(not(feature = "library"))]
-use cosmwasm_std::{entry_point, Timestamp};
+use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    from_binary, to_binary, BankMsg, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Order,
-    QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
-    WasmQuery,
+    from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Deps, DepsMut, Env,
+    MessageInfo, Order, QueryRequest, Reply, Response, StdError, StdResult, Storage, SubMsg,
+    Timestamp, Uint128, WasmMsg, WasmQuery,
 };
 
 use cw2::set_contract_version;
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
-use cw_storage_plus::Map;
+use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg, Denom};
+use cw721::{Cw721ExecuteMsg, Cw721QueryMsg, OwnerOfResponse};
+use cw721_base::{ExecuteMsg as Cw721BaseExecuteMsg, Extension, MintMsg};
+use cw_storage_plus::{Bound, Map};
 
 use crate::error::ContractError;
-use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceivedMsg};
+use crate::msg::{
+    ClaimableUnbondsResponse, ExecuteMsg, FeeAction, FeeEstimateResponse, InstantiateMsg,
+    MigrateMsg, QueryMsg, ReceivedMsg, ScheduleResponse, TotalPowerAtHeightResponse,
+    VotingPowerAtHeightResponse,
+};
 use crate::state::{
-    ClubBondingDetails, ClubOwnershipDetails, ClubPreviousOwnerDetails, ClubStakingDetails, Config,
-    WinningClubDetails, CLUB_BONDING_DETAILS, CLUB_OWNERSHIP_DETAILS, CLUB_PREVIOUS_OWNER_DETAILS,
-    CLUB_REWARD_NEXT_TIMESTAMP, CLUB_STAKING_DETAILS, CLUB_STAKING_SNAPSHOT, CONFIG, FEE_WALLET,
-    REWARD, REWARD_GIVEN_IN_CURRENT_TIMESTAMP, WINNING_CLUB_DETAILS_SNAPSHOT,
+    club_bonding_details, club_ownership_details, club_staking_details, full_commission_rate,
+    ClubBondingDetails, ClubMetadata, ClubOwnershipDetails, ClubPreviousOwnerDetails,
+    ClubStakingDetails, Config, DenomAmount, EarlyExitPenalty, EmissionSchedule, LockTier,
+    PenaltyDestination, WinningClubDetails, CLUB_COMMISSION_CHANGE_TIMESTAMP,
+    CLUB_LOCK_BONUS_STAKE, CLUB_NFT_CONTRACT, CLUB_NFT_MINTED, CLUB_PREVIOUS_OWNER_DETAILS,
+    CLUB_REWARD_NEXT_TIMESTAMP, CLUB_STAKING_SNAPSHOT, CLUB_WEIGHTED_STAKE_SNAPSHOT, CONFIG,
+    EARLY_EXIT_PENALTY, EMISSION_SCHEDULE, FEE_EXEMPT_ADDRESSES, FEE_WALLET,
+    LAST_STAKE_MOVE_TIMESTAMP, LEGACY_REWARD, LEGACY_REWARD_GIVEN_IN_CURRENT_TIMESTAMP,
+    OWNER_PARTNER_COMMISSION_OWED, PARTNER_REWARDS_OWED, REGISTERED_CLUBS, REWARDS,
+    REWARDS_GIVEN_IN_CURRENT_TIMESTAMP, STAKED_BALANCES, STAKED_TOTAL,
+    WINNING_CLUB_DETAILS_SNAPSHOT,
 };
 use wasmswap::msg::{
     QueryMsg as WasmswapQueryMsg, Token1ForToken2PriceResponse, Token2ForToken1PriceResponse,
@@ -50,9 +63,27 @@ const CLUB_STAKING_DURATION: u64 = 0u64;
 // const CLUB_BONDING_DURATION: u64 = 3600u64;
 // - now part of instantiation msg.bonding_duration
 
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+
 const HUNDRED_PERCENT: u128 = 10000u128;
 const NINETY_NINE_NINE_PERCENT: u128 = 9990u128;
 
+// Most a club commission can change in one reward period - 5%
+const MAX_COMMISSION_CHANGE_RATE: u128 = 500u128;
+
+// Lock durations in seconds and reward weights (100% = 10000) for each lock tier
+const LOCK_TIER_30_DAYS_DURATION: u64 = 30 * 24 * 60 * 60u64;
+const LOCK_TIER_90_DAYS_DURATION: u64 = 90 * 24 * 60 * 60u64;
+const LOCK_TIER_180_DAYS_DURATION: u64 = 180 * 24 * 60 * 60u64;
+const LOCK_TIER_30_DAYS_MULTIPLIER: u128 = 11000u128;
+const LOCK_TIER_90_DAYS_MULTIPLIER: u128 = 12500u128;
+const LOCK_TIER_180_DAYS_MULTIPLIER: u128 = 15000u128;
+
+// 10% of a stake withdrawn before its lock ends goes to the reward pool
+const EARLY_UNLOCK_PENALTY: u128 = 1000u128;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -108,7 +139,10 @@ pub fn execute(
             club_name,
             amount,
             auto_stake,
-        } => stake_on_a_club(deps, env, info, staker, club_name, amount, auto_stake),
+            lock_tier,
+        } => stake_on_a_club(
+            deps, env, info, staker, club_name, amount, auto_stake, lock_tier,
+        ),
         ExecuteMsg::AssignStakesToAClub {
             stake_list,
             club_name,
@@ -119,8 +153,7 @@ pub fn execute(
             club_name,
             auto_stake,
         } => {
-            let config = CONFIG.load(deps.storage)?;
-            let price = config.club_price;
+            let price = club_price(deps.storage, club_name.clone())?;
             buy_a_club(deps, env, info, buyer, seller, club_name, price, auto_stake)
         }
         ExecuteMsg::AssignAClub {
@@ -175,11 +208,142 @@ pub fn execute(
         } => increase_reward_amount(deps, env, info, reward_from, amount),
         ExecuteMsg::ChangeConfig { config } => change_config(deps, env, info, config),
         ExecuteMsg::ChangeFeeWallet { address } => change_fee_wallet(deps, env, info, address),
+        ExecuteMsg::AddFeeExemptAddress { address } => {
+            add_fee_exempt_address(deps, env, info, address)
+        }
+        ExecuteMsg::RemoveFeeExemptAddress { address } => {
+            remove_fee_exempt_address(deps, env, info, address)
+        }
+        ExecuteMsg::SetClubNftContract { address } => {
+            set_club_nft_contract(deps, env, info, address)
+        }
+        ExecuteMsg::SyncClubOwner { club_name } => sync_club_owner(deps, env, club_name),
+        ExecuteMsg::Receive(msg) => received_message(deps, env, info, msg),
+        ExecuteMsg::ClaimUnbondedStake { staker, club_name } => {
+            claim_unbonded_stake(deps, env, info, staker, club_name)
+        }
+        ExecuteMsg::RegisterClub {
+            club_name,
+            display_name,
+            sport,
+            logo_uri,
+            max_total_stake,
+            price_override,
+        } => register_club(
+            deps,
+            env,
+            info,
+            ClubMetadata {
+                club_name: club_name,
+                display_name: display_name,
+                sport: sport,
+                logo_uri: logo_uri,
+                max_total_stake: max_total_stake,
+                price_override: price_override,
+            },
+        ),
+        ExecuteMsg::SetEarlyExitPenalty { early_exit } => {
+            set_early_exit_penalty(deps, env, info, early_exit)
+        }
+        ExecuteMsg::Rebond {
+            club_name,
+            amount,
+            to_club,
+        } => rebond(deps, env, info, club_name, amount, to_club),
+        ExecuteMsg::MoveStake {
+            from_club,
+            to_club,
+            amount,
+        } => move_stake(deps, env, info, from_club, to_club, amount),
+        ExecuteMsg::IncreasePartnerReward {} => {
+            let rewards = info
+                .funds
+                .iter()
+                .map(|fund| DenomAmount {
+                    denom: Denom::Native(fund.denom.clone()),
+                    amount: fund.amount,
+                })
+                .collect();
+            increase_partner_reward(deps, env, info, rewards)
+        }
+        ExecuteMsg::SetAutoStake { club_name, enabled } => {
+            set_auto_stake(deps, env, info, club_name, enabled)
+        }
+        ExecuteMsg::SetClubCommission {
+            club_name,
+            commission_rate,
+        } => set_club_commission(deps, env, info, club_name, commission_rate),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Re-save the indexed maps so that their secondary indexes are built
+    let all_owners = club_ownership_details()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (club_name, owner_details) in all_owners {
+        club_ownership_details().save(deps.storage, club_name.clone(), &owner_details)?;
+        // clubs created before the registry keep working
+        if !REGISTERED_CLUBS.has(deps.storage, club_name.clone()) {
+            REGISTERED_CLUBS.save(
+                deps.storage,
+                club_name.clone(),
+                &ClubMetadata {
+                    club_name: club_name.clone(),
+                    display_name: club_name,
+                    sport: String::default(),
+                    logo_uri: None,
+                    max_total_stake: None,
+                    price_override: None,
+                },
+            )?;
+        }
+    }
+    let all_stakes = club_staking_details()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut stakers = Vec::new();
+    for ((club_name, staker), stakes) in all_stakes {
+        if stakes.is_empty() {
+            club_staking_details().remove(deps.storage, (&club_name, &staker))?;
+            continue;
+        }
+        club_staking_details().save(deps.storage, (&club_name, &staker), &stakes)?;
+        if !stakers.contains(&staker) {
+            stakers.push(staker);
+        }
+    }
+    // checkpoint the voting power of the existing stakes
+    for staker in stakers {
+        sync_voting_power(deps.storage, env.block.height, staker)?;
+    }
+    let all_bonds = club_bonding_details()
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for ((club_name, bonder), bonds) in all_bonds {
+        save_remaining_bonds(deps.storage, &club_name, &bonder, &bonds)?;
+    }
+    // the FURY reward moves into the rewards kept by denom
+    let fury = fury_denom(deps.storage)?;
+    match LEGACY_REWARD.may_load(deps.storage)? {
+        Some(reward) => {
+            add_reward(deps.storage, fury.clone(), reward)?;
+            LEGACY_REWARD.remove(deps.storage);
+        }
+        None => {}
+    }
+    match LEGACY_REWARD_GIVEN_IN_CURRENT_TIMESTAMP.may_load(deps.storage)? {
+        Some(reward_given) => {
+            REWARDS_GIVEN_IN_CURRENT_TIMESTAMP.save(
+                deps.storage,
+                denom_key(&fury),
+                &reward_given,
+            )?;
+            LEGACY_REWARD_GIVEN_IN_CURRENT_TIMESTAMP.remove(deps.storage);
+        }
+        None => {}
+    }
     Ok(Response::default())
 }
 pub fn uusd(deps: &DepsMut) -> Result<String, ContractError> {
@@ -193,18 +357,225 @@ fn received_message(
     info: MessageInfo,
     message: Cw20ReceiveMsg,
 ) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
     let msg: ReceivedMsg = from_binary(&message.msg)?;
     let amount = Uint128::from(message.amount);
+    let sender_info = MessageInfo {
+        sender: deps.api.addr_validate(&message.sender)?,
+        funds: info.funds,
+    };
+    // Any cw20 token can be sent as a partner reward, FURY goes to the staking reward
+    if msg == (ReceivedMsg::IncreasePartnerReward {}) && info.sender != config.fury_token_address {
+        return increase_partner_reward(
+            deps,
+            env,
+            sender_info,
+            vec![DenomAmount {
+                denom: Denom::Cw20(info.sender),
+                amount: amount,
+            }],
+        );
+    }
+    // Otherwise only the fury token can be sent to this contract
+    if info.sender != config.fury_token_address {
+        return Err(ContractError::Unauthorized {});
+    }
     match msg {
+        ReceivedMsg::IncreasePartnerReward {} => {
+            increase_reward_amount(deps, env, sender_info, message.sender.clone(), amount)
+        }
         ReceivedMsg::IncreaseRewardAmount(irac) => {
-            increase_reward_amount(deps, env, info, irac.reward_from, amount)
+            increase_reward_amount(deps, env, sender_info, irac.reward_from, amount)
+        }
+        ReceivedMsg::StakeOnAClub {
+            club_name,
+            auto_stake,
+            beneficiary,
+        } => received_stake_on_a_club(
+            deps,
+            env,
+            sender_info,
+            club_name,
+            amount,
+            auto_stake,
+            beneficiary,
+        ),
+        ReceivedMsg::BuyAClub { club_name } => {
+            received_buy_a_club(deps, env, sender_info, club_name, amount)
         }
+        ReceivedMsg::FundEmissionSchedule {
+            reward_per_second,
+            start_timestamp,
+            end_timestamp,
+        } => fund_emission_schedule(
+            deps,
+            env,
+            sender_info,
+            amount,
+            reward_per_second,
+            start_timestamp,
+            end_timestamp,
+        ),
     }
     // Err(ContractError::Std(StdError::GenericErr {
     //     msg: format!("received_message where msg = {:?}", msg),
     // }))
 }
 
+/// Fees for a cw20 send are paid in fury and taken out of the sent amount.
+fn required_fury_fees(
+    deps: Deps,
+    sender: &Addr,
+    action: FeeAction,
+    amount: Uint128,
+) -> StdResult<Uint128> {
+    let is_exempt = FEE_EXEMPT_ADDRESSES
+        .may_load(deps.storage, sender.to_string())?
+        .unwrap_or_default();
+    if is_exempt {
+        return Ok(Uint128::zero());
+    }
+    let fees = query_estimate_fees(deps, action, amount, None)?;
+    return Ok(fees.total_fees_fury);
+}
+
+fn received_stake_on_a_club(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    club_name: String,
+    amount: Uint128,
+    auto_stake: bool,
+    beneficiary: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    let staker;
+    match beneficiary {
+        Some(b) => staker = deps.api.addr_validate(&b)?.to_string(),
+        None => staker = info.sender.to_string(),
+    }
+
+    let fury_fees =
+        required_fury_fees(deps.as_ref(), &info.sender, FeeAction::StakeOnAClub, amount)?;
+    if amount <= fury_fees {
+        return Err(ContractError::InsufficientFees {
+            required: fury_fees,
+            received: amount,
+        });
+    }
+    let stake_amount = amount - fury_fees;
+
+    record_club_stake(
+        deps.storage,
+        env,
+        staker.clone(),
+        club_name.clone(),
+        stake_amount,
+        auto_stake,
+        None,
+    )?;
+
+    let mut rsp = Response::new();
+    if fury_fees != Uint128::zero() {
+        let fee_wallet = FEE_WALLET.load(deps.storage)?;
+        let transfer_msg = Cw20ExecuteMsg::Transfer {
+            recipient: fee_wallet,
+            amount: fury_fees,
+        };
+        let exec = WasmMsg::Execute {
+            contract_addr: config.fury_token_address.to_string(),
+            msg: to_binary(&transfer_msg).unwrap(),
+            funds: vec![],
+        };
+        rsp = rsp.add_message(exec);
+    }
+    let data_msg = format!("Club stake {} received", stake_amount).into_bytes();
+    return Ok(rsp
+        .add_attribute("action", "stake_on_a_club")
+        .add_attribute("staker", staker)
+        .add_attribute("club_name", club_name)
+        .add_attribute("stake", stake_amount.to_string())
+        .add_attribute("fees", fury_fees.to_string())
+        .set_data(data_msg));
+}
+
+fn received_buy_a_club(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    club_name: String,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let buyer = info.sender.to_string();
+    let price = club_price(deps.storage, club_name.clone())?;
+
+    let fury_fees = required_fury_fees(deps.as_ref(), &info.sender, FeeAction::BuyAClub, price)?;
+    let required_amount = price + fury_fees;
+    if amount < required_amount {
+        return Err(ContractError::InsufficientFees {
+            required: required_amount,
+            received: amount,
+        });
+    }
+
+    // A released club can only be bought from its current owner
+    let mut seller_opt = None;
+    match club_ownership_details().may_load(deps.storage, club_name.clone())? {
+        Some(od) => {
+            if od.owner_address != String::default() {
+                seller_opt = Some(od.owner_address);
+            }
+        }
+        None => {}
+    }
+
+    let nft_msg = record_club_purchase(
+        deps.branch(),
+        env,
+        buyer.clone(),
+        seller_opt,
+        club_name.clone(),
+        price,
+        false,
+    )?;
+
+    let fee_wallet = FEE_WALLET.load(deps.storage)?;
+    let transfer_msg = Cw20ExecuteMsg::Transfer {
+        recipient: fee_wallet,
+        amount: required_amount,
+    };
+    let exec = WasmMsg::Execute {
+        contract_addr: config.fury_token_address.to_string(),
+        msg: to_binary(&transfer_msg).unwrap(),
+        funds: vec![],
+    };
+    let mut rsp = Response::new().add_message(exec).add_messages(nft_msg);
+
+    // Anything sent over the price and fees goes back to the buyer
+    let refund = amount - required_amount;
+    if refund != Uint128::zero() {
+        let refund_msg = Cw20ExecuteMsg::Transfer {
+            recipient: buyer.clone(),
+            amount: refund,
+        };
+        let exec = WasmMsg::Execute {
+            contract_addr: config.fury_token_address.to_string(),
+            msg: to_binary(&refund_msg).unwrap(),
+            funds: vec![],
+        };
+        rsp = rsp.add_message(exec);
+    }
+    let data_msg = format!("Club fees {} received", price).into_bytes();
+    return Ok(rsp
+        .add_attribute("action", "buy_a_club")
+        .add_attribute("buyer", buyer)
+        .add_attribute("club_name", club_name)
+        .add_attribute("fees", price.to_string())
+        .set_data(data_msg));
+}
+
 fn change_config(
     deps: DepsMut,
     _env: Env,
@@ -239,6 +610,211 @@ fn change_fee_wallet(
         .add_attribute("fee_wallet", address))
 }
 
+fn add_fee_exempt_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let exempt_addr = deps.api.addr_validate(&address)?;
+
+    FEE_EXEMPT_ADDRESSES.save(deps.storage, exempt_addr.to_string(), &true)?;
+    Ok(Response::new()
+        .add_attribute("action", "add_fee_exempt_address")
+        .add_attribute("address", address))
+}
+
+fn remove_fee_exempt_address(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    FEE_EXEMPT_ADDRESSES.remove(deps.storage, address.clone());
+    Ok(Response::new()
+        .add_attribute("action", "remove_fee_exempt_address")
+        .add_attribute("address", address))
+}
+
+/// Platform fees to be paid by the sender for the given message,
+/// zero for addresses in the fee exemption list
+fn required_platform_fees(deps: Deps, sender: &Addr, msg: Binary) -> StdResult<Uint128> {
+    let is_exempt = FEE_EXEMPT_ADDRESSES
+        .may_load(deps.storage, sender.to_string())?
+        .unwrap_or_default();
+    if is_exempt {
+        return Ok(Uint128::zero());
+    }
+    query_platform_fees(deps, msg)
+}
+
+fn set_club_nft_contract(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    address: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let nft_contract = deps.api.addr_validate(&address)?;
+
+    CLUB_NFT_CONTRACT.save(deps.storage, &nft_contract)?;
+    Ok(Response::new()
+        .add_attribute("action", "set_club_nft_contract")
+        .add_attribute("club_nft_contract", address))
+}
+
+fn sync_club_owner(deps: DepsMut, env: Env, club_name: String) -> Result<Response, ContractError> {
+    let owner_changed = update_club_owner_from_nft(deps, env, club_name.clone())?;
+    Ok(Response::new()
+        .add_attribute("action", "sync_club_owner")
+        .add_attribute("club_name", club_name)
+        .add_attribute("owner_changed", owner_changed.to_string()))
+}
+
+/// Moves the club ownership to the current holder of the club token, if different.
+/// Rewards accrued by the outgoing owner are kept for them as previous owner rewards.
+fn update_club_owner_from_nft(
+    deps: DepsMut,
+    env: Env,
+    club_name: String,
+) -> Result<bool, ContractError> {
+    let holder;
+    match query_club_nft_owner(deps.as_ref(), club_name.clone())? {
+        Some(h) => holder = h,
+        None => return Ok(false),
+    }
+
+    let owner;
+    match club_ownership_details().may_load(deps.storage, club_name.clone())? {
+        Some(od) => owner = od,
+        None => return Ok(false),
+    }
+    if owner.owner_address == holder {
+        return Ok(false);
+    }
+
+    if owner.reward_amount != Uint128::zero() {
+        let mut previous_reward = Uint128::zero();
+        let pod =
+            CLUB_PREVIOUS_OWNER_DETAILS.may_load(deps.storage, owner.owner_address.clone())?;
+        match pod {
+            Some(pod) => {
+                previous_reward = pod.reward_amount;
+            }
+            None => {}
+        }
+        CLUB_PREVIOUS_OWNER_DETAILS.save(
+            deps.storage,
+            owner.owner_address.clone(),
+            &ClubPreviousOwnerDetails {
+                previous_owner_address: owner.owner_address.clone(),
+                reward_amount: previous_reward + owner.reward_amount,
+            },
+        )?;
+    }
+
+    club_ownership_details().save(
+        deps.storage,
+        club_name.clone(),
+        &ClubOwnershipDetails {
+            club_name: club_name.clone(),
+            start_timestamp: env.block.time,
+            locking_period: owner.locking_period,
+            owner_address: holder.clone(),
+            price_paid: owner.price_paid,
+            reward_amount: Uint128::zero(),
+            owner_released: false,
+            total_staked_amount: owner.total_staked_amount,
+            commission_rate: full_commission_rate(),
+        },
+    )?;
+    // the commission cooldown of the previous owner does not apply to the new owner
+    CLUB_COMMISSION_CHANGE_TIMESTAMP.remove(deps.storage, club_name.clone());
+
+    // the new holder needs a stake entry to receive owner rewards
+    let existing_stakes =
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &holder.clone()))?;
+    if existing_stakes.is_none() {
+        save_staking_details(
+            deps.storage,
+            env,
+            holder,
+            club_name,
+            Uint128::zero(),
+            DONT_CHANGE_AUTO_STAKE_SETTING,
+            INCREASE_STAKE,
+        )?;
+    }
+    Ok(true)
+}
+
+/// Mints the club token to the buyer, or transfers it if it already exists.
+/// A transfer requires the holder to have approved this contract for the club token
+/// (operator approvals are not looked at), otherwise the purchase is rejected.
+fn club_nft_message(
+    deps: DepsMut,
+    env: &Env,
+    club_name: String,
+    recipient: String,
+) -> StdResult<Option<CosmosMsg>> {
+    let nft_contract;
+    match CLUB_NFT_CONTRACT.may_load(deps.storage)? {
+        Some(nc) => nft_contract = nc,
+        None => return Ok(None),
+    }
+    let msg;
+    match query_club_nft_owner_of(deps.as_ref(), club_name.clone())? {
+        Some(owner_of) => {
+            if owner_of.owner == recipient {
+                return Ok(None);
+            }
+            let approved = owner_of
+                .approvals
+                .iter()
+                .any(|approval| approval.spender == env.contract.address.to_string());
+            if !approved {
+                return Err(StdError::GenericErr {
+                    msg: String::from(
+                        "Club token holder has not approved this contract to transfer the token",
+                    ),
+                });
+            }
+            msg = to_binary(&Cw721ExecuteMsg::TransferNft {
+                recipient: recipient,
+                token_id: club_name,
+            })?;
+        }
+        None => {
+            CLUB_NFT_MINTED.save(deps.storage, (&nft_contract, &club_name), &true)?;
+            msg = to_binary(&Cw721BaseExecuteMsg::<Extension>::Mint(MintMsg {
+                token_id: club_name,
+                owner: recipient,
+                token_uri: None,
+                extension: None,
+            }))?;
+        }
+    }
+    Ok(Some(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: nft_contract.to_string(),
+        msg: msg,
+        funds: vec![],
+    })))
+}
+
 fn claim_previous_owner_rewards(
     deps: DepsMut,
     info: MessageInfo,
@@ -297,7 +873,7 @@ fn claim_previous_owner_rewards(
 }
 
 fn claim_owner_rewards(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     owner: String,
@@ -311,8 +887,12 @@ fn claim_owner_rewards(
         return Err(ContractError::Unauthorized {});
     }
 
+    // rewards belong to whoever holds the club token now
+    update_club_owner_from_nft(deps.branch(), env, club_name.clone())?;
+
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -325,7 +905,12 @@ fn claim_owner_rewards(
     if !(ownership_details.is_none()) {
         for owner_detail in ownership_details {
             if owner_detail.owner_address == owner.clone() {
-                if Uint128::zero() == owner_detail.reward_amount {
+                let partner_commission_owed = OWNER_PARTNER_COMMISSION_OWED
+                    .may_load(deps.storage, (&club_name.clone(), &owner.clone()))?
+                    .unwrap_or_default();
+                if Uint128::zero() == owner_detail.reward_amount
+                    && partner_commission_owed.is_empty()
+                {
                     return Err(ContractError::Std(StdError::GenericErr {
                         msg: String::from("No rewards for this owner"),
                     }));
@@ -336,7 +921,7 @@ fn claim_owner_rewards(
                 amount = owner_detail.reward_amount;
 
                 // Now save the ownership details
-                CLUB_OWNERSHIP_DETAILS.save(
+                club_ownership_details().save(
                     deps.storage,
                     club_name.clone(),
                     &ClubOwnershipDetails {
@@ -348,6 +933,7 @@ fn claim_owner_rewards(
                         reward_amount: Uint128::zero(),
                         owner_released: owner_detail.owner_released,
                         total_staked_amount: owner_detail.total_staked_amount,
+                        commission_rate: owner_detail.commission_rate,
                     },
                 )?;
             }
@@ -359,12 +945,22 @@ fn claim_owner_rewards(
             msg: String::from("Not a valid owner for the club"),
         }));
     }
-    transfer_from_contract_to_wallet(
+    let mut rsp = Response::new().add_attribute("action", "owner_reward");
+    if amount > Uint128::zero() {
+        rsp = transfer_from_contract_to_wallet(
+            deps.storage,
+            owner.clone(),
+            amount,
+            "owner_reward".to_string(),
+        )?;
+    }
+    let partner_payouts = take_partner_rewards_owed(
         deps.storage,
-        owner.clone(),
-        amount,
-        "owner_reward".to_string(),
-    )
+        &OWNER_PARTNER_COMMISSION_OWED,
+        club_name,
+        owner,
+    )?;
+    return Ok(rsp.add_messages(partner_payouts));
 }
 
 fn periodically_refund_stakeouts(
@@ -379,7 +975,7 @@ fn periodically_refund_stakeouts(
 }
 
 fn buy_a_club(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     buyer: String,
@@ -393,39 +989,19 @@ fn buy_a_club(
     }
 
     println!("seller_opt = {:?}", seller_opt);
-    let seller;
-    match seller_opt.clone() {
-        Some(s) => seller = s,
-        None => seller = String::default(),
-    }
 
     let config = CONFIG.load(deps.storage)?;
 
-    let club_price = config.club_price;
-    if price != club_price {
-        return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("Club price is not matching"),
-        }));
-    }
-
-    let required_ust_fees: Uint128;
-    //To bypass calls from unit tests
-    if info.sender.clone().into_string() == String::from("owner001")
-        || info.sender.clone().into_string() == String::from("owner002")
-        || info.sender.clone().into_string() == String::from("owner003")
-    {
-        required_ust_fees = Uint128::zero();
-    } else {
-        required_ust_fees = query_platform_fees(
-            deps.as_ref(),
-            to_binary(&ExecuteMsg::BuyAClub {
-                buyer: buyer.clone(),
-                club_name: club_name.clone(),
-                seller: seller_opt,
-                auto_stake: auto_stake,
-            })?,
-        )?;
-    }
+    let required_ust_fees = required_platform_fees(
+        deps.as_ref(),
+        &info.sender,
+        to_binary(&ExecuteMsg::BuyAClub {
+            buyer: buyer.clone(),
+            club_name: club_name.clone(),
+            seller: seller_opt.clone(),
+            auto_stake: auto_stake,
+        })?,
+    )?;
     let mut fees = Uint128::zero();
     for fund in info.funds.clone() {
         if fund.denom == uusd(&deps)? {
@@ -440,10 +1016,84 @@ fn buy_a_club(
             received: fees,
         });
     }
+
+    let nft_msg = record_club_purchase(
+        deps.branch(),
+        env,
+        buyer.clone(),
+        seller_opt,
+        club_name.clone(),
+        price,
+        auto_stake,
+    )?;
+
+    let fee_wallet = FEE_WALLET.load(deps.storage)?;
+
+    let transfer_msg = Cw20ExecuteMsg::TransferFrom {
+        owner: info.sender.into_string(),
+        recipient: fee_wallet,
+        amount: price,
+    };
+    let exec = WasmMsg::Execute {
+        contract_addr: config.fury_token_address.to_string(),
+        msg: to_binary(&transfer_msg).unwrap(),
+        funds: vec![],
+    };
+
+    let fee_wallet = FEE_WALLET.load(deps.storage)?;
+
+    let send_wasm: CosmosMsg = CosmosMsg::Wasm(exec);
+    let send_bank: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: fee_wallet,
+        amount: info.funds,
+    });
+    let data_msg = format!("Club fees {} received", price).into_bytes();
+    return Ok(Response::new()
+        .add_message(send_wasm)
+        .add_message(send_bank)
+        .add_messages(nft_msg)
+        .add_attribute("action", "buy_a_club")
+        .add_attribute("buyer", buyer)
+        .add_attribute("club_name", club_name)
+        .add_attribute("fees", price.to_string())
+        .set_data(data_msg));
+}
+
+/// Checks that the club can be bought and records the buyer as its new owner.
+/// Returns the message moving the club token to the buyer, if a token contract is set.
+fn record_club_purchase(
+    mut deps: DepsMut,
+    env: Env,
+    buyer: String,
+    seller_opt: Option<String>,
+    club_name: String,
+    price: Uint128,
+    auto_stake: bool,
+) -> Result<Option<CosmosMsg>, ContractError> {
+    let seller;
+    match seller_opt.clone() {
+        Some(s) => seller = s,
+        None => seller = String::default(),
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+
+    ensure_club_registered(deps.storage, club_name.clone())?;
+
+    update_club_owner_from_nft(deps.branch(), env.clone(), club_name.clone())?;
+
+    let club_price = club_price(deps.storage, club_name.clone())?;
+    if price != club_price {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Club price is not matching"),
+        }));
+    }
+
     let buyer_addr = deps.api.addr_validate(&buyer)?;
 
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -453,19 +1103,16 @@ fn buy_a_club(
         }
     }
 
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
+    let clubs_owned_by_buyer = club_ownership_details()
+        .idx
+        .owner
+        .prefix(buyer.clone())
         .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-
-    for one_club_name in all_clubs {
-        let one_ownership_details =
-            CLUB_OWNERSHIP_DETAILS.load(deps.storage, one_club_name.clone())?;
-        if buyer == one_ownership_details.owner_address {
-            return Err(ContractError::Std(StdError::GenericErr {
-                msg: String::from("buyer already owns this club"),
-            }));
-        }
+        .next();
+    if clubs_owned_by_buyer.is_some() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("buyer already owns this club"),
+        }));
     }
 
     let mut previous_owners_reward_amount = Uint128::from(0u128);
@@ -530,7 +1177,7 @@ fn buy_a_club(
     }
 
     // Now save the ownership details
-    CLUB_OWNERSHIP_DETAILS.save(
+    club_ownership_details().save(
         deps.storage,
         club_name.clone(),
         &ClubOwnershipDetails {
@@ -542,13 +1189,16 @@ fn buy_a_club(
             reward_amount: Uint128::from(CLUB_BUYING_REWARD_AMOUNT),
             owner_released: false,
             total_staked_amount: total_staked_amount,
+            commission_rate: full_commission_rate(),
         },
     )?;
+    // the commission cooldown of the previous owner does not apply to the new owner
+    CLUB_COMMISSION_CHANGE_TIMESTAMP.remove(deps.storage, club_name.clone());
 
     let mut stakes = Vec::new();
     let mut user_stake_exists = false;
     let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &buyer.clone()))?;
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &buyer.clone()))?;
     match all_stakes {
         Some(some_stakes) => {
             stakes = some_stakes;
@@ -564,7 +1214,7 @@ fn buy_a_club(
         // Now save the staking details for the owner - with 0 stake
         save_staking_details(
             deps.storage,
-            env,
+            env.clone(),
             buyer.clone(),
             club_name.clone(),
             Uint128::zero(),
@@ -573,39 +1223,12 @@ fn buy_a_club(
         )?;
     }
 
-    let fee_wallet = FEE_WALLET.load(deps.storage)?;
-
-    let transfer_msg = Cw20ExecuteMsg::TransferFrom {
-        owner: info.sender.into_string(),
-        recipient: fee_wallet,
-        amount: price,
-    };
-    let exec = WasmMsg::Execute {
-        contract_addr: config.fury_token_address.to_string(),
-        msg: to_binary(&transfer_msg).unwrap(),
-        funds: vec![],
-    };
-
-    let fee_wallet = FEE_WALLET.load(deps.storage)?;
-
-    let send_wasm: CosmosMsg = CosmosMsg::Wasm(exec);
-    let send_bank: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: fee_wallet,
-        amount: info.funds,
-    });
-    let data_msg = format!("Club fees {} received", price).into_bytes();
-    return Ok(Response::new()
-        .add_message(send_wasm)
-        .add_message(send_bank)
-        .add_attribute("action", "buy_a_club")
-        .add_attribute("buyer", buyer)
-        .add_attribute("club_name", club_name)
-        .add_attribute("fees", price.to_string())
-        .set_data(data_msg));
+    let nft_msg = club_nft_message(deps.branch(), &env, club_name.clone(), buyer.clone())?;
+    return Ok(nft_msg);
 }
 
 fn assign_a_club(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     buyer: String,
@@ -618,6 +1241,10 @@ fn assign_a_club(
         return Err(ContractError::Unauthorized {});
     }
 
+    ensure_club_registered(deps.storage, club_name.clone())?;
+
+    update_club_owner_from_nft(deps.branch(), env.clone(), club_name.clone())?;
+
     println!("seller_opt = {:?}", seller_opt);
     let seller;
     match seller_opt.clone() {
@@ -628,7 +1255,8 @@ fn assign_a_club(
     let buyer_addr = deps.api.addr_validate(&buyer)?;
 
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -638,19 +1266,16 @@ fn assign_a_club(
         }
     }
 
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
+    let clubs_owned_by_buyer = club_ownership_details()
+        .idx
+        .owner
+        .prefix(buyer.clone())
         .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-
-    for one_club_name in all_clubs {
-        let one_ownership_details =
-            CLUB_OWNERSHIP_DETAILS.load(deps.storage, one_club_name.clone())?;
-        if buyer == one_ownership_details.owner_address {
-            return Err(ContractError::Std(StdError::GenericErr {
-                msg: String::from("buyer already owns this club"),
-            }));
-        }
+        .next();
+    if clubs_owned_by_buyer.is_some() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("buyer already owns this club"),
+        }));
     }
 
     let mut previous_owners_reward_amount = Uint128::from(0u128);
@@ -715,7 +1340,7 @@ fn assign_a_club(
     }
 
     // Now save the ownership details
-    CLUB_OWNERSHIP_DETAILS.save(
+    club_ownership_details().save(
         deps.storage,
         club_name.clone(),
         &ClubOwnershipDetails {
@@ -727,13 +1352,16 @@ fn assign_a_club(
             reward_amount: Uint128::from(CLUB_BUYING_REWARD_AMOUNT),
             owner_released: false,
             total_staked_amount: total_staked_amount,
+            commission_rate: full_commission_rate(),
         },
     )?;
+    // the commission cooldown of the previous owner does not apply to the new owner
+    CLUB_COMMISSION_CHANGE_TIMESTAMP.remove(deps.storage, club_name.clone());
 
     let mut stakes = Vec::new();
     let mut user_stake_exists = false;
     let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &buyer.clone()))?;
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &buyer.clone()))?;
     match all_stakes {
         Some(some_stakes) => {
             stakes = some_stakes;
@@ -749,7 +1377,7 @@ fn assign_a_club(
         // Now save the staking details for the owner - with 0 stake
         save_staking_details(
             deps.storage,
-            env,
+            env.clone(),
             buyer.clone(),
             club_name.clone(),
             Uint128::zero(),
@@ -758,7 +1386,8 @@ fn assign_a_club(
         )?;
     }
 
-    return Ok(Response::default());
+    let nft_msg = club_nft_message(deps.branch(), &env, club_name, buyer)?;
+    return Ok(Response::new().add_messages(nft_msg));
 }
 
 #[entry_point]
@@ -769,7 +1398,7 @@ pub fn reply(deps: DepsMut, env: Env, reply: Reply) -> Result<Response, Contract
 }
 
 fn release_club(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     seller: String,
@@ -780,8 +1409,12 @@ fn release_club(
     if seller_addr != info.sender {
         return Err(ContractError::Unauthorized {});
     }
+
+    // only the current token holder can release the club
+    update_club_owner_from_nft(deps.branch(), env.clone(), club_name.clone())?;
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -804,7 +1437,7 @@ fn release_club(
             }));
         } else {
             // Update the ownership details
-            CLUB_OWNERSHIP_DETAILS.save(
+            club_ownership_details().save(
                 deps.storage,
                 club_name.clone(),
                 &ClubOwnershipDetails {
@@ -816,6 +1449,7 @@ fn release_club(
                     reward_amount: owner.reward_amount,
                     owner_released: true,
                     total_staked_amount: owner.total_staked_amount,
+                    commission_rate: owner.commission_rate,
                 },
             )?;
         }
@@ -831,6 +1465,7 @@ fn stake_on_a_club(
     club_name: String,
     amount: Uint128,
     auto_stake: bool,
+    lock_tier: Option<LockTier>,
 ) -> Result<Response, ContractError> {
     if info.sender != staker {
         return Err(ContractError::Unauthorized {});
@@ -841,27 +1476,17 @@ fn stake_on_a_club(
     let staker_addr = deps.api.addr_validate(&staker)?;
     let contract_address = env.clone().contract.address.into_string();
 
-    let required_ust_fees: Uint128;
-    //To bypass calls from unit tests
-    if info.sender.clone().into_string() == String::from("staker001")
-        || info.sender.clone().into_string() == String::from("staker002")
-        || info.sender.clone().into_string() == String::from("staker003")
-        || info.sender.clone().into_string() == String::from("staker004")
-        || info.sender.clone().into_string() == String::from("staker005")
-        || info.sender.clone().into_string() == String::from("staker006")
-    {
-        required_ust_fees = Uint128::zero();
-    } else {
-        required_ust_fees = query_platform_fees(
-            deps.as_ref(),
-            to_binary(&ExecuteMsg::StakeOnAClub {
-                staker: staker.clone(),
-                club_name: club_name.clone(),
-                amount: amount,
-                auto_stake: auto_stake,
-            })?,
-        )?;
-    }
+    let required_ust_fees = required_platform_fees(
+        deps.as_ref(),
+        &info.sender,
+        to_binary(&ExecuteMsg::StakeOnAClub {
+            staker: staker.clone(),
+            club_name: club_name.clone(),
+            amount: amount,
+            auto_stake: auto_stake,
+            lock_tier: lock_tier,
+        })?,
+    )?;
     let mut fees = Uint128::zero();
     for fund in info.funds.clone() {
         if fund.denom == uusd(&deps)? {
@@ -877,35 +1502,15 @@ fn stake_on_a_club(
         });
     }
 
-    //check if the club_name is available for staking
-    let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
-    match ownership_details_result {
-        Ok(od) => {
-            ownership_details = od;
-        }
-        Err(e) => {
-            return Err(ContractError::Std(StdError::GenericErr {
-                msg: String::from("Cannot find the club"),
-            }));
-        }
-    }
-    if ownership_details.is_some() {
-        // Now save the staking details
-        save_staking_details(
-            deps.storage,
-            env,
-            staker.clone(),
-            club_name.clone(),
-            amount,
-            auto_stake,
-            INCREASE_STAKE,
-        )?;
-    } else {
-        return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("The club is not available for staking"),
-        }));
-    }
+    record_club_stake(
+        deps.storage,
+        env.clone(),
+        staker.clone(),
+        club_name.clone(),
+        amount,
+        auto_stake,
+        lock_tier,
+    )?;
 
     let transfer_msg = Cw20ExecuteMsg::TransferFrom {
         owner: info.sender.into_string(),
@@ -936,6 +1541,55 @@ fn stake_on_a_club(
         .set_data(data_msg));
 }
 
+fn record_club_stake(
+    storage: &mut dyn Storage,
+    env: Env,
+    staker: String,
+    club_name: String,
+    amount: Uint128,
+    auto_stake: bool,
+    lock_tier: Option<LockTier>,
+) -> Result<Response, ContractError> {
+    ensure_club_registered(storage, club_name.clone())?;
+
+    //check if the club_name is available for staking
+    let ownership_details;
+    let ownership_details_result = club_ownership_details().may_load(storage, club_name.clone());
+    match ownership_details_result {
+        Ok(od) => {
+            ownership_details = od;
+        }
+        Err(e) => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Cannot find the club"),
+            }));
+        }
+    }
+    if ownership_details.is_some() {
+        // Now save the staking details
+        save_staking_details(
+            storage,
+            env.clone(),
+            staker.clone(),
+            club_name.clone(),
+            amount,
+            auto_stake,
+            INCREASE_STAKE,
+        )?;
+        match lock_tier {
+            Some(tier) => {
+                lock_stake(storage, env, staker.clone(), club_name.clone(), tier)?;
+            }
+            None => {}
+        }
+    } else {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("The club is not available for staking"),
+        }));
+    }
+    return Ok(Response::default());
+}
+
 fn assign_stakes_to_a_club(
     deps: DepsMut,
     env: Env,
@@ -948,6 +1602,7 @@ fn assign_stakes_to_a_club(
         return Err(ContractError::Unauthorized {});
     }
     let contract_address = env.clone().contract.address.into_string();
+    ensure_club_registered(deps.storage, club_name.clone())?;
 
     for stake in stake_list.clone() {
         if stake.club_name != club_name {
@@ -959,7 +1614,8 @@ fn assign_stakes_to_a_club(
 
     //check if the club_name is available for staking
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -1035,7 +1691,8 @@ fn withdraw_stake_from_a_club(
 
     //check if the club_name is available for staking
     let ownership_details;
-    let ownership_details_result = CLUB_OWNERSHIP_DETAILS.may_load(deps.storage, club_name.clone());
+    let ownership_details_result =
+        club_ownership_details().may_load(deps.storage, club_name.clone());
     match ownership_details_result {
         Ok(od) => {
             ownership_details = od;
@@ -1045,27 +1702,16 @@ fn withdraw_stake_from_a_club(
         }
     }
 
-    let required_ust_fees: Uint128;
-    //To bypass calls from unit tests
-    if info.sender.clone().into_string() == String::from("staker001")
-        || info.sender.clone().into_string() == String::from("staker002")
-        || info.sender.clone().into_string() == String::from("staker003")
-        || info.sender.clone().into_string() == String::from("staker004")
-        || info.sender.clone().into_string() == String::from("staker005")
-        || info.sender.clone().into_string() == String::from("staker006")
-    {
-        required_ust_fees = Uint128::zero();
-    } else {
-        required_ust_fees = query_platform_fees(
-            deps.as_ref(),
-            to_binary(&ExecuteMsg::StakeWithdrawFromAClub {
-                staker: staker.clone(),
-                club_name: club_name.clone(),
-                amount: withdrawal_amount,
-                immediate_withdrawal,
-            })?,
-        )?;
-    }
+    let required_ust_fees = required_platform_fees(
+        deps.as_ref(),
+        &info.sender,
+        to_binary(&ExecuteMsg::StakeWithdrawFromAClub {
+            staker: staker.clone(),
+            club_name: club_name.clone(),
+            amount: withdrawal_amount,
+            immediate_withdrawal,
+        })?,
+    )?;
     let mut fees = Uint128::zero();
     for fund in info.funds.clone() {
         if fund.denom == uusd(&deps)? {
@@ -1083,7 +1729,7 @@ fn withdraw_stake_from_a_club(
 
     let mut stakes = Vec::new();
     let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
     match all_stakes {
         Some(some_stakes) => {
             stakes = some_stakes;
@@ -1096,12 +1742,18 @@ fn withdraw_stake_from_a_club(
     }
     let mut user_stake_exists = false;
     let mut withdrawal_amount_in_excess = false;
+    let mut stake_is_locked = false;
+    let mut staked_amount = Uint128::zero();
     for stake in stakes {
         if staker == stake.staker_address {
             user_stake_exists = true;
+            staked_amount = stake.staked_amount;
             if stake.staked_amount < withdrawal_amount {
                 withdrawal_amount_in_excess = true;
             }
+            if env.block.time < stake.lock_end_timestamp {
+                stake_is_locked = true;
+            }
         }
     }
     if !user_stake_exists {
@@ -1113,6 +1765,8 @@ fn withdraw_stake_from_a_club(
     let mut transfer_confirmed = false;
     let mut action = "withdraw_stake".to_string();
     let mut burn_amount = Uint128::zero();
+    let mut lock_penalty = Uint128::zero();
+    let mut reward_pool_penalty = Uint128::zero();
     if ownership_details.is_some() {
         let owner = ownership_details.unwrap();
         let mut unbonded_amount = Uint128::zero();
@@ -1120,124 +1774,81 @@ fn withdraw_stake_from_a_club(
         let mut amount_remaining = withdrawal_amount.clone();
 
         if immediate_withdrawal == IMMEDIATE_WITHDRAWAL {
-            // parse bonding to check maturity and sort with descending order of timestamp
-            let mut bonds = Vec::new();
-            let mut all_bonds = CLUB_BONDING_DETAILS
-                .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
-            let mut s_bonds = Vec::new();
-            match all_bonds {
-                Some(some_bonds) => {
-                    bonds = some_bonds;
-                    for bond in bonds {
-                        s_bonds.push((bond.bonding_start_timestamp.seconds(), bond.clone()));
-                    }
+            let early_exit = EARLY_EXIT_PENALTY.may_load(deps.storage)?;
+
+            // matured bonds are paid first, then bonds still unbonding if early exit is allowed
+            let (matured_amount, unbonding_amount, remaining_bonds) = take_from_unbonding_queue(
+                deps.storage,
+                env.clone(),
+                staker.clone(),
+                club_name.clone(),
+                withdrawal_amount,
+                early_exit.is_some(),
+            )?;
+            unbonded_amount = matured_amount;
+            bonded_amount = unbonding_amount;
+            amount_remaining = withdrawal_amount - unbonded_amount - bonded_amount;
+
+            // whatever is left comes directly from the stake
+            let mut early_exit_amount = bonded_amount;
+            if amount_remaining > Uint128::zero() {
+                if early_exit.is_none() {
+                    println!("Not Sufficient Matured Unstaked Bonds");
+                    return Err(ContractError::Std(StdError::GenericErr {
+                        msg: String::from("Not Sufficient Matured Unstaked Bonds"),
+                    }));
+                }
+                if staked_amount < amount_remaining {
+                    return Err(ContractError::Std(StdError::GenericErr {
+                        msg: String::from("Excess amount demanded for unstaking"),
+                    }));
+                }
+                // a locked stake pays the early unlock penalty instead of the early exit penalty
+                if !stake_is_locked {
+                    early_exit_amount += amount_remaining;
                 }
-                None => {}
             }
 
-            //  sort using first element, ie timestamp
-            s_bonds.sort_by(|a, b| b.0.cmp(&a.0));
+            save_remaining_bonds(deps.storage, &club_name, &staker, &remaining_bonds)?;
 
-            let existing_bonds = s_bonds.clone();
-            let mut updated_bonds = Vec::new();
+            if amount_remaining > Uint128::zero() {
+                // update the staking details
+                save_staking_details(
+                    deps.storage,
+                    env.clone(),
+                    staker.clone(),
+                    club_name.clone(),
+                    amount_remaining,
+                    DONT_CHANGE_AUTO_STAKE_SETTING,
+                    DECREASE_STAKE,
+                )?;
+                // Stake withdrawn before its lock ends pays a penalty into the reward pool
+                if stake_is_locked {
+                    lock_penalty =
+                        amount_remaining.multiply_ratio(EARLY_UNLOCK_PENALTY, HUNDRED_PERCENT);
+                    reward_pool_penalty += lock_penalty;
+                }
+            }
 
-            // PRE-MATURITY BOND are extracted here
-            // let mut bonded_bonds = Vec::new();amount_remaining
-
-            for bond in existing_bonds {
-                let mut updated_bond = bond.1.clone();
-                if staker_addr == bond.1.bonder_address {
-                    println!(
-                        "staker {:?} timestamp  {:?} amount {:?}",
-                        staker_addr, bond.1.bonding_start_timestamp, bond.1.bonded_amount
-                    );
-                    if bond.1.bonding_start_timestamp
-                        < env.block.time.minus_seconds(bond.1.bonding_duration)
-                    {
-                        if amount_remaining > Uint128::zero() {
-                            if bond.1.bonded_amount > amount_remaining {
-                                unbonded_amount
-                                    .checked_add(amount_remaining)
-                                    .unwrap_or_default();
-                                updated_bond
-                                    .bonded_amount
-                                    .checked_sub(amount_remaining)
-                                    .unwrap_or_default();
-                                amount_remaining = Uint128::zero();
-                                updated_bonds.push(updated_bond);
-                            } else {
-                                unbonded_amount
-                                    .checked_add(bond.1.bonded_amount)
-                                    .unwrap_or_default();
-                                amount_remaining
-                                    .checked_sub(bond.1.bonded_amount)
-                                    .unwrap_or_default();
-                            }
-                        } else {
-                            updated_bonds.push(updated_bond);
+            match early_exit {
+                Some(ee) => {
+                    let early_exit_penalty =
+                        early_exit_amount.multiply_ratio(ee.penalty, HUNDRED_PERCENT);
+                    match ee.destination {
+                        PenaltyDestination::Burn => burn_amount = early_exit_penalty,
+                        PenaltyDestination::RewardPool => {
+                            reward_pool_penalty += early_exit_penalty;
                         }
-                    } else {
-                        // PRE-MATURITY BOND ENCASH AT DISCOUNT - enable the following line
-                        // bonded_bonds.push(updated_bond);
-                        // PRE-MATURITY BOND ENCASH AT DISCOUNT - bypased or masked using this line
-                        updated_bonds.push(updated_bond);
                     }
-                } else {
-                    updated_bonds.push(updated_bond);
                 }
+                None => {}
+            }
+            if reward_pool_penalty > Uint128::zero() {
+                let fury = fury_denom(deps.storage)?;
+                add_reward(deps.storage, fury, reward_pool_penalty)?;
             }
 
-            // // This section Checks the Pre-Maturity Bonds for possible encashment
-            // for bond in bonded_bonds {
-            //     let mut updated_bond = bond.clone();
-            //     if amount_remaining > Uint128::zero() {
-            //         if bond.bonded_amount > amount_remaining {
-            //             bonded_amount = amount_remaining;
-            //             updated_bond.bonded_amount -= amount_remaining;
-            //             amount_remaining = Uint128::zero();
-            //             updated_bonds.push(updated_bond);
-            //         } else {
-            //             bonded_amount += bond.bonded_amount;
-            //             amount_remaining -= bond.bonded_amount;
-            //         }
-            //     } else {
-            //         updated_bonds.push(updated_bond);
-            //     }
-            // }
-
-            CLUB_BONDING_DETAILS.save(
-                deps.storage,
-                (&club_name.clone(), &staker.clone()),
-                &updated_bonds,
-            )?;
-
-            // update the staking details
-            save_staking_details(
-                deps.storage,
-                env.clone(),
-                staker.clone(),
-                club_name.clone(),
-                (withdrawal_amount - unbonded_amount) - bonded_amount,
-                DONT_CHANGE_AUTO_STAKE_SETTING,
-                DECREASE_STAKE,
-            )?;
-
-            // // PRE-MATURITY Withdrawal directly from Basic Stake , not even into Bonding - commented out to bypass
-            if withdrawal_amount > unbonded_amount {
-                println!("Not Sufficient Matured Unstaked Bonds");
-                // // Deduct 10% and burn it
-                //     burn_amount = (withdrawal_amount - unbonded_amount)
-                //         .checked_mul(Uint128::from(10u128))
-                //         .unwrap_or_default()
-                //         .checked_div(Uint128::from(100u128))
-                //         .unwrap_or_default();
-                return Err(ContractError::Std(StdError::GenericErr {
-                    msg: String::from("Not Sufficient Matured Unstaked Bonds"),
-                }));
-            };
-
-            // // Continue if reached here
-            // Remaining 90% transfer to staker wallet
+            // Continue if reached here, the rest less penalties is transferred to staker wallet
             transfer_confirmed = true;
         } else {
             if withdrawal_amount_in_excess {
@@ -1246,7 +1857,7 @@ fn withdraw_stake_from_a_club(
                 }));
             }
 
-            let all_bonds = CLUB_BONDING_DETAILS
+            let all_bonds = club_bonding_details()
                 .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?
                 .unwrap_or_default();
             let bonds_for_staker = all_bonds.len() as u64;
@@ -1269,13 +1880,22 @@ fn withdraw_stake_from_a_club(
                 DECREASE_STAKE,
             )?;
 
+            // Stake withdrawn before its lock ends pays a penalty into the reward pool
+            if stake_is_locked {
+                lock_penalty =
+                    withdrawal_amount.multiply_ratio(EARLY_UNLOCK_PENALTY, HUNDRED_PERCENT);
+                let fury = fury_denom(deps.storage)?;
+                add_reward(deps.storage, fury, lock_penalty)?;
+            }
+            let bonded_amount = withdrawal_amount - lock_penalty;
+
             // Move the withdrawn stakes to bonding list
             save_bonding_details(
                 deps.storage,
                 env.clone(),
                 staker.clone(),
                 club_name.clone(),
-                withdrawal_amount,
+                bonded_amount,
                 config.bonding_duration,
             )?;
 
@@ -1288,11 +1908,12 @@ fn withdraw_stake_from_a_club(
             });
 
             // early exit with only state change and platform fee transfer - no token exchange
-            let data_msg = format!("Amount {} bonded", withdrawal_amount).into_bytes();
+            let data_msg = format!("Amount {} bonded", bonded_amount).into_bytes();
             rsp = rsp
                 .add_message(send_bank)
                 .add_attribute("action", action)
-                .add_attribute("bonded", withdrawal_amount.clone().to_string())
+                .add_attribute("bonded", bonded_amount.to_string())
+                .add_attribute("lock_penalty", lock_penalty.to_string())
                 .add_attribute("staker", staker)
                 .add_attribute("club_name", club_name)
                 .set_data(data_msg);
@@ -1327,9 +1948,12 @@ fn withdraw_stake_from_a_club(
             .add_message(burn_wasm)
             .add_attribute("burnt", burn_amount.to_string());
     }
+    if reward_pool_penalty > Uint128::zero() {
+        rsp = rsp.add_attribute("penalty_to_reward", reward_pool_penalty.to_string());
+    }
     let transfer_msg = Cw20ExecuteMsg::Transfer {
         recipient: staker.clone(),
-        amount: withdrawal_amount - burn_amount,
+        amount: withdrawal_amount - burn_amount - reward_pool_penalty,
     };
     let exec = WasmMsg::Execute {
         contract_addr: config.fury_token_address.to_string(),
@@ -1369,7 +1993,7 @@ fn save_staking_details(
     // Get the exising stakes for this club
     let mut stakes = Vec::new();
     let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(storage, (&club_name.clone(), &staker.clone()))?;
+        club_staking_details().may_load(storage, (&club_name.clone(), &staker.clone()))?;
     match all_stakes {
         Some(some_stakes) => {
             stakes = some_stakes;
@@ -1379,6 +2003,7 @@ fn save_staking_details(
 
     // if already staked for this club, then increase or decrease the staked_amount in existing stake
     let mut already_staked = false;
+    let mut compounded_reward = Uint128::zero();
     let existing_stakes = stakes.clone();
     let mut updated_stakes = Vec::new();
     for stake in existing_stakes {
@@ -1387,7 +2012,18 @@ fn save_staking_details(
             if increase_stake == INCREASE_STAKE {
                 updated_stake.staked_amount += amount;
                 updated_stake.auto_stake = auto_stake;
+                // stake added to an active lock is locked for a full tier duration as well
+                if !amount.is_zero()
+                    && stake.lock_tier != LockTier::NoLock
+                    && env.block.time < stake.lock_end_timestamp
+                {
+                    updated_stake.lock_end_timestamp = env
+                        .block
+                        .time
+                        .plus_seconds(lock_tier_duration(stake.lock_tier));
+                }
                 if auto_stake == SET_AUTO_STAKE {
+                    compounded_reward += updated_stake.reward_amount;
                     updated_stake.staked_amount += updated_stake.reward_amount;
                     updated_stake.reward_amount = Uint128::zero();
                 }
@@ -1400,13 +2036,14 @@ fn save_staking_details(
                     }));
                 }
             }
+            update_club_lock_bonus(storage, &club_name, &stake, &updated_stake)?;
             already_staked = true;
         }
         updated_stakes.push(updated_stake);
     }
     if already_staked == true {
         // save the modified stakes - with updation or removal of existing stake
-        CLUB_STAKING_DETAILS.save(
+        club_staking_details().save(
             storage,
             (&club_name.clone(), &staker.clone()),
             &updated_stakes,
@@ -1420,19 +2057,32 @@ fn save_staking_details(
             club_name: club_name.clone(),
             reward_amount: Uint128::from(CLUB_STAKING_REWARD_AMOUNT), // ensure that the first time reward amount is set to 0
             auto_stake: auto_stake,
+            lock_tier: LockTier::NoLock,
+            lock_end_timestamp: env.block.time,
         });
-        CLUB_STAKING_DETAILS.save(storage, (&club_name.clone(), &staker.clone()), &stakes)?;
+        club_staking_details().save(storage, (&club_name.clone(), &staker.clone()), &stakes)?;
     }
 
     // Now update the total stake for this club
-    let owner = CLUB_OWNERSHIP_DETAILS.load(storage, club_name.clone())?;
+    let owner = club_ownership_details().load(storage, club_name.clone())?;
     let mut total_staked_amount = owner.total_staked_amount;
     if increase_stake == INCREASE_STAKE {
-        total_staked_amount += amount;
+        let stake_increase = amount + compounded_reward;
+        if !club_stake_limit_allows(
+            storage,
+            club_name.clone(),
+            total_staked_amount,
+            stake_increase,
+        )? {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club stake limit reached"),
+            }));
+        }
+        total_staked_amount += stake_increase;
     } else {
         total_staked_amount -= amount;
     }
-    CLUB_OWNERSHIP_DETAILS.save(
+    club_ownership_details().save(
         storage,
         club_name.clone(),
         &ClubOwnershipDetails {
@@ -1444,12 +2094,167 @@ fn save_staking_details(
             reward_amount: owner.reward_amount,
             owner_released: owner.owner_released,
             total_staked_amount: total_staked_amount,
+            commission_rate: owner.commission_rate,
         },
     )?;
+    sync_voting_power(storage, env.block.height, staker.clone())?;
+
+    return Ok(Response::default());
+}
+
+/// Checkpoints the stake of the staker across all clubs as its voting power,
+/// and the change in the total voting power
+fn sync_voting_power(storage: &mut dyn Storage, height: u64, staker: String) -> StdResult<()> {
+    let all_stakes = club_staking_details()
+        .idx
+        .staker
+        .prefix(staker.clone())
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    let mut power = Uint128::zero();
+    for (_, stakes) in all_stakes {
+        for stake in stakes {
+            if stake.staker_address == staker {
+                power += stake.staked_amount;
+            }
+        }
+    }
+    let previous_power = STAKED_BALANCES
+        .may_load(storage, staker.clone())?
+        .unwrap_or_default();
+    if power == previous_power {
+        return Ok(());
+    }
+    STAKED_BALANCES.save(storage, staker, &power, height)?;
+    let total_power = STAKED_TOTAL.may_load(storage)?.unwrap_or_default();
+    STAKED_TOTAL.save(storage, &(total_power + power - previous_power), height)?;
+    Ok(())
+}
+
+/// Locks the stake of the staker in the club for the lock tier duration.
+/// An active lock can be extended or moved to a longer tier, but not shortened.
+fn lock_stake(
+    storage: &mut dyn Storage,
+    env: Env,
+    staker: String,
+    club_name: String,
+    lock_tier: LockTier,
+) -> Result<Response, ContractError> {
+    let stakes = club_staking_details()
+        .may_load(storage, (&club_name.clone(), &staker.clone()))?
+        .unwrap_or_default();
 
+    let mut updated_stakes = Vec::new();
+    for stake in stakes {
+        let mut updated_stake = stake.clone();
+        if staker == stake.staker_address {
+            let lock_is_active = env.block.time < stake.lock_end_timestamp;
+            if lock_is_active && lock_tier_duration(lock_tier) < lock_tier_duration(stake.lock_tier)
+            {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Cannot shorten an active stake lock"),
+                }));
+            }
+            let new_lock_end = env.block.time.plus_seconds(lock_tier_duration(lock_tier));
+            if !lock_is_active || new_lock_end > stake.lock_end_timestamp {
+                updated_stake.lock_end_timestamp = new_lock_end;
+            }
+            updated_stake.lock_tier = lock_tier;
+            updated_stake.staking_duration = lock_tier_duration(lock_tier);
+            update_club_lock_bonus(storage, &club_name, &stake, &updated_stake)?;
+        }
+        updated_stakes.push(updated_stake);
+    }
+    club_staking_details().save(
+        storage,
+        (&club_name.clone(), &staker.clone()),
+        &updated_stakes,
+    )?;
     return Ok(Response::default());
 }
 
+fn lock_tier_duration(lock_tier: LockTier) -> u64 {
+    match lock_tier {
+        LockTier::NoLock => CLUB_STAKING_DURATION,
+        LockTier::Days30 => LOCK_TIER_30_DAYS_DURATION,
+        LockTier::Days90 => LOCK_TIER_90_DAYS_DURATION,
+        LockTier::Days180 => LOCK_TIER_180_DAYS_DURATION,
+    }
+}
+
+fn lock_tier_multiplier(lock_tier: LockTier) -> u128 {
+    match lock_tier {
+        LockTier::NoLock => HUNDRED_PERCENT,
+        LockTier::Days30 => LOCK_TIER_30_DAYS_MULTIPLIER,
+        LockTier::Days90 => LOCK_TIER_90_DAYS_MULTIPLIER,
+        LockTier::Days180 => LOCK_TIER_180_DAYS_MULTIPLIER,
+    }
+}
+
+/// Reward weight of the stake above its staked amount
+fn lock_bonus(stake: &ClubStakingDetails) -> Uint128 {
+    stake.staked_amount.multiply_ratio(
+        lock_tier_multiplier(stake.lock_tier) - HUNDRED_PERCENT,
+        HUNDRED_PERCENT,
+    )
+}
+
+/// Moves the lock bonus of a stake from its old lock end to its new lock end
+fn update_club_lock_bonus(
+    storage: &mut dyn Storage,
+    club_name: &str,
+    stake_before: &ClubStakingDetails,
+    stake_after: &ClubStakingDetails,
+) -> StdResult<()> {
+    let bonus_before = lock_bonus(stake_before);
+    let bonus_after = lock_bonus(stake_after);
+    let lock_end_before = stake_before.lock_end_timestamp.seconds();
+    let lock_end_after = stake_after.lock_end_timestamp.seconds();
+    if bonus_before == bonus_after && lock_end_before == lock_end_after {
+        return Ok(());
+    }
+    if !bonus_before.is_zero() {
+        let key = (club_name, lock_end_before);
+        let club_bonus = CLUB_LOCK_BONUS_STAKE
+            .may_load(storage, key)?
+            .unwrap_or_default()
+            .checked_sub(bonus_before)?;
+        if club_bonus.is_zero() {
+            CLUB_LOCK_BONUS_STAKE.remove(storage, key);
+        } else {
+            CLUB_LOCK_BONUS_STAKE.save(storage, key, &club_bonus)?;
+        }
+    }
+    if !bonus_after.is_zero() {
+        let key = (club_name, lock_end_after);
+        let club_bonus = CLUB_LOCK_BONUS_STAKE
+            .may_load(storage, key)?
+            .unwrap_or_default()
+            .checked_add(bonus_after)?;
+        CLUB_LOCK_BONUS_STAKE.save(storage, key, &club_bonus)?;
+    }
+    Ok(())
+}
+
+/// Lock bonus of the stakes in the club whose lock has not ended at the given time
+fn active_club_lock_bonus(
+    storage: &dyn Storage,
+    club_name: &str,
+    now: Timestamp,
+) -> StdResult<Uint128> {
+    let mut club_bonus = Uint128::zero();
+    for bonus in CLUB_LOCK_BONUS_STAKE.prefix(club_name).range(
+        storage,
+        Some(Bound::exclusive(now.seconds())),
+        None,
+        Order::Ascending,
+    ) {
+        let (_, bonus) = bonus?;
+        club_bonus = club_bonus.checked_add(bonus)?;
+    }
+    Ok(club_bonus)
+}
+
 fn save_bonding_details(
     storage: &mut dyn Storage,
     env: Env,
@@ -1461,7 +2266,7 @@ fn save_bonding_details(
     // Get the exising bonds for this club
     let mut bonds = Vec::new();
     let all_bonds =
-        CLUB_BONDING_DETAILS.may_load(storage, (&club_name.clone(), &bonder.clone()))?;
+        club_bonding_details().may_load(storage, (&club_name.clone(), &bonder.clone()))?;
     match all_bonds {
         Some(some_bonds) => {
             bonds = some_bonds;
@@ -1475,947 +2280,3927 @@ fn save_bonding_details(
         bonding_duration: duration,
         club_name: club_name.clone(),
     });
-    CLUB_BONDING_DETAILS.save(storage, (&club_name.clone(), &bonder.clone()), &bonds)?;
+    club_bonding_details().save(storage, (&club_name.clone(), &bonder.clone()), &bonds)?;
     return Ok(Response::default());
 }
 
-fn increase_reward_amount(
-    deps: DepsMut,
+fn bond_is_matured(bond: &ClubBondingDetails, env: &Env) -> bool {
+    return env.block.time
+        >= bond
+            .bonding_start_timestamp
+            .plus_seconds(bond.bonding_duration);
+}
+
+/// Takes up to amount from the unbonding queue of the staker, oldest bond first.
+/// Bonds still in the unbonding period are only taken when include_unbonding is set.
+/// Returns the matured amount and unbonding amount taken along with the bonds left.
+fn take_from_unbonding_queue(
+    storage: &dyn Storage,
     env: Env,
-    info: MessageInfo,
-    reward_from: String,
+    staker: String,
+    club_name: String,
     amount: Uint128,
-) -> Result<Response, ContractError> {
-    let config = CONFIG.load(deps.storage)?;
-    // For SECURITY This message MUST only come from the Admin
-    if info.sender != config.admin_address {
-        return Err(ContractError::Unauthorized {});
-    }
-    let existing_reward = REWARD.may_load(deps.storage)?.unwrap_or_default();
-    let new_reward = existing_reward + amount;
-    REWARD.save(deps.storage, &new_reward)?;
+    include_unbonding: bool,
+) -> StdResult<(Uint128, Uint128, Vec<ClubBondingDetails>)> {
+    let mut bonds = club_bonding_details()
+        .may_load(storage, (&club_name.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    bonds.sort_by(|a, b| {
+        a.bonding_start_timestamp
+            .seconds()
+            .cmp(&b.bonding_start_timestamp.seconds())
+    });
 
-    let reward_given_in_current_timestamp = Uint128::zero();
-    REWARD_GIVEN_IN_CURRENT_TIMESTAMP.save(deps.storage, &reward_given_in_current_timestamp)?;
+    let mut amount_remaining = amount;
+    let mut matured_amount = Uint128::zero();
+    for bond in bonds.iter_mut() {
+        if amount_remaining == Uint128::zero() {
+            break;
+        }
+        if bond_is_matured(bond, &env) {
+            let taken = std::cmp::min(bond.bonded_amount, amount_remaining);
+            bond.bonded_amount -= taken;
+            matured_amount += taken;
+            amount_remaining -= taken;
+        }
+    }
+    let mut unbonding_amount = Uint128::zero();
+    if include_unbonding {
+        for bond in bonds.iter_mut() {
+            if amount_remaining == Uint128::zero() {
+                break;
+            }
+            if !bond_is_matured(bond, &env) {
+                let taken = std::cmp::min(bond.bonded_amount, amount_remaining);
+                bond.bonded_amount -= taken;
+                unbonding_amount += taken;
+                amount_remaining -= taken;
+            }
+        }
+    }
 
-    // get the actual transfer from the wallet containing funds
-    // transfer_from_wallet_to_contract(deps.storage, config.admin_address.to_string(), amount);
-    // NOTHING required to transfer anything staking fund has arrived in the staking contract
+    let remaining_bonds = bonds
+        .into_iter()
+        .filter(|bond| bond.bonded_amount > Uint128::zero())
+        .collect();
+    return Ok((matured_amount, unbonding_amount, remaining_bonds));
+}
 
-    return Ok(Response::default());
+/// Saves the bonds left for the bonder, the entry is removed once no bonds are left
+fn save_remaining_bonds(
+    storage: &mut dyn Storage,
+    club_name: &str,
+    bonder: &str,
+    remaining_bonds: &Vec<ClubBondingDetails>,
+) -> StdResult<()> {
+    if remaining_bonds.is_empty() {
+        club_bonding_details().remove(storage, (club_name, bonder))
+    } else {
+        club_bonding_details().save(storage, (club_name, bonder), remaining_bonds)
+    }
 }
 
-fn claim_staker_rewards(
+fn claim_unbonded_stake(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     staker: String,
     club_name: String,
 ) -> Result<Response, ContractError> {
-    let mut transfer_confirmed = false;
-    let mut amount = Uint128::zero();
+    let config = CONFIG.load(deps.storage)?;
     let staker_addr = deps.api.addr_validate(&staker)?;
-    //Check if withdrawer is same as invoker
+    //Check if claimer is same as invoker
     if staker_addr != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
-    let required_ust_fees = query_platform_fees(
-        deps.as_ref(),
-        to_binary(&ExecuteMsg::ClaimStakerRewards {
-            staker: staker.clone(),
-            club_name: club_name.clone(),
-        })?,
+    let (matured_amount, _, remaining_bonds) = take_from_unbonding_queue(
+        deps.storage,
+        env,
+        staker.clone(),
+        club_name.clone(),
+        Uint128::MAX,
+        false,
     )?;
-    let mut fees = Uint128::zero();
-    for fund in info.funds.clone() {
-        if fund.denom == uusd(&deps)? {
-            fees = fees.checked_add(fund.amount).unwrap();
-        }
-    }
-    let adjusted_ust_fees = required_ust_fees * (Uint128::from(NINETY_NINE_NINE_PERCENT))
-        / (Uint128::from(HUNDRED_PERCENT));
-    if fees < adjusted_ust_fees {
-        return Err(ContractError::InsufficientFees {
-            required: required_ust_fees,
-            received: fees,
-        });
-    }
-
-    // Get the exising stakes for this club
-    let mut stakes = Vec::new();
-    let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
-    match all_stakes {
-        Some(some_stakes) => {
-            stakes = some_stakes;
-        }
-        None => {}
-    }
-
-    let existing_stakes = stakes.clone();
-    let mut updated_stakes = Vec::new();
-    for stake in existing_stakes {
-        let mut updated_stake = stake.clone();
-        if staker == stake.staker_address {
-            amount += updated_stake.reward_amount;
-            updated_stake.reward_amount = Uint128::zero();
-            // confirm transfer to staker wallet
-            transfer_confirmed = true;
-        }
-        updated_stakes.push(updated_stake);
-    }
-    CLUB_STAKING_DETAILS.save(deps.storage, (&club_name.clone(), &staker.clone()), &stakes)?;
-
-    if transfer_confirmed == false {
+    if matured_amount == Uint128::zero() {
         return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("Not a valid staker for the club"),
-        }));
-    }
-    if amount == Uint128::zero() {
-        return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("No rewards for this user"),
+            msg: String::from("No matured bonds to claim"),
         }));
     }
-
-    let config = CONFIG.load(deps.storage)?;
+    save_remaining_bonds(deps.storage, &club_name, &staker, &remaining_bonds)?;
 
     let transfer_msg = Cw20ExecuteMsg::Transfer {
         recipient: staker.clone(),
-        amount: amount,
+        amount: matured_amount,
     };
     let exec = WasmMsg::Execute {
         contract_addr: config.fury_token_address.to_string(),
         msg: to_binary(&transfer_msg).unwrap(),
         funds: vec![],
     };
-
-    let fee_wallet = FEE_WALLET.load(deps.storage)?;
-
-    let send_wasm: CosmosMsg = CosmosMsg::Wasm(exec);
-    let send_bank: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
-        to_address: fee_wallet,
-        amount: info.funds,
-    });
-    let data_msg = format!("Amount {} transferred", amount).into_bytes();
+    let data_msg = format!("Amount {} transferred", matured_amount).into_bytes();
     return Ok(Response::new()
-        .add_message(send_wasm)
-        .add_message(send_bank)
-        .add_attribute("action", "staking_reward_claim")
+        .add_message(exec)
+        .add_attribute("action", "claim_unbonded_stake")
+        .add_attribute("claimed", matured_amount.to_string())
         .add_attribute("staker", staker)
-        .add_attribute("amount", amount.to_string())
+        .add_attribute("club_name", club_name)
         .set_data(data_msg));
 }
 
-fn calculate_and_distribute_rewards(
+fn rebond(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    staker_list: Vec<String>,
     club_name: String,
-    is_first_batch: bool,
-    is_final_batch: bool,
+    amount: Uint128,
+    to_club: Option<String>,
 ) -> Result<Response, ContractError> {
-    // Check if this is executed by main/transaction wallet
-    let config = CONFIG.load(deps.storage)?;
-    if info.sender != config.admin_address {
+    let staker = info.sender.to_string();
+    let target_club;
+    match to_club {
+        Some(tc) => target_club = tc,
+        None => target_club = club_name.clone(),
+    }
+
+    if amount == Uint128::zero() {
         return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("not authorised"),
+            msg: String::from("Amount to rebond must be greater than zero"),
         }));
     }
-    let total_reward = REWARD.may_load(deps.storage)?.unwrap_or_default();
+    // rebonding into another club is a stake move as well
+    let moves_club = target_club != club_name;
+    if moves_club {
+        ensure_stake_move_allowed(deps.storage, &env, &staker)?;
+    }
 
-    let mut next_reward_time = CLUB_REWARD_NEXT_TIMESTAMP
-        .may_load(deps.storage)?
+    // newest bonds are the furthest from maturity, so they are moved first
+    let mut bonds = club_bonding_details()
+        .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?
         .unwrap_or_default();
-    println!(
-        "now = {:?} next_reward_time = {:?} periodicity = {:?} is_first_batch = {:?} is_final_batch = {:?}",
-        env.block.time, next_reward_time, config.reward_periodicity, is_first_batch, is_final_batch
-    );
-
-    let saved_next_reward_timestamp = next_reward_time;
-
-    if env.block.time < next_reward_time {
-        println!("Time for Reward not yet arrived");
+    bonds.sort_by(|a, b| {
+        b.bonding_start_timestamp
+            .seconds()
+            .cmp(&a.bonding_start_timestamp.seconds())
+    });
+    let mut amount_remaining = amount;
+    for bond in bonds.iter_mut() {
+        if amount_remaining == Uint128::zero() {
+            break;
+        }
+        let taken = std::cmp::min(bond.bonded_amount, amount_remaining);
+        bond.bonded_amount -= taken;
+        amount_remaining -= taken;
+    }
+    if amount_remaining > Uint128::zero() {
         return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("Time for Reward not yet arrived"),
+            msg: String::from("Excess amount demanded for rebonding"),
         }));
     }
-    if is_final_batch {
-        if next_reward_time < env.block.time {
-            next_reward_time = next_reward_time.plus_seconds(config.reward_periodicity);
+    let mut remaining_bonds: Vec<ClubBondingDetails> = bonds
+        .into_iter()
+        .filter(|bond| bond.bonded_amount > Uint128::zero())
+        .collect();
+    remaining_bonds.reverse();
+    save_remaining_bonds(deps.storage, &club_name, &staker, &remaining_bonds)?;
+
+    // keep the auto stake setting of an existing stake in the target club
+    let mut auto_stake = DONT_CHANGE_AUTO_STAKE_SETTING;
+    let target_stakes = club_staking_details()
+        .may_load(deps.storage, (&target_club.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    for stake in target_stakes {
+        if staker == stake.staker_address {
+            auto_stake = stake.auto_stake;
         }
-        println!("setting next_reward_time = {:?}", next_reward_time);
-        CLUB_REWARD_NEXT_TIMESTAMP.save(deps.storage, &next_reward_time)?;
     }
-
-    // No need to calculate if there is no reward amount
-    if total_reward == Uint128::zero() {
-        return Ok(Response::new()
-            .add_attribute("response", "no accumulated rewards")
-            .add_attribute("next_timestamp", next_reward_time.to_string()));
+    record_club_stake(
+        deps.storage,
+        env.clone(),
+        staker.clone(),
+        target_club.clone(),
+        amount,
+        auto_stake,
+        None,
+    )?;
+    if moves_club {
+        LAST_STAKE_MOVE_TIMESTAMP.save(deps.storage, staker.clone(), &env.block.time)?;
     }
-    distribute_reward_to_club_stakers(
-        deps,
-        env,
-        config.reward_periodicity,
-        saved_next_reward_timestamp,
-        staker_list.clone(),
-        club_name.clone(),
-        total_reward,
-        is_first_batch,
-        is_final_batch,
-    )
-}
 
-fn distribute_reward_to_club_stakers(
+    return Ok(Response::new()
+        .add_attribute("action", "rebond")
+        .add_attribute("staker", staker)
+        .add_attribute("club_name", club_name)
+        .add_attribute("to_club", target_club)
+        .add_attribute("rebonded", amount.to_string()));
+}
+
+/// One move per reward period, so stake cannot be shuffled into the club
+/// with the highest incremental stake just before the distribution
+fn ensure_stake_move_allowed(
+    storage: &dyn Storage,
+    env: &Env,
+    staker: &String,
+) -> Result<(), ContractError> {
+    let config = CONFIG.load(storage)?;
+    let last_move = LAST_STAKE_MOVE_TIMESTAMP.may_load(storage, staker.clone())?;
+    match last_move {
+        Some(lm) => {
+            if env.block.time < lm.plus_seconds(config.reward_periodicity) {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Stake was moved too recently"),
+                }));
+            }
+        }
+        None => {}
+    }
+    Ok(())
+}
+
+fn move_stake(
     deps: DepsMut,
     env: Env,
-    reward_periodicity: u64,
-    saved_next_reward_timestamp: Timestamp,
-    staker_list: Vec<String>,
-    club_name: String,
-    total_reward: Uint128,
-    is_first_batch: bool,
-    is_final_batch: bool,
+    info: MessageInfo,
+    from_club: String,
+    to_club: String,
+    amount: Uint128,
 ) -> Result<Response, ContractError> {
-    let mut winning_clubs_info: WinningClubDetails;
-    if is_first_batch {
-        let response = get_winning_clubs_details(deps.storage)?;
-        winning_clubs_info = WinningClubDetails {
-            total_number_of_clubs: response.0,
-            total_stake_across_all_clubs: response.1,
-            total_stake_in_winning_club: response.2,
-            winner_list: response.3.clone(),
-        };
-        WINNING_CLUB_DETAILS_SNAPSHOT.save(deps.storage, &winning_clubs_info);
-    } else {
-        winning_clubs_info = WINNING_CLUB_DETAILS_SNAPSHOT
-            .may_load(deps.storage)?
-            .unwrap_or_default();
-    }
-    println!("winning_clubs_info = {:?}", winning_clubs_info);
-    let total_number_of_clubs = winning_clubs_info.total_number_of_clubs;
-    let total_stake_across_all_clubs = winning_clubs_info.total_stake_across_all_clubs;
-    let total_stake_in_winning_club = winning_clubs_info.total_stake_in_winning_club;
-    let winner_list = winning_clubs_info.winner_list.clone();
-    let is_club_a_winner = is_winning_club(club_name.clone(), winner_list.clone());
-    let club_details = query_club_ownership_details(deps.storage, club_name.clone())?;
-    let club_owner_address = club_details.owner_address.clone();
-    let num_of_winners = winner_list.len() as u64;
-    let other_club_count = total_number_of_clubs - num_of_winners;
+    let staker = info.sender.to_string();
 
-    if !is_club_a_winner && other_club_count <= 0 {
+    if from_club == to_club {
         return Err(ContractError::Std(StdError::GenericErr {
-            msg: String::from("computation error - should never happen"),
+            msg: String::from("Stake can only be moved to a different club"),
+        }));
+    }
+    if amount == Uint128::zero() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Amount to move must be greater than zero"),
         }));
     }
 
-    let mut owner_reward = Uint128::zero();
-    let mut reward_for_all_stakers_in_winning_club = Uint128::zero();
+    ensure_stake_move_allowed(deps.storage, &env, &staker)?;
 
-    if is_club_a_winner {
-        if other_club_count > 0 {
-            // distribute 1% equally to owners in this winning club
-            owner_reward = total_reward
-                .checked_div(Uint128::from(100u128))
-                .unwrap_or_default()
-                .checked_div(Uint128::from(num_of_winners))
-                .unwrap_or_default();
-            println!(
-                "club_name {:?} owner reward for this winner is {:?}",
-                club_name.clone(),
-                owner_reward
-            );
-        } else {
-            // there are only winning clubs
-            // distribute 3% equally to owners in this winning club
-            owner_reward = total_reward
-                .checked_mul(Uint128::from(3u128))
-                .unwrap_or_default()
-                .checked_div(Uint128::from(100u128))
-                .unwrap_or_default()
-                .checked_div(Uint128::from(num_of_winners))
-                .unwrap_or_default();
-            println!(
-                "all clubs are winners club_name {:?} owner reward for this winner is {:?}",
-                club_name.clone(),
-                owner_reward
-            );
+    let mut user_stake_exists = false;
+    let mut auto_stake = DONT_CHANGE_AUTO_STAKE_SETTING;
+    let from_stakes = club_staking_details()
+        .may_load(deps.storage, (&from_club.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    for stake in from_stakes {
+        if staker == stake.staker_address {
+            user_stake_exists = true;
+            auto_stake = stake.auto_stake;
+            if env.block.time < stake.lock_end_timestamp {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Cannot move a locked stake"),
+                }));
+            }
+            if stake.staked_amount < amount {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Excess amount demanded for moving"),
+                }));
+            }
         }
-        // distribute 19% to stakers in winning club
-        reward_for_all_stakers_in_winning_club = total_reward
-            .checked_mul(Uint128::from(19u128))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(100u128))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(num_of_winners))
-            .unwrap_or_default();
-        println!(
-            "club_name {:?} stakers award in winner is {:?}",
-            club_name.clone(),
-            reward_for_all_stakers_in_winning_club
-        );
-    } else {
-        // other_club_count must be greater than 0
-        // distribute 2% equally to owner in this non winning club
-        owner_reward = total_reward
-            .checked_mul(Uint128::from(2u128))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(100u128))
-            .unwrap_or_default()
-            .checked_div(Uint128::from(other_club_count))
-            .unwrap_or_default();
-        println!(
-            "club_name {:?} owner reward for non winner is {:?}",
-            club_name.clone(),
-            owner_reward
-        );
+    }
+    if !user_stake_exists {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("User has not staked in this club"),
+        }));
     }
 
-    // distribute the 78% to all stakers
-    let all_stakers_reward = total_reward
-        .checked_mul(Uint128::from(78u128))
-        .unwrap_or_default()
-        .checked_div(Uint128::from(100u128))
+    // keep the auto stake setting of an existing stake in the target club
+    let to_stakes = club_staking_details()
+        .may_load(deps.storage, (&to_club.clone(), &staker.clone()))?
         .unwrap_or_default();
+    for stake in to_stakes {
+        if staker == stake.staker_address {
+            auto_stake = stake.auto_stake;
+        }
+    }
 
-    let mut reward_given_so_far = Uint128::zero();
-    let mut stake_to_add_for_club = Uint128::zero();
-    for staker in staker_list {
-        let mut updated_stakes_for_this_staker = Vec::new();
-        let csd =
-            CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
-        let staking_details;
-        match csd {
-            None => {}
-            Some(some_csd) => {
-                staking_details = some_csd;
-
-                for mut stake in staking_details {
-                    let mut updated_stake = stake.clone();
-                    println!("stake = {:?}", updated_stake);
-                    if updated_stake.staking_start_timestamp > env.block.time {
-                        continue;
-                    }
-                    if saved_next_reward_timestamp < env.block.time {
-                        updated_stake.staking_start_timestamp =
-                            saved_next_reward_timestamp.plus_seconds(reward_periodicity);
-                        println!(
-                            "setting timestamp for stake = {:?}",
-                            updated_stake.staking_start_timestamp
-                        );
-                    } else {
-                        updated_stake.staking_start_timestamp = saved_next_reward_timestamp;
-                        println!(
-                            "setting timestamp for stake = {:?}",
-                            updated_stake.staking_start_timestamp
-                        );
-                    }
-
-                    let auto_stake = updated_stake.auto_stake;
+    save_staking_details(
+        deps.storage,
+        env.clone(),
+        staker.clone(),
+        from_club.clone(),
+        amount,
+        DONT_CHANGE_AUTO_STAKE_SETTING,
+        DECREASE_STAKE,
+    )?;
+    record_club_stake(
+        deps.storage,
+        env.clone(),
+        staker.clone(),
+        to_club.clone(),
+        amount,
+        auto_stake,
+        None,
+    )?;
+    LAST_STAKE_MOVE_TIMESTAMP.save(deps.storage, staker.clone(), &env.block.time)?;
 
-                    // Calculate for All Staker - 78% proportional
-                    let mut reward_for_this_stake = all_stakers_reward
-                        .checked_mul(stake.staked_amount)
-                        .unwrap_or_default()
-                        .checked_div(total_stake_across_all_clubs)
-                        .unwrap_or_default();
-
-                    if is_club_a_winner {
-                        // Calculate for Winning Club Staker - 19% proportional
-                        reward_for_this_stake += reward_for_all_stakers_in_winning_club
-                            .checked_mul(stake.staked_amount)
-                            .unwrap_or_default()
-                            .checked_div(total_stake_in_winning_club)
-                            .unwrap_or_default();
-                    }
+    return Ok(Response::new()
+        .add_attribute("action", "move_stake")
+        .add_attribute("staker", staker)
+        .add_attribute("from_club", from_club)
+        .add_attribute("to_club", to_club)
+        .add_attribute("stake", amount.to_string()));
+}
 
-                    if stake.staker_address == club_owner_address {
-                        // Calculate for Club Owner - (1% or 3% for winner owner) or 2% proportional for non-winner owner
-                        reward_for_this_stake += owner_reward;
-                    }
+/// Turns compounding of rewards on or off for the stake of the sender in the club.
+/// Turning it on also compounds the reward accumulated so far, as much as fits under the club cap.
+fn set_auto_stake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    club_name: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let staker = info.sender.to_string();
+    let stakes = club_staking_details()
+        .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    if stakes.is_empty() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("No stake found for this club"),
+        }));
+    }
 
-                    reward_given_so_far += reward_for_this_stake;
+    let owner = club_ownership_details().load(deps.storage, club_name.clone())?;
+    let mut room = None;
+    let club = REGISTERED_CLUBS.may_load(deps.storage, club_name.clone())?;
+    match club {
+        Some(club) => match club.max_total_stake {
+            Some(max_total_stake) => {
+                room = Some(max_total_stake.saturating_sub(owner.total_staked_amount));
+            }
+            None => {}
+        },
+        None => {}
+    }
 
-                    if auto_stake == SET_AUTO_STAKE {
-                        stake_to_add_for_club += reward_for_this_stake;
-                        updated_stake.staked_amount += reward_for_this_stake;
-                        updated_stake.staked_amount += updated_stake.reward_amount;
-                        updated_stake.reward_amount = Uint128::zero();
-                    } else {
-                        updated_stake.reward_amount += reward_for_this_stake;
+    let mut compounded_reward = Uint128::zero();
+    let mut updated_stakes = Vec::new();
+    for stake in stakes {
+        let mut updated_stake = stake.clone();
+        if updated_stake.staker_address == staker {
+            updated_stake.auto_stake = enabled;
+            if enabled == SET_AUTO_STAKE {
+                let mut compound = updated_stake.reward_amount;
+                match room {
+                    Some(room) => {
+                        if compound > room - compounded_reward {
+                            compound = room - compounded_reward;
+                        }
                     }
-                    updated_stakes_for_this_staker.push(updated_stake);
+                    None => {}
                 }
+                compounded_reward += compound;
+                updated_stake.staked_amount += compound;
+                updated_stake.reward_amount -= compound;
             }
+            update_club_lock_bonus(deps.storage, &club_name, &stake, &updated_stake)?;
         }
-        CLUB_STAKING_DETAILS.save(
-            deps.storage,
-            (&club_name.clone(), &staker.clone()),
-            &updated_stakes_for_this_staker,
-        )?;
+        updated_stakes.push(updated_stake);
     }
-
-    // Now update the total stake for this club
-    CLUB_OWNERSHIP_DETAILS.save(
+    club_staking_details().save(
         deps.storage,
-        club_name.clone(),
-        &ClubOwnershipDetails {
-            club_name: club_details.club_name.clone(),
-            start_timestamp: club_details.start_timestamp,
-            locking_period: club_details.locking_period,
-            owner_address: club_details.owner_address,
-            price_paid: club_details.price_paid,
-            reward_amount: club_details.reward_amount,
-            owner_released: club_details.owner_released,
-            total_staked_amount: club_details.total_staked_amount + stake_to_add_for_club,
-        },
+        (&club_name.clone(), &staker.clone()),
+        &updated_stakes,
     )?;
 
-    println!(
-        "club_name = {:?} total reward = {:?} reward so far = {:?} club stake increased by {:?}",
-        club_name.clone(),
-        total_reward,
-        reward_given_so_far,
-        stake_to_add_for_club
-    );
-
-    let mut reward_given_in_current_timestamp = REWARD_GIVEN_IN_CURRENT_TIMESTAMP
-        .may_load(deps.storage)?
-        .unwrap_or_default();
-    reward_given_in_current_timestamp += reward_given_so_far;
-    REWARD_GIVEN_IN_CURRENT_TIMESTAMP.save(deps.storage, &reward_given_in_current_timestamp)?;
-    println!(
-        "reward_given_in_current_timestamp = {:?}",
-        reward_given_in_current_timestamp
-    );
-
-    if is_final_batch {
-        let mut new_reward = Uint128::zero();
-        if total_reward > reward_given_in_current_timestamp {
-            new_reward = total_reward - reward_given_in_current_timestamp;
-        }
-        REWARD.save(deps.storage, &new_reward)?;
-        println!("new_reward = {:?} ", new_reward);
+    if !compounded_reward.is_zero() {
+        club_ownership_details().save(
+            deps.storage,
+            club_name.clone(),
+            &ClubOwnershipDetails {
+                club_name: owner.club_name.clone(),
+                start_timestamp: owner.start_timestamp,
+                locking_period: owner.locking_period,
+                owner_address: owner.owner_address,
+                price_paid: owner.price_paid,
+                reward_amount: owner.reward_amount,
+                owner_released: owner.owner_released,
+                total_staked_amount: owner.total_staked_amount + compounded_reward,
+                commission_rate: owner.commission_rate,
+            },
+        )?;
+        sync_voting_power(deps.storage, env.block.height, staker.clone())?;
     }
-    Ok(Response::default())
-}
 
-fn get_winning_clubs_details(
-    storage: &mut dyn Storage,
-) -> StdResult<(u64, Uint128, Uint128, Vec<String>)> {
-    let mut max_incremental_stake_value = 0i128 - MAX_UFURY_COUNT;
-    let mut max_total_stake_value = Uint128::zero();
+    return Ok(Response::new()
+        .add_attribute("action", "set_auto_stake")
+        .add_attribute("staker", staker)
+        .add_attribute("club_name", club_name)
+        .add_attribute("enabled", enabled.to_string())
+        .add_attribute("compounded_reward", compounded_reward.to_string()));
+}
 
-    let mut total_number_of_clubs = 0u64;
-    let mut total_stake_across_all_clubs = Uint128::zero();
-    let mut total_stake_in_winning_club = Uint128::zero();
-    let mut winners: Vec<String> = Vec::new();
-
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club in all_clubs {
-        let club_details = query_club_ownership_details(storage, club.clone())?;
-        let stake_in_club = club_details.total_staked_amount;
-        total_stake_across_all_clubs += stake_in_club;
-        let staked_amount_u128: u128 = stake_in_club.into();
-        let staked_amount_i128 = staked_amount_u128 as i128;
-        let previous_amount = CLUB_STAKING_SNAPSHOT
-            .may_load(storage, club.clone())?
-            .unwrap_or_default();
-        let previous_amount_u128: u128 = previous_amount.into();
-        let previous_amount_i128 = previous_amount_u128 as i128;
-        let difference_amount = staked_amount_i128 - previous_amount_i128;
+/// Sets the share of the owner reward kept by the club owner.
+/// The commission can change by at most MAX_COMMISSION_CHANGE_RATE once per reward period.
+fn set_club_commission(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    club_name: String,
+    commission_rate: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let owner = query_club_ownership_details(deps.storage, club_name.clone())?;
+    if owner.owner_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+    if commission_rate > Uint128::from(HUNDRED_PERCENT) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Commission rate cannot exceed 100%"),
+        }));
+    }
+    let change = if commission_rate > owner.commission_rate {
+        commission_rate - owner.commission_rate
+    } else {
+        owner.commission_rate - commission_rate
+    };
+    if change > Uint128::from(MAX_COMMISSION_CHANGE_RATE) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Commission rate change exceeds the maximum change rate"),
+        }));
+    }
+    let last_change = CLUB_COMMISSION_CHANGE_TIMESTAMP.may_load(deps.storage, club_name.clone())?;
+    match last_change {
+        Some(lc) => {
+            if env.block.time < lc.plus_seconds(config.reward_periodicity) {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Commission rate was changed too recently"),
+                }));
+            }
+        }
+        None => {}
+    }
 
-        if difference_amount > max_incremental_stake_value {
-            // found a new max_incremental_stake
-            total_stake_in_winning_club = stake_in_club;
-            max_incremental_stake_value = difference_amount;
-            max_total_stake_value = stake_in_club;
+    let mut updated_owner = owner.clone();
+    updated_owner.commission_rate = commission_rate;
+    club_ownership_details().save(deps.storage, club_name.clone(), &updated_owner)?;
+    CLUB_COMMISSION_CHANGE_TIMESTAMP.save(deps.storage, club_name.clone(), &env.block.time)?;
 
-            // pop all pre-existing winners
-            let winner_len = winners.len();
-            let mut i = 0;
-            while i < winner_len {
-                winners.swap_remove(0);
-                i += 1;
-            }
+    return Ok(Response::new()
+        .add_attribute("action", "set_club_commission")
+        .add_attribute("club_name", club_name)
+        .add_attribute("commission_rate", commission_rate.to_string()));
+}
 
-            // now add this stake to the winner list
-            winners.push(club.clone());
-        } else if difference_amount == max_incremental_stake_value {
-            if stake_in_club >= max_total_stake_value {
-                total_stake_in_winning_club = stake_in_club;
-                max_incremental_stake_value = difference_amount;
-                max_total_stake_value = stake_in_club;
-                if stake_in_club > max_total_stake_value {
-                    // found a new max_total_stake
+fn register_club(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    club: ClubMetadata,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
 
-                    // pop all pre-existing winners
-                    let winner_len = winners.len();
-                    let mut i = 0;
-                    while i < winner_len {
-                        winners.swap_remove(0);
-                        i += 1;
-                    }
+    if club.club_name.is_empty() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Club name cannot be empty"),
+        }));
+    }
 
-                    // now add this stake to the winner list
-                    winners.push(club.clone());
-                } else if stake_in_club == max_total_stake_value {
-                    // more than one winners have same total and incremental stake
+    REGISTERED_CLUBS.save(deps.storage, club.club_name.clone(), &club)?;
+    Ok(Response::new()
+        .add_attribute("action", "register_club")
+        .add_attribute("club_name", club.club_name))
+}
 
-                    // add this stake to the winner list
-                    winners.push(club.clone());
-                }
-                // else skip this club
-            }
+fn ensure_club_registered(
+    storage: &dyn Storage,
+    club_name: String,
+) -> Result<ClubMetadata, ContractError> {
+    match REGISTERED_CLUBS.may_load(storage, club_name)? {
+        Some(club) => return Ok(club),
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club is not registered"),
+            }));
         }
-        // else skip this club
+    }
+}
 
-        total_number_of_clubs += 1;
-        CLUB_STAKING_SNAPSHOT.save(storage, club.clone(), &stake_in_club)?;
+/// Whether the club stays within its max_total_stake after the stake increase
+fn club_stake_limit_allows(
+    storage: &dyn Storage,
+    club_name: String,
+    total_staked_amount: Uint128,
+    stake_increase: Uint128,
+) -> StdResult<bool> {
+    if stake_increase.is_zero() {
+        return Ok(true);
+    }
+    let club = REGISTERED_CLUBS.may_load(storage, club_name)?;
+    match club {
+        Some(club) => match club.max_total_stake {
+            Some(max_total_stake) => {
+                return Ok(total_staked_amount + stake_increase <= max_total_stake)
+            }
+            None => return Ok(true),
+        },
+        None => return Ok(true),
     }
+}
 
-    println!(
-        "total_clubs = {:?}, total_stake = {:?}, winning_stake = {:?}, winners = {:?}",
-        total_number_of_clubs, total_stake_across_all_clubs, total_stake_in_winning_club, winners
-    );
-    Ok((
-        total_number_of_clubs,
-        total_stake_across_all_clubs,
-        total_stake_in_winning_club,
-        winners,
-    ))
+/// Price in Fury for buying the club, the configured club price unless overridden for the club
+fn club_price(storage: &dyn Storage, club_name: String) -> StdResult<Uint128> {
+    let config = CONFIG.load(storage)?;
+    let club = REGISTERED_CLUBS.may_load(storage, club_name)?;
+    match club {
+        Some(club) => match club.price_override {
+            Some(price) => return Ok(price),
+            None => return Ok(config.club_price),
+        },
+        None => return Ok(config.club_price),
+    }
 }
 
-fn is_winning_club(club_name: String, winner_list: Vec<String>) -> bool {
-    for winner in winner_list {
-        if club_name == winner {
-            return true;
+fn set_early_exit_penalty(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    early_exit: Option<EarlyExitPenalty>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.admin_address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    match early_exit {
+        Some(ee) => {
+            if ee.penalty > Uint128::from(HUNDRED_PERCENT) {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Early exit penalty cannot exceed 100%"),
+                }));
+            }
+            EARLY_EXIT_PENALTY.save(deps.storage, &ee)?;
+        }
+        None => {
+            EARLY_EXIT_PENALTY.remove(deps.storage);
         }
     }
-    return false;
+    Ok(Response::new().add_attribute("action", "set_early_exit_penalty"))
 }
 
-fn transfer_from_contract_to_wallet(
-    store: &dyn Storage,
-    wallet_owner: String,
+fn increase_reward_amount(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    reward_from: String,
     amount: Uint128,
-    action: String,
 ) -> Result<Response, ContractError> {
-    let config = CONFIG.load(store)?;
+    let config = CONFIG.load(deps.storage)?;
+    // For SECURITY This message MUST only come from the Admin
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {});
+    }
+    let fury = fury_denom(deps.storage)?;
+    add_reward(deps.storage, fury.clone(), amount)?;
 
-    let transfer_msg = Cw20ExecuteMsg::Transfer {
-        recipient: wallet_owner,
-        amount: amount,
-    };
-    let exec = WasmMsg::Execute {
-        contract_addr: config.fury_token_address.to_string(),
-        msg: to_binary(&transfer_msg).unwrap(),
-        funds: vec![
-            // Coin {
-            //     denom: token_info.name.to_string(),
-            //     amount: price,
-            // },
-        ],
-    };
-    let send: SubMsg = SubMsg::new(exec);
-    let data_msg = format!("Amount {} transferred", amount).into_bytes();
-    return Ok(Response::new()
-        .add_submessage(send)
-        .add_attribute("action", action)
-        .add_attribute("amount", amount.to_string())
-        .set_data(data_msg));
+    REWARDS_GIVEN_IN_CURRENT_TIMESTAMP.remove(deps.storage, denom_key(&fury));
+
+    // get the actual transfer from the wallet containing funds
+    // transfer_from_wallet_to_contract(deps.storage, config.admin_address.to_string(), amount);
+    // NOTHING required to transfer anything staking fund has arrived in the staking contract
+
+    return Ok(Response::default());
 }
 
-#[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
-        QueryMsg::QueryPlatformFees { msg } => to_binary(&query_platform_fees(deps, msg)?),
-        QueryMsg::ClubStakingDetails {
-            club_name,
-            user_list,
-        } => to_binary(&query_club_staking_details(
-            deps.storage,
-            club_name,
-            user_list,
-        )?),
-        QueryMsg::ClubOwnershipDetails { club_name } => {
-            to_binary(&query_club_ownership_details(deps.storage, club_name)?)
-        }
-        QueryMsg::ClubPreviousOwnershipDetails { previous_owner } => to_binary(
-            &query_club_previous_owner_details(deps.storage, previous_owner)?,
-        ),
-        QueryMsg::AllClubOwnershipDetails {} => {
-            to_binary(&query_all_club_ownership_details(deps.storage)?)
-        }
-        QueryMsg::AllPreviousClubOwnershipDetails {} => {
-            to_binary(&query_all_previous_club_ownership_details(deps.storage)?)
-        }
-        QueryMsg::ClubOwnershipDetailsForOwner { owner_address } => to_binary(
-            &query_club_ownership_details_for_owner(deps.storage, owner_address)?,
-        ),
-        QueryMsg::AllStakes { user_address_list } => {
-            to_binary(&query_all_stakes(deps.storage, user_address_list)?)
-        }
-        QueryMsg::AllStakesForUser { user_address } => {
-            to_binary(&query_all_stakes_for_user(deps.storage, user_address)?)
-        }
-        QueryMsg::AllBonds { user_address_list } => {
-            to_binary(&query_all_bonds(deps.storage, user_address_list)?)
-        }
-        QueryMsg::ClubBondingDetailsForUser {
-            club_name,
-            user_address,
-        } => to_binary(&query_club_bonding_details_for_user(
-            deps.storage,
-            club_name,
-            user_address,
-        )?),
-        QueryMsg::RewardAmount {} => to_binary(&query_reward_amount(deps.storage)?),
-        QueryMsg::QueryStakerRewards { staker, club_name } => {
-            to_binary(&query_staker_rewards(deps, staker, club_name)?)
+/// Administrator adds partner rewards, in native tokens sent with the message
+/// or in a cw20 token sent to the contract
+fn increase_partner_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    rewards: Vec<DenomAmount>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    // For SECURITY This message MUST only come from the Admin
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {});
+    }
+    if rewards.is_empty() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("No partner reward sent"),
+        }));
+    }
+    let mut rsp = Response::new().add_attribute("action", "increase_partner_reward");
+    for reward in rewards {
+        let key = denom_key(&reward.denom);
+        add_reward(deps.storage, reward.denom, reward.amount)?;
+        rsp = rsp.add_attribute(key, reward.amount.to_string());
+    }
+    return Ok(rsp);
+}
+
+/// Removes the partner rewards owed to the staker in the club from the owed map,
+/// returning the messages paying them out
+fn take_partner_rewards_owed(
+    storage: &mut dyn Storage,
+    owed_map: &Map<(&str, &str), Vec<DenomAmount>>,
+    club_name: String,
+    staker: String,
+) -> StdResult<Vec<CosmosMsg>> {
+    let owed = owed_map
+        .may_load(storage, (&club_name.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    owed_map.remove(storage, (&club_name.clone(), &staker.clone()));
+    let mut payouts = Vec::new();
+    for denom_amount in owed {
+        if denom_amount.amount > Uint128::zero() {
+            payouts.push(partner_reward_payout_msg(denom_amount, staker.clone())?);
         }
-        QueryMsg::Config {} => to_binary(&query_config(deps.storage)?),
-        QueryMsg::GetFeeWallet {} => to_binary(&query_get_fee_wallet(deps.storage)?),
     }
+    Ok(payouts)
 }
 
-pub fn query_platform_fees(deps: Deps, msg: Binary) -> StdResult<Uint128> {
+fn fund_emission_schedule(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    amount: Uint128,
+    reward_per_second: Uint128,
+    start_timestamp: Timestamp,
+    end_timestamp: Timestamp,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
-    let platform_fees_percentage: Uint128;
-    let fury_amount_provided;
-    match from_binary(&msg) {
-        Ok(ExecuteMsg::IncreaseRewardAmount {
-            reward_from: _,
-            amount: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::ChangeConfig { config: _ }) => return Ok(Uint128::zero()),
-        Ok(ExecuteMsg::ChangeFeeWallet { address: _ }) => return Ok(Uint128::zero()),
-        Ok(ExecuteMsg::BuyAClub {
-            buyer: _,
-            seller: _,
-            club_name: _,
-            auto_stake: _,
-        }) => {
-            platform_fees_percentage = config.platform_fees + config.transaction_fees;
-            fury_amount_provided = config.club_price;
-        }
-        Ok(ExecuteMsg::AssignAClub {
-            buyer: _,
-            seller: _,
-            club_name: _,
-            auto_stake: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::StakeOnAClub {
-            staker,
-            club_name,
-            amount,
-            auto_stake,
-        }) => {
-            platform_fees_percentage =
-                config.platform_fees + config.transaction_fees + config.control_fees;
-            fury_amount_provided = amount;
-        }
-        Ok(ExecuteMsg::AssignStakesToAClub {
-            stake_list: _,
-            club_name: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::ReleaseClub {
-            owner: _,
-            club_name: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::ClaimOwnerRewards {
-            owner: _,
-            club_name: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::ClaimPreviousOwnerRewards { previous_owner: _ }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::StakeWithdrawFromAClub {
-            staker: _,
-            club_name: _,
-            amount,
-            immediate_withdrawal: _,
-        }) => {
-            platform_fees_percentage = config.platform_fees + config.transaction_fees;
-            fury_amount_provided = amount;
-        }
-        Ok(ExecuteMsg::CalculateAndDistributeRewards {
-            staker_list: _,
-            club_name: _,
-            is_first_batch: _,
-            is_final_batch: _,
-        }) => {
-            return Ok(Uint128::zero());
-        }
-        Ok(ExecuteMsg::ClaimStakerRewards { staker, club_name }) => {
-            fury_amount_provided = query_staker_rewards(deps, staker, club_name)?;
-            platform_fees_percentage = config.platform_fees + config.transaction_fees;
-        }
-        Err(err) => {
-            return Err(StdError::generic_err(format!("{:?}", err)));
-        }
+    // For SECURITY This message MUST only come from the Admin
+    if info.sender != config.admin_address {
+        return Err(ContractError::Unauthorized {});
     }
-
-    let ust_equiv_for_fury_response: Token2ForToken1PriceResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: config.pool_address.clone().to_string(),
-            msg: to_binary(&WasmswapQueryMsg::Token2ForToken1Price {
-                token2_amount: fury_amount_provided,
-            })?,
-        }))?;
-    let ust_equiv_for_fury = ust_equiv_for_fury_response.token1_amount;
-
-    return Ok(ust_equiv_for_fury
-        .checked_mul(platform_fees_percentage)?
-        .checked_div(Uint128::from(HUNDRED_PERCENT))?);
-}
-
-pub fn query_club_staking_details(
-    storage: &dyn Storage,
-    club_name: String,
-    user_list: Vec<String>,
-) -> StdResult<Vec<ClubStakingDetails>> {
-    let mut all_stakes = Vec::new();
-    for user in user_list {
-        let csd = CLUB_STAKING_DETAILS.may_load(storage, (&club_name.clone(), &user.clone()))?;
-        match csd {
-            Some(staking_details) => {
-                for stake in staking_details {
-                    all_stakes.push(stake);
-                }
-            }
-            None => {}
-        }
+    if end_timestamp <= start_timestamp || start_timestamp < env.block.time {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Invalid emission schedule period"),
+        }));
     }
-    return Ok(all_stakes);
-}
-
-fn query_all_stakes(
-    storage: &dyn Storage,
-    user_address_list: Vec<String>,
-) -> StdResult<Vec<ClubStakingDetails>> {
-    let mut all_stakes = Vec::new();
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club_name in all_clubs {
-        for user_address in user_address_list.clone() {
-            let csd = CLUB_STAKING_DETAILS
-                .may_load(storage, (&club_name.clone(), &user_address.clone()))?;
-            match csd {
-                Some(staking_details) => {
-                    for stake in staking_details {
-                        all_stakes.push(stake);
-                    }
-                }
-                None => {}
+    // settle what the current schedule emitted so far, an ended schedule is fully emitted
+    emit_scheduled_rewards(deps.storage, env.block.time)?;
+    let existing_schedule = EMISSION_SCHEDULE.may_load(deps.storage)?;
+    match existing_schedule {
+        Some(es) => {
+            if es.emitted_amount < es.funded_amount {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("An emission schedule is still running"),
+                }));
             }
         }
+        None => {}
     }
-    return Ok(all_stakes);
+    let duration = end_timestamp.seconds() - start_timestamp.seconds();
+    let funded_amount = reward_per_second * Uint128::from(duration);
+    if amount != funded_amount {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Funds do not match the emission schedule"),
+        }));
+    }
+    EMISSION_SCHEDULE.save(
+        deps.storage,
+        &EmissionSchedule {
+            reward_per_second: reward_per_second,
+            start_timestamp: start_timestamp,
+            end_timestamp: end_timestamp,
+            funded_amount: funded_amount,
+            emitted_amount: Uint128::zero(),
+            last_emission_timestamp: start_timestamp,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("action", "fund_emission_schedule")
+        .add_attribute("reward_per_second", reward_per_second.to_string())
+        .add_attribute("funded_amount", funded_amount.to_string()));
 }
 
-fn query_all_bonds(
-    storage: &dyn Storage,
-    user_address_list: Vec<String>,
-) -> StdResult<Vec<ClubBondingDetails>> {
-    let mut all_bonds = Vec::new();
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club_name in all_clubs {
-        for user_address in user_address_list.clone() {
-            let cbd = CLUB_BONDING_DETAILS
-                .may_load(storage, (&club_name.clone(), &user_address.clone()))?;
-            match cbd {
-                Some(bonding_details) => {
-                    for bond in bonding_details {
-                        all_bonds.push(bond);
-                    }
-                }
-                None => {}
-            }
-        }
+/// Emissions of the schedule from its last emission up to now, and the time they run to
+fn pending_emissions(schedule: &EmissionSchedule, now: Timestamp) -> (Uint128, Timestamp) {
+    let mut emit_until = now;
+    if emit_until > schedule.end_timestamp {
+        emit_until = schedule.end_timestamp;
     }
-    return Ok(all_bonds);
+    if emit_until <= schedule.last_emission_timestamp {
+        return (Uint128::zero(), schedule.last_emission_timestamp);
+    }
+    let seconds = emit_until.seconds() - schedule.last_emission_timestamp.seconds();
+    let mut amount = schedule.reward_per_second * Uint128::from(seconds);
+    // never emit more than was funded
+    if amount > schedule.funded_amount - schedule.emitted_amount {
+        amount = schedule.funded_amount - schedule.emitted_amount;
+    }
+    (amount, emit_until)
 }
 
-fn query_reward_amount(storage: &dyn Storage) -> StdResult<Uint128> {
-    let reward: Uint128 = REWARD.may_load(storage)?.unwrap_or_default();
-    return Ok(reward);
+/// Moves the emissions of the schedule up to now into the reward
+fn emit_scheduled_rewards(storage: &mut dyn Storage, now: Timestamp) -> StdResult<()> {
+    let schedule = EMISSION_SCHEDULE.may_load(storage)?;
+    let mut schedule = match schedule {
+        Some(es) => es,
+        None => return Ok(()),
+    };
+    let (amount, emit_until) = pending_emissions(&schedule, now);
+    if amount == Uint128::zero() {
+        return Ok(());
+    }
+    schedule.emitted_amount += amount;
+    schedule.last_emission_timestamp = emit_until;
+    EMISSION_SCHEDULE.save(storage, &schedule)?;
+
+    let fury = fury_denom(storage)?;
+    add_reward(storage, fury.clone(), amount)?;
+    REWARDS_GIVEN_IN_CURRENT_TIMESTAMP.remove(storage, denom_key(&fury));
+    Ok(())
 }
 
-fn query_config(storage: &dyn Storage) -> StdResult<Config> {
-    let config = CONFIG.load(storage)?;
-    return Ok(config);
-}
+fn claim_staker_rewards(
+    deps: DepsMut,
+    info: MessageInfo,
+    staker: String,
+    club_name: String,
+) -> Result<Response, ContractError> {
+    let mut transfer_confirmed = false;
+    let mut amount = Uint128::zero();
+    let staker_addr = deps.api.addr_validate(&staker)?;
+    //Check if withdrawer is same as invoker
+    if staker_addr != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
 
-fn query_get_fee_wallet(storage: &dyn Storage) -> StdResult<String> {
-    let address = FEE_WALLET.load(storage)?;
-    return Ok(address);
-}
+    let required_ust_fees = required_platform_fees(
+        deps.as_ref(),
+        &info.sender,
+        to_binary(&ExecuteMsg::ClaimStakerRewards {
+            staker: staker.clone(),
+            club_name: club_name.clone(),
+        })?,
+    )?;
+    let mut fees = Uint128::zero();
+    for fund in info.funds.clone() {
+        if fund.denom == uusd(&deps)? {
+            fees = fees.checked_add(fund.amount).unwrap();
+        }
+    }
+    let adjusted_ust_fees = required_ust_fees * (Uint128::from(NINETY_NINE_NINE_PERCENT))
+        / (Uint128::from(HUNDRED_PERCENT));
+    if fees < adjusted_ust_fees {
+        return Err(ContractError::InsufficientFees {
+            required: required_ust_fees,
+            received: fees,
+        });
+    }
 
-fn query_staker_rewards(deps: Deps, staker: String, club_name: String) -> StdResult<Uint128> {
     // Get the exising stakes for this club
     let mut stakes = Vec::new();
     let all_stakes =
-        CLUB_STAKING_DETAILS.may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
     match all_stakes {
         Some(some_stakes) => {
             stakes = some_stakes;
         }
         None => {}
     }
-    let mut amount = Uint128::zero();
-    for stake in stakes {
+
+    let existing_stakes = stakes.clone();
+    let mut updated_stakes = Vec::new();
+    for stake in existing_stakes {
+        let mut updated_stake = stake.clone();
         if staker == stake.staker_address {
-            amount += stake.reward_amount;
+            amount += updated_stake.reward_amount;
+            updated_stake.reward_amount = Uint128::zero();
+            // confirm transfer to staker wallet
+            transfer_confirmed = true;
         }
+        updated_stakes.push(updated_stake);
     }
-    return Ok(amount);
-}
+    club_staking_details().save(
+        deps.storage,
+        (&club_name.clone(), &staker.clone()),
+        &updated_stakes,
+    )?;
 
-fn query_club_ownership_details(
-    storage: &dyn Storage,
-    club_name: String,
-) -> StdResult<ClubOwnershipDetails> {
-    let cod = CLUB_OWNERSHIP_DETAILS.may_load(storage, club_name)?;
-    match cod {
-        Some(cod) => return Ok(cod),
-        None => return Err(StdError::generic_err("No ownership details found")),
-    };
-}
+    if transfer_confirmed == false {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Not a valid staker for the club"),
+        }));
+    }
+    let partner_payouts = take_partner_rewards_owed(
+        deps.storage,
+        &PARTNER_REWARDS_OWED,
+        club_name.clone(),
+        staker.clone(),
+    )?;
+    if amount == Uint128::zero() && partner_payouts.is_empty() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("No rewards for this user"),
+        }));
+    }
 
-pub fn query_club_previous_owner_details(
-    storage: &dyn Storage,
-    previous_owner: String,
-) -> StdResult<ClubPreviousOwnerDetails> {
-    let cod = CLUB_PREVIOUS_OWNER_DETAILS.may_load(storage, previous_owner)?;
-    match cod {
-        Some(cod) => return Ok(cod),
-        None => return Err(StdError::generic_err("No previous ownership details found")),
-    };
-}
+    let config = CONFIG.load(deps.storage)?;
 
-pub fn query_all_stakes_for_user(
-    storage: &dyn Storage,
-    user_address: String,
-) -> StdResult<Vec<ClubStakingDetails>> {
-    let mut all_stakes = Vec::new();
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club_name in all_clubs {
-        let staking_details =
-            CLUB_STAKING_DETAILS.load(storage, (&club_name.clone(), &user_address.clone()))?;
-        for stake in staking_details {
-            if stake.staker_address == user_address {
-                all_stakes.push(stake);
-            }
-        }
+    let mut messages = Vec::new();
+    if amount > Uint128::zero() {
+        let transfer_msg = Cw20ExecuteMsg::Transfer {
+            recipient: staker.clone(),
+            amount: amount,
+        };
+        let exec = WasmMsg::Execute {
+            contract_addr: config.fury_token_address.to_string(),
+            msg: to_binary(&transfer_msg).unwrap(),
+            funds: vec![],
+        };
+        messages.push(CosmosMsg::Wasm(exec));
     }
-    return Ok(all_stakes);
-}
+    messages.extend(partner_payouts);
 
-pub fn query_club_bonding_details_for_user(
-    storage: &dyn Storage,
-    club_name: String,
-    user_address: String,
-) -> StdResult<Vec<ClubBondingDetails>> {
-    let mut bonds: Vec<ClubBondingDetails> = Vec::new();
-    let cbd =
-        CLUB_BONDING_DETAILS.may_load(storage, (&club_name.clone(), &user_address.clone()))?;
-    match cbd {
-        Some(cbd) => {
+    let fee_wallet = FEE_WALLET.load(deps.storage)?;
+
+    let send_bank: CosmosMsg = CosmosMsg::Bank(BankMsg::Send {
+        to_address: fee_wallet,
+        amount: info.funds,
+    });
+    let data_msg = format!("Amount {} transferred", amount).into_bytes();
+    return Ok(Response::new()
+        .add_messages(messages)
+        .add_message(send_bank)
+        .add_attribute("action", "staking_reward_claim")
+        .add_attribute("staker", staker)
+        .add_attribute("amount", amount.to_string())
+        .set_data(data_msg));
+}
+
+fn calculate_and_distribute_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker_list: Vec<String>,
+    club_name: String,
+    is_first_batch: bool,
+    is_final_batch: bool,
+) -> Result<Response, ContractError> {
+    // Check if this is executed by main/transaction wallet
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("not authorised"),
+        }));
+    }
+    let mut next_reward_time = CLUB_REWARD_NEXT_TIMESTAMP
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    println!(
+        "now = {:?} next_reward_time = {:?} periodicity = {:?} is_first_batch = {:?} is_final_batch = {:?}",
+        env.block.time, next_reward_time, config.reward_periodicity, is_first_batch, is_final_batch
+    );
+
+    let saved_next_reward_timestamp = next_reward_time;
+
+    if env.block.time < next_reward_time {
+        println!("Time for Reward not yet arrived");
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Time for Reward not yet arrived"),
+        }));
+    }
+    if is_first_batch {
+        // stream the scheduled emissions into this round
+        emit_scheduled_rewards(deps.storage, env.block.time)?;
+    }
+    let fury = fury_denom(deps.storage)?;
+    let total_reward = reward_amount(deps.storage, &fury)?;
+
+    if is_final_batch {
+        if next_reward_time < env.block.time {
+            next_reward_time = next_reward_time.plus_seconds(config.reward_periodicity);
+        }
+        println!("setting next_reward_time = {:?}", next_reward_time);
+        CLUB_REWARD_NEXT_TIMESTAMP.save(deps.storage, &next_reward_time)?;
+    }
+
+    // owner reward goes to the current holder of the club token
+    update_club_owner_from_nft(deps.branch(), env.clone(), club_name.clone())?;
+
+    // No need to calculate if there is no reward amount
+    let has_partner_rewards = query_partner_rewards(deps.storage)?
+        .into_iter()
+        .any(|partner_reward| partner_reward.amount > Uint128::zero());
+    if total_reward == Uint128::zero() && !has_partner_rewards {
+        return Ok(Response::new()
+            .add_attribute("response", "no accumulated rewards")
+            .add_attribute("next_timestamp", next_reward_time.to_string()));
+    }
+    distribute_reward_to_club_stakers(
+        deps,
+        env,
+        config.reward_periodicity,
+        saved_next_reward_timestamp,
+        staker_list.clone(),
+        club_name.clone(),
+        total_reward,
+        is_first_batch,
+        is_final_batch,
+    )
+}
+
+fn distribute_reward_to_club_stakers(
+    deps: DepsMut,
+    env: Env,
+    reward_periodicity: u64,
+    saved_next_reward_timestamp: Timestamp,
+    staker_list: Vec<String>,
+    club_name: String,
+    total_reward: Uint128,
+    is_first_batch: bool,
+    is_final_batch: bool,
+) -> Result<Response, ContractError> {
+    let mut winning_clubs_info: WinningClubDetails;
+    if is_first_batch {
+        let response = get_winning_clubs_details(deps.storage, env.block.time)?;
+        winning_clubs_info = WinningClubDetails {
+            total_number_of_clubs: response.0,
+            total_stake_across_all_clubs: response.1,
+            total_stake_in_winning_club: response.2,
+            winner_list: response.3.clone(),
+            total_weighted_stake_across_all_clubs: response.4,
+        };
+        WINNING_CLUB_DETAILS_SNAPSHOT.save(deps.storage, &winning_clubs_info);
+    } else {
+        winning_clubs_info = WINNING_CLUB_DETAILS_SNAPSHOT
+            .may_load(deps.storage)?
+            .unwrap_or_default();
+    }
+    println!("winning_clubs_info = {:?}", winning_clubs_info);
+    let total_number_of_clubs = winning_clubs_info.total_number_of_clubs;
+    let total_stake_across_all_clubs = winning_clubs_info.total_stake_across_all_clubs;
+    let total_stake_in_winning_club = winning_clubs_info.total_stake_in_winning_club;
+    let mut total_weighted_stake_across_all_clubs =
+        winning_clubs_info.total_weighted_stake_across_all_clubs;
+    if total_weighted_stake_across_all_clubs == Uint128::zero() {
+        total_weighted_stake_across_all_clubs = total_stake_across_all_clubs;
+    }
+    let mut total_weighted_stake_in_club = CLUB_WEIGHTED_STAKE_SNAPSHOT
+        .may_load(deps.storage, club_name.clone())?
+        .unwrap_or_default();
+    if total_weighted_stake_in_club == Uint128::zero() {
+        total_weighted_stake_in_club = total_stake_in_winning_club;
+    }
+    let winner_list = winning_clubs_info.winner_list.clone();
+    let is_club_a_winner = is_winning_club(club_name.clone(), winner_list.clone());
+    let club_details = query_club_ownership_details(deps.storage, club_name.clone())?;
+    let club_owner_address = club_details.owner_address.clone();
+    let num_of_winners = winner_list.len() as u64;
+    let other_club_count = total_number_of_clubs - num_of_winners;
+
+    if !is_club_a_winner && other_club_count <= 0 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("computation error - should never happen"),
+        }));
+    }
+
+    let fury_reward_split = club_reward_split(
+        club_name.clone(),
+        total_reward,
+        is_club_a_winner,
+        num_of_winners,
+        other_club_count,
+        club_details.commission_rate,
+    );
+    let partner_rewards: Vec<DenomAmount> = query_partner_rewards(deps.storage)?
+        .into_iter()
+        .filter(|partner_reward| partner_reward.amount > Uint128::zero())
+        .collect();
+    let mut partner_reward_splits = Vec::new();
+    let mut partner_reward_given_so_far = Vec::new();
+    for partner_reward in partner_rewards.clone() {
+        partner_reward_splits.push(club_reward_split(
+            club_name.clone(),
+            partner_reward.amount,
+            is_club_a_winner,
+            num_of_winners,
+            other_club_count,
+            club_details.commission_rate,
+        ));
+        partner_reward_given_so_far.push(Uint128::zero());
+    }
+
+    let mut reward_given_so_far = Uint128::zero();
+    let mut stake_to_add_for_club = Uint128::zero();
+    for staker in staker_list {
+        let mut updated_stakes_for_this_staker = Vec::new();
+        let csd =
+            club_staking_details().may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
+        let staking_details;
+        match csd {
+            None => {}
+            Some(some_csd) => {
+                staking_details = some_csd;
+
+                for mut stake in staking_details {
+                    let mut updated_stake = stake.clone();
+                    println!("stake = {:?}", updated_stake);
+                    if updated_stake.staking_start_timestamp > env.block.time {
+                        continue;
+                    }
+                    if saved_next_reward_timestamp < env.block.time {
+                        updated_stake.staking_start_timestamp =
+                            saved_next_reward_timestamp.plus_seconds(reward_periodicity);
+                        println!(
+                            "setting timestamp for stake = {:?}",
+                            updated_stake.staking_start_timestamp
+                        );
+                    } else {
+                        updated_stake.staking_start_timestamp = saved_next_reward_timestamp;
+                        println!(
+                            "setting timestamp for stake = {:?}",
+                            updated_stake.staking_start_timestamp
+                        );
+                    }
+
+                    let auto_stake = updated_stake.auto_stake;
+
+                    // an expired lock no longer adds reward weight
+                    if updated_stake.lock_end_timestamp <= env.block.time {
+                        updated_stake.lock_tier = LockTier::NoLock;
+                        updated_stake.staking_duration = CLUB_STAKING_DURATION;
+                    }
+                    let weighted_stake = stake.staked_amount.multiply_ratio(
+                        lock_tier_multiplier(updated_stake.lock_tier),
+                        HUNDRED_PERCENT,
+                    );
+
+                    let is_club_owner = stake.staker_address == club_owner_address;
+                    let reward_for_this_stake = reward_for_stake(
+                        fury_reward_split,
+                        weighted_stake,
+                        total_weighted_stake_across_all_clubs,
+                        total_weighted_stake_in_club,
+                        is_club_a_winner,
+                        is_club_owner,
+                    );
+
+                    // partner rewards are owed in their own denom, they are never compounded.
+                    // The owner commission is owed to the owner apart from its stake
+                    if !partner_rewards.is_empty() {
+                        let mut owed = PARTNER_REWARDS_OWED
+                            .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?
+                            .unwrap_or_default();
+                        let mut commission_owed = OWNER_PARTNER_COMMISSION_OWED
+                            .may_load(deps.storage, (&club_name.clone(), &staker.clone()))?
+                            .unwrap_or_default();
+                        for (i, partner_reward) in partner_rewards.iter().enumerate() {
+                            let partner_reward_for_this_stake = reward_for_stake(
+                                partner_reward_splits[i],
+                                weighted_stake,
+                                total_weighted_stake_across_all_clubs,
+                                total_weighted_stake_in_club,
+                                is_club_a_winner,
+                                false,
+                            );
+                            partner_reward_given_so_far[i] += partner_reward_for_this_stake;
+                            add_denom_amount(
+                                &mut owed,
+                                partner_reward.denom.clone(),
+                                partner_reward_for_this_stake,
+                            );
+                            if is_club_owner {
+                                let owner_commission = partner_reward_splits[i].0;
+                                partner_reward_given_so_far[i] += owner_commission;
+                                add_denom_amount(
+                                    &mut commission_owed,
+                                    partner_reward.denom.clone(),
+                                    owner_commission,
+                                );
+                            }
+                        }
+                        PARTNER_REWARDS_OWED.save(
+                            deps.storage,
+                            (&club_name.clone(), &staker.clone()),
+                            &owed,
+                        )?;
+                        if !commission_owed.is_empty() {
+                            OWNER_PARTNER_COMMISSION_OWED.save(
+                                deps.storage,
+                                (&club_name.clone(), &staker.clone()),
+                                &commission_owed,
+                            )?;
+                        }
+                    }
+
+                    reward_given_so_far += reward_for_this_stake;
+
+                    // a club at its stake limit keeps the reward unstaked
+                    let compounding_allowed = club_stake_limit_allows(
+                        deps.storage,
+                        club_name.clone(),
+                        club_details.total_staked_amount + stake_to_add_for_club,
+                        reward_for_this_stake + updated_stake.reward_amount,
+                    )?;
+                    if auto_stake == SET_AUTO_STAKE && compounding_allowed {
+                        // compound this round's reward and any reward left from before
+                        stake_to_add_for_club +=
+                            reward_for_this_stake + updated_stake.reward_amount;
+                        updated_stake.staked_amount += reward_for_this_stake;
+                        updated_stake.staked_amount += updated_stake.reward_amount;
+                        updated_stake.reward_amount = Uint128::zero();
+                    } else {
+                        updated_stake.reward_amount += reward_for_this_stake;
+                    }
+                    update_club_lock_bonus(deps.storage, &club_name, &stake, &updated_stake)?;
+                    updated_stakes_for_this_staker.push(updated_stake);
+                }
+                club_staking_details().save(
+                    deps.storage,
+                    (&club_name.clone(), &staker.clone()),
+                    &updated_stakes_for_this_staker,
+                )?;
+                sync_voting_power(deps.storage, env.block.height, staker.clone())?;
+            }
+        }
+    }
+
+    // Now update the total stake for this club
+    club_ownership_details().save(
+        deps.storage,
+        club_name.clone(),
+        &ClubOwnershipDetails {
+            club_name: club_details.club_name.clone(),
+            start_timestamp: club_details.start_timestamp,
+            locking_period: club_details.locking_period,
+            owner_address: club_details.owner_address,
+            price_paid: club_details.price_paid,
+            reward_amount: club_details.reward_amount,
+            owner_released: club_details.owner_released,
+            total_staked_amount: club_details.total_staked_amount + stake_to_add_for_club,
+            commission_rate: club_details.commission_rate,
+        },
+    )?;
+
+    println!(
+        "club_name = {:?} total reward = {:?} reward so far = {:?} club stake increased by {:?}",
+        club_name.clone(),
+        total_reward,
+        reward_given_so_far,
+        stake_to_add_for_club
+    );
+
+    // FURY and partner rewards are settled alike, what is left over after the
+    // final batch stays in the pool for the next distribution
+    let mut rewards_given = vec![(
+        DenomAmount {
+            denom: fury_denom(deps.storage)?,
+            amount: total_reward,
+        },
+        reward_given_so_far,
+    )];
+    for (i, partner_reward) in partner_rewards.iter().enumerate() {
+        rewards_given.push((partner_reward.clone(), partner_reward_given_so_far[i]));
+    }
+    for (reward, given_so_far) in rewards_given {
+        let key = denom_key(&reward.denom);
+        let reward_given_in_current_timestamp = REWARDS_GIVEN_IN_CURRENT_TIMESTAMP
+            .may_load(deps.storage, key.clone())?
+            .unwrap_or_default()
+            + given_so_far;
+        println!(
+            "{:?} reward_given_in_current_timestamp = {:?}",
+            key, reward_given_in_current_timestamp
+        );
+        if is_final_batch {
+            let new_reward = reward
+                .amount
+                .saturating_sub(reward_given_in_current_timestamp);
+            REWARDS.save(
+                deps.storage,
+                key.clone(),
+                &DenomAmount {
+                    denom: reward.denom,
+                    amount: new_reward,
+                },
+            )?;
+            REWARDS_GIVEN_IN_CURRENT_TIMESTAMP.remove(deps.storage, key);
+            println!("new_reward = {:?} ", new_reward);
+        } else {
+            REWARDS_GIVEN_IN_CURRENT_TIMESTAMP.save(
+                deps.storage,
+                key,
+                &reward_given_in_current_timestamp,
+            )?;
+        }
+    }
+    Ok(Response::default())
+}
+
+/// Splits a reward pool for a club into
+/// (owner commission, owner reward for the club stakers, reward for the stakers
+/// of a winning club, reward for all stakers)
+fn club_reward_split(
+    club_name: String,
+    total_reward: Uint128,
+    is_club_a_winner: bool,
+    num_of_winners: u64,
+    other_club_count: u64,
+    commission_rate: Uint128,
+) -> (Uint128, Uint128, Uint128, Uint128) {
+    let mut owner_reward = Uint128::zero();
+    let mut reward_for_all_stakers_in_winning_club = Uint128::zero();
+
+    if is_club_a_winner {
+        if other_club_count > 0 {
+            // distribute 1% equally to owners in this winning club
+            owner_reward = total_reward
+                .checked_div(Uint128::from(100u128))
+                .unwrap_or_default()
+                .checked_div(Uint128::from(num_of_winners))
+                .unwrap_or_default();
+            println!(
+                "club_name {:?} owner reward for this winner is {:?}",
+                club_name.clone(),
+                owner_reward
+            );
+        } else {
+            // there are only winning clubs
+            // distribute 3% equally to owners in this winning club
+            owner_reward = total_reward
+                .checked_mul(Uint128::from(3u128))
+                .unwrap_or_default()
+                .checked_div(Uint128::from(100u128))
+                .unwrap_or_default()
+                .checked_div(Uint128::from(num_of_winners))
+                .unwrap_or_default();
+            println!(
+                "all clubs are winners club_name {:?} owner reward for this winner is {:?}",
+                club_name.clone(),
+                owner_reward
+            );
+        }
+        // distribute 19% to stakers in winning club
+        reward_for_all_stakers_in_winning_club = total_reward
+            .checked_mul(Uint128::from(19u128))
+            .unwrap_or_default()
+            .checked_div(Uint128::from(100u128))
+            .unwrap_or_default()
+            .checked_div(Uint128::from(num_of_winners))
+            .unwrap_or_default();
+        println!(
+            "club_name {:?} stakers award in winner is {:?}",
+            club_name.clone(),
+            reward_for_all_stakers_in_winning_club
+        );
+    } else {
+        // other_club_count must be greater than 0
+        // distribute 2% equally to owner in this non winning club
+        owner_reward = total_reward
+            .checked_mul(Uint128::from(2u128))
+            .unwrap_or_default()
+            .checked_div(Uint128::from(100u128))
+            .unwrap_or_default()
+            .checked_div(Uint128::from(other_club_count))
+            .unwrap_or_default();
+        println!(
+            "club_name {:?} owner reward for non winner is {:?}",
+            club_name.clone(),
+            owner_reward
+        );
+    }
+
+    // the owner keeps the commission, the rest of the owner reward
+    // is shared by the stakers of the club
+    let owner_commission = owner_reward.multiply_ratio(commission_rate, HUNDRED_PERCENT);
+    let owner_reward_for_club_stakers = owner_reward - owner_commission;
+
+    // distribute the 78% to all stakers
+    let all_stakers_reward = total_reward
+        .checked_mul(Uint128::from(78u128))
+        .unwrap_or_default()
+        .checked_div(Uint128::from(100u128))
+        .unwrap_or_default();
+
+    (
+        owner_commission,
+        owner_reward_for_club_stakers,
+        reward_for_all_stakers_in_winning_club,
+        all_stakers_reward,
+    )
+}
+
+/// Reward for a stake out of a reward pool split by club_reward_split
+fn reward_for_stake(
+    reward_split: (Uint128, Uint128, Uint128, Uint128),
+    weighted_stake: Uint128,
+    total_weighted_stake_across_all_clubs: Uint128,
+    total_weighted_stake_in_club: Uint128,
+    is_club_a_winner: bool,
+    is_club_owner: bool,
+) -> Uint128 {
+    let (
+        owner_commission,
+        owner_reward_for_club_stakers,
+        reward_for_all_stakers_in_winning_club,
+        all_stakers_reward,
+    ) = reward_split;
+
+    // Calculate for All Staker - 78% proportional
+    let mut reward_for_this_stake = all_stakers_reward
+        .checked_mul(weighted_stake)
+        .unwrap_or_default()
+        .checked_div(total_weighted_stake_across_all_clubs)
+        .unwrap_or_default();
+
+    if is_club_a_winner {
+        // Calculate for Winning Club Staker - 19% proportional
+        reward_for_this_stake += reward_for_all_stakers_in_winning_club
+            .checked_mul(weighted_stake)
+            .unwrap_or_default()
+            .checked_div(total_weighted_stake_in_club)
+            .unwrap_or_default();
+    }
+
+    // Calculate for Club Staker - the owner reward less the owner commission, proportional
+    reward_for_this_stake += owner_reward_for_club_stakers
+        .checked_mul(weighted_stake)
+        .unwrap_or_default()
+        .checked_div(total_weighted_stake_in_club)
+        .unwrap_or_default();
+
+    if is_club_owner {
+        // Calculate for Club Owner - commission on (1% or 3% for winner owner) or 2% for non-winner owner
+        reward_for_this_stake += owner_commission;
+    }
+    reward_for_this_stake
+}
+
+/// Key of a denom in the reward maps
+fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(native_denom) => format!("native:{}", native_denom),
+        Denom::Cw20(cw20_address) => format!("cw20:{}", cw20_address),
+    }
+}
+
+/// Denom of the FURY token, the FURY reward is kept under it in REWARDS
+fn fury_denom(storage: &dyn Storage) -> StdResult<Denom> {
+    let config = CONFIG.load(storage)?;
+    Ok(Denom::Cw20(config.fury_token_address))
+}
+
+/// Reward waiting for distribution in the denom
+fn reward_amount(storage: &dyn Storage, denom: &Denom) -> StdResult<Uint128> {
+    let reward = REWARDS.may_load(storage, denom_key(denom))?;
+    match reward {
+        Some(reward) => Ok(reward.amount),
+        None => Ok(Uint128::zero()),
+    }
+}
+
+fn add_reward(storage: &mut dyn Storage, denom: Denom, amount: Uint128) -> StdResult<()> {
+    let existing_reward = reward_amount(storage, &denom)?;
+    REWARDS.save(
+        storage,
+        denom_key(&denom),
+        &DenomAmount {
+            denom: denom,
+            amount: existing_reward + amount,
+        },
+    )
+}
+
+fn add_denom_amount(denom_amounts: &mut Vec<DenomAmount>, denom: Denom, amount: Uint128) {
+    if amount == Uint128::zero() {
+        return;
+    }
+    for denom_amount in denom_amounts.iter_mut() {
+        if denom_amount.denom == denom {
+            denom_amount.amount += amount;
+            return;
+        }
+    }
+    denom_amounts.push(DenomAmount {
+        denom: denom,
+        amount: amount,
+    });
+}
+
+/// Message paying out a partner reward from the contract
+fn partner_reward_payout_msg(denom_amount: DenomAmount, recipient: String) -> StdResult<CosmosMsg> {
+    match denom_amount.denom {
+        Denom::Native(native_denom) => Ok(CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient,
+            amount: vec![Coin {
+                denom: native_denom,
+                amount: denom_amount.amount,
+            }],
+        })),
+        Denom::Cw20(cw20_address) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: cw20_address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient,
+                amount: denom_amount.amount,
+            })?,
+            funds: vec![],
+        })),
+    }
+}
+
+fn get_winning_clubs_details(
+    storage: &mut dyn Storage,
+    now: Timestamp,
+) -> StdResult<(u64, Uint128, Uint128, Vec<String>, Uint128)> {
+    let mut max_incremental_stake_value = 0i128 - MAX_UFURY_COUNT;
+    let mut max_total_stake_value = Uint128::zero();
+
+    let mut total_number_of_clubs = 0u64;
+    let mut total_stake_across_all_clubs = Uint128::zero();
+    let mut total_stake_in_winning_club = Uint128::zero();
+    let mut total_weighted_stake_across_all_clubs = Uint128::zero();
+    let mut winners: Vec<String> = Vec::new();
+
+    let all_clubs: Vec<String> = club_ownership_details()
+        .keys(storage, None, None, Order::Ascending)
+        .map(|k| k.unwrap())
+        .collect();
+    for club in all_clubs {
+        let club_details = query_club_ownership_details(storage, club.clone())?;
+        let stake_in_club = club_details.total_staked_amount;
+        total_stake_across_all_clubs += stake_in_club;
+        let staked_amount_u128: u128 = stake_in_club.into();
+        let staked_amount_i128 = staked_amount_u128 as i128;
+        let previous_amount = CLUB_STAKING_SNAPSHOT
+            .may_load(storage, club.clone())?
+            .unwrap_or_default();
+        let previous_amount_u128: u128 = previous_amount.into();
+        let previous_amount_i128 = previous_amount_u128 as i128;
+        let difference_amount = staked_amount_i128 - previous_amount_i128;
+
+        if difference_amount > max_incremental_stake_value {
+            // found a new max_incremental_stake
+            total_stake_in_winning_club = stake_in_club;
+            max_incremental_stake_value = difference_amount;
+            max_total_stake_value = stake_in_club;
+
+            // pop all pre-existing winners
+            let winner_len = winners.len();
+            let mut i = 0;
+            while i < winner_len {
+                winners.swap_remove(0);
+                i += 1;
+            }
+
+            // now add this stake to the winner list
+            winners.push(club.clone());
+        } else if difference_amount == max_incremental_stake_value {
+            if stake_in_club >= max_total_stake_value {
+                total_stake_in_winning_club = stake_in_club;
+                max_incremental_stake_value = difference_amount;
+                max_total_stake_value = stake_in_club;
+                if stake_in_club > max_total_stake_value {
+                    // found a new max_total_stake
+
+                    // pop all pre-existing winners
+                    let winner_len = winners.len();
+                    let mut i = 0;
+                    while i < winner_len {
+                        winners.swap_remove(0);
+                        i += 1;
+                    }
+
+                    // now add this stake to the winner list
+                    winners.push(club.clone());
+                } else if stake_in_club == max_total_stake_value {
+                    // more than one winners have same total and incremental stake
+
+                    // add this stake to the winner list
+                    winners.push(club.clone());
+                }
+                // else skip this club
+            }
+        }
+        // else skip this club
+
+        total_number_of_clubs += 1;
+        CLUB_STAKING_SNAPSHOT.save(storage, club.clone(), &stake_in_club)?;
+
+        let lock_bonus_in_club = active_club_lock_bonus(storage, &club, now)?;
+        let weighted_stake_in_club = stake_in_club + lock_bonus_in_club;
+        total_weighted_stake_across_all_clubs += weighted_stake_in_club;
+        CLUB_WEIGHTED_STAKE_SNAPSHOT.save(storage, club.clone(), &weighted_stake_in_club)?;
+    }
+
+    println!(
+        "total_clubs = {:?}, total_stake = {:?}, winning_stake = {:?}, winners = {:?}",
+        total_number_of_clubs, total_stake_across_all_clubs, total_stake_in_winning_club, winners
+    );
+    Ok((
+        total_number_of_clubs,
+        total_stake_across_all_clubs,
+        total_stake_in_winning_club,
+        winners,
+        total_weighted_stake_across_all_clubs,
+    ))
+}
+
+fn is_winning_club(club_name: String, winner_list: Vec<String>) -> bool {
+    for winner in winner_list {
+        if club_name == winner {
+            return true;
+        }
+    }
+    return false;
+}
+
+fn transfer_from_contract_to_wallet(
+    store: &dyn Storage,
+    wallet_owner: String,
+    amount: Uint128,
+    action: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(store)?;
+
+    let transfer_msg = Cw20ExecuteMsg::Transfer {
+        recipient: wallet_owner,
+        amount: amount,
+    };
+    let exec = WasmMsg::Execute {
+        contract_addr: config.fury_token_address.to_string(),
+        msg: to_binary(&transfer_msg).unwrap(),
+        funds: vec![
+            // Coin {
+            //     denom: token_info.name.to_string(),
+            //     amount: price,
+            // },
+        ],
+    };
+    let send: SubMsg = SubMsg::new(exec);
+    let data_msg = format!("Amount {} transferred", amount).into_bytes();
+    return Ok(Response::new()
+        .add_submessage(send)
+        .add_attribute("action", action)
+        .add_attribute("amount", amount.to_string())
+        .set_data(data_msg));
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::QueryPlatformFees { msg } => to_binary(&query_platform_fees(deps, msg)?),
+        QueryMsg::EstimateFees {
+            action,
+            amount,
+            club_name,
+        } => to_binary(&query_estimate_fees(deps, action, amount, club_name)?),
+        QueryMsg::ClubStakingDetails {
+            club_name,
+            user_list,
+        } => to_binary(&query_club_staking_details(
+            deps.storage,
+            club_name,
+            user_list,
+        )?),
+        QueryMsg::ClubOwnershipDetails { club_name } => {
+            to_binary(&query_club_ownership_details(deps.storage, club_name)?)
+        }
+        QueryMsg::ClubPreviousOwnershipDetails { previous_owner } => to_binary(
+            &query_club_previous_owner_details(deps.storage, previous_owner)?,
+        ),
+        QueryMsg::AllClubOwnershipDetails { start_after, limit } => to_binary(
+            &query_all_club_ownership_details(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::AllPreviousClubOwnershipDetails { start_after, limit } => to_binary(
+            &query_all_previous_club_ownership_details(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::ClubOwnershipDetailsForOwner {
+            owner_address,
+            start_after,
+            limit,
+        } => to_binary(&query_club_ownership_details_for_owner(
+            deps.storage,
+            owner_address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AllStakes { start_after, limit } => {
+            to_binary(&query_all_stakes(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::AllStakesForUser {
+            user_address,
+            start_after,
+            limit,
+        } => to_binary(&query_all_stakes_for_user(
+            deps.storage,
+            user_address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::AllBonds { start_after, limit } => {
+            to_binary(&query_all_bonds(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::AllBondsForUser {
+            user_address,
+            start_after,
+            limit,
+        } => to_binary(&query_all_bonds_for_user(
+            deps.storage,
+            user_address,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::ClubBondingDetailsForUser {
+            club_name,
+            user_address,
+        } => to_binary(&query_club_bonding_details_for_user(
+            deps.storage,
+            club_name,
+            user_address,
+        )?),
+        QueryMsg::RewardAmount {} => to_binary(&query_reward_amount(deps.storage)?),
+        QueryMsg::PartnerRewards {} => to_binary(&query_partner_rewards(deps.storage)?),
+        QueryMsg::PartnerRewardsOwed { staker, club_name } => to_binary(
+            &PARTNER_REWARDS_OWED
+                .may_load(deps.storage, (&club_name, &staker))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::OwnerPartnerCommissionOwed { owner, club_name } => to_binary(
+            &OWNER_PARTNER_COMMISSION_OWED
+                .may_load(deps.storage, (&club_name, &owner))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::QueryStakerRewards { staker, club_name } => {
+            to_binary(&query_staker_rewards(deps, staker, club_name)?)
+        }
+        QueryMsg::Config {} => to_binary(&query_config(deps.storage)?),
+        QueryMsg::VotingPowerAtHeight { address, height } => {
+            let height = height.unwrap_or(env.block.height);
+            let power = STAKED_BALANCES
+                .may_load_at_height(deps.storage, address, height)?
+                .unwrap_or_default();
+            to_binary(&VotingPowerAtHeightResponse {
+                power: power,
+                height: height,
+            })
+        }
+        QueryMsg::TotalPowerAtHeight { height } => {
+            let height = height.unwrap_or(env.block.height);
+            let power = STAKED_TOTAL
+                .may_load_at_height(deps.storage, height)?
+                .unwrap_or_default();
+            to_binary(&TotalPowerAtHeightResponse {
+                power: power,
+                height: height,
+            })
+        }
+        QueryMsg::Schedule {} => to_binary(&query_schedule(deps.storage, env.block.time)?),
+        QueryMsg::GetFeeWallet {} => to_binary(&query_get_fee_wallet(deps.storage)?),
+        QueryMsg::ClubNftContract {} => to_binary(&query_club_nft_contract(deps.storage)?),
+        QueryMsg::FeeExemptAddresses { start_after, limit } => to_binary(
+            &query_fee_exempt_addresses(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::Clubs { start_after, limit } => {
+            to_binary(&query_clubs(deps.storage, start_after, limit)?)
+        }
+        QueryMsg::ClaimableUnbonds { staker, club_name } => to_binary(&query_claimable_unbonds(
+            deps.storage,
+            env,
+            staker,
+            club_name,
+        )?),
+    }
+}
+
+pub fn query_platform_fees(deps: Deps, msg: Binary) -> StdResult<Uint128> {
+    let fee_action;
+    let fury_amount_provided;
+    match from_binary(&msg) {
+        Ok(ExecuteMsg::IncreaseRewardAmount {
+            reward_from: _,
+            amount: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::ChangeConfig { config: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::ChangeFeeWallet { address: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::AddFeeExemptAddress { address: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::RemoveFeeExemptAddress { address: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::SetClubNftContract { address: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::SyncClubOwner { club_name: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::Receive(_)) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::ClaimUnbondedStake {
+            staker: _,
+            club_name: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::SetEarlyExitPenalty { early_exit: _ }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::RegisterClub {
+            club_name: _,
+            display_name: _,
+            sport: _,
+            logo_uri: _,
+            max_total_stake: _,
+            price_override: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::Rebond {
+            club_name: _,
+            amount: _,
+            to_club: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::MoveStake {
+            from_club: _,
+            to_club: _,
+            amount: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::IncreasePartnerReward {}) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::SetAutoStake {
+            club_name: _,
+            enabled: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::SetClubCommission {
+            club_name: _,
+            commission_rate: _,
+        }) => return Ok(Uint128::zero()),
+        Ok(ExecuteMsg::BuyAClub {
+            buyer: _,
+            seller: _,
+            club_name,
+            auto_stake: _,
+        }) => {
+            fee_action = FeeAction::BuyAClub;
+            fury_amount_provided = club_price(deps.storage, club_name)?;
+        }
+        Ok(ExecuteMsg::AssignAClub {
+            buyer: _,
+            seller: _,
+            club_name: _,
+            auto_stake: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::StakeOnAClub {
+            staker,
+            club_name,
+            amount,
+            auto_stake,
+            lock_tier: _,
+        }) => {
+            fee_action = FeeAction::StakeOnAClub;
+            fury_amount_provided = amount;
+        }
+        Ok(ExecuteMsg::AssignStakesToAClub {
+            stake_list: _,
+            club_name: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::ReleaseClub {
+            owner: _,
+            club_name: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::ClaimOwnerRewards {
+            owner: _,
+            club_name: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::ClaimPreviousOwnerRewards { previous_owner: _ }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::StakeWithdrawFromAClub {
+            staker: _,
+            club_name: _,
+            amount,
+            immediate_withdrawal: _,
+        }) => {
+            fee_action = FeeAction::StakeWithdrawFromAClub;
+            fury_amount_provided = amount;
+        }
+        Ok(ExecuteMsg::CalculateAndDistributeRewards {
+            staker_list: _,
+            club_name: _,
+            is_first_batch: _,
+            is_final_batch: _,
+        }) => {
+            return Ok(Uint128::zero());
+        }
+        Ok(ExecuteMsg::ClaimStakerRewards { staker, club_name }) => {
+            fee_action = FeeAction::ClaimStakerRewards;
+            fury_amount_provided = query_staker_rewards(deps, staker, club_name)?;
+        }
+        Err(err) => {
+            return Err(StdError::generic_err(format!("{:?}", err)));
+        }
+    }
+
+    let estimate = query_estimate_fees(deps, fee_action, fury_amount_provided, None)?;
+    return Ok(estimate.total_fees_usdc);
+}
+
+pub fn query_estimate_fees(
+    deps: Deps,
+    action: FeeAction,
+    amount: Uint128,
+    club_name: Option<String>,
+) -> StdResult<FeeEstimateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+    let mut fury_amount_provided = amount;
+    let mut control_fees_percentage = Uint128::zero();
+    match action {
+        FeeAction::BuyAClub => {
+            if amount == Uint128::zero() {
+                match club_name {
+                    Some(club_name) => {
+                        fury_amount_provided = club_price(deps.storage, club_name)?;
+                    }
+                    None => {
+                        fury_amount_provided = config.club_price;
+                    }
+                }
+            }
+        }
+        FeeAction::StakeOnAClub => {
+            control_fees_percentage = config.control_fees;
+        }
+        FeeAction::StakeWithdrawFromAClub => {}
+        FeeAction::ClaimStakerRewards => {}
+    }
+    let ust_equiv_for_fury_response: Token2ForToken1PriceResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: config.pool_address.clone().to_string(),
+            msg: to_binary(&WasmswapQueryMsg::Token2ForToken1Price {
+                token2_amount: fury_amount_provided,
+            })?,
+        }))?;
+    let ust_equiv_for_fury = ust_equiv_for_fury_response.token1_amount;
+    let hundred_percent = Uint128::from(HUNDRED_PERCENT);
+
+    let platform_fees_usdc =
+        ust_equiv_for_fury.multiply_ratio(config.platform_fees, hundred_percent);
+    let transaction_fees_usdc =
+        ust_equiv_for_fury.multiply_ratio(config.transaction_fees, hundred_percent);
+    let control_fees_usdc =
+        ust_equiv_for_fury.multiply_ratio(control_fees_percentage, hundred_percent);
+    let platform_fees_fury =
+        fury_amount_provided.multiply_ratio(config.platform_fees, hundred_percent);
+    let transaction_fees_fury =
+        fury_amount_provided.multiply_ratio(config.transaction_fees, hundred_percent);
+    let control_fees_fury =
+        fury_amount_provided.multiply_ratio(control_fees_percentage, hundred_percent);
+
+    // The totals are the sum of the parts so that the breakdown always adds up
+    return Ok(FeeEstimateResponse {
+        fury_amount: fury_amount_provided,
+        usdc_equivalent: ust_equiv_for_fury,
+        platform_fees_usdc: platform_fees_usdc,
+        transaction_fees_usdc: transaction_fees_usdc,
+        control_fees_usdc: control_fees_usdc,
+        total_fees_usdc: platform_fees_usdc
+            .checked_add(transaction_fees_usdc)?
+            .checked_add(control_fees_usdc)?,
+        platform_fees_fury: platform_fees_fury,
+        transaction_fees_fury: transaction_fees_fury,
+        control_fees_fury: control_fees_fury,
+        total_fees_fury: platform_fees_fury
+            .checked_add(transaction_fees_fury)?
+            .checked_add(control_fees_fury)?,
+    });
+}
+
+pub fn query_club_staking_details(
+    storage: &dyn Storage,
+    club_name: String,
+    user_list: Vec<String>,
+) -> StdResult<Vec<ClubStakingDetails>> {
+    let mut all_stakes = Vec::new();
+    for user in user_list {
+        let csd = club_staking_details().may_load(storage, (&club_name.clone(), &user.clone()))?;
+        match csd {
+            Some(staking_details) => {
+                for stake in staking_details {
+                    all_stakes.push(stake);
+                }
+            }
+            None => {}
+        }
+    }
+    return Ok(all_stakes);
+}
+
+fn query_all_stakes(
+    storage: &dyn Storage,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubStakingDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|(club_name, staker)| Bound::exclusive((club_name.as_str(), staker.as_str())));
+    let mut all_stakes = Vec::new();
+    let entries = club_staking_details()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter(|item| match item {
+            Ok((_, staking_details)) => !staking_details.is_empty(),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, staking_details) in entries {
+        for stake in staking_details {
+            all_stakes.push(stake);
+        }
+    }
+    return Ok(all_stakes);
+}
+
+fn query_all_bonds(
+    storage: &dyn Storage,
+    start_after: Option<(String, String)>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubBondingDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|(club_name, bonder)| Bound::exclusive((club_name.as_str(), bonder.as_str())));
+    let mut all_bonds = Vec::new();
+    let entries = club_bonding_details()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter(|item| match item {
+            Ok((_, bonding_details)) => !bonding_details.is_empty(),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, bonding_details) in entries {
+        for bond in bonding_details {
+            all_bonds.push(bond);
+        }
+    }
+    return Ok(all_bonds);
+}
+
+pub fn query_all_bonds_for_user(
+    storage: &dyn Storage,
+    user_address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubBondingDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|club_name| Bound::exclusive((club_name.as_str(), user_address.as_str())));
+    let mut all_bonds = Vec::new();
+    let entries = club_bonding_details()
+        .idx
+        .bonder
+        .prefix(user_address.clone())
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter(|item| match item {
+            Ok((_, bonding_details)) => !bonding_details.is_empty(),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, bonding_details) in entries {
+        for bond in bonding_details {
+            all_bonds.push(bond);
+        }
+    }
+    return Ok(all_bonds);
+}
+
+fn query_schedule(storage: &dyn Storage, now: Timestamp) -> StdResult<ScheduleResponse> {
+    let config = CONFIG.load(storage)?;
+    let mut total_staked_amount = Uint128::zero();
+    let all_clubs = club_ownership_details()
+        .range(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, club_details) in all_clubs {
+        total_staked_amount += club_details.total_staked_amount;
+    }
+
+    let schedule = EMISSION_SCHEDULE.may_load(storage)?;
+    let mut pending = Uint128::zero();
+    let mut remaining = Uint128::zero();
+    let mut reward_per_period = Uint128::zero();
+    match schedule.clone() {
+        Some(es) => {
+            pending = pending_emissions(&es, now).0;
+            remaining = es.funded_amount - es.emitted_amount;
+            if now < es.end_timestamp {
+                reward_per_period = es.reward_per_second * Uint128::from(config.reward_periodicity);
+            }
+        }
+        None => {}
+    }
+    Ok(ScheduleResponse {
+        schedule: schedule,
+        pending_emissions: pending,
+        remaining_emissions: remaining,
+        reward_per_period: reward_per_period,
+        total_staked_amount: total_staked_amount,
+    })
+}
+
+fn query_partner_rewards(storage: &dyn Storage) -> StdResult<Vec<DenomAmount>> {
+    let fury = fury_denom(storage)?;
+    let rewards = REWARDS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, reward)| reward))
+        .collect::<StdResult<Vec<DenomAmount>>>()?;
+    Ok(rewards
+        .into_iter()
+        .filter(|reward| reward.denom != fury)
+        .collect())
+}
+
+fn query_reward_amount(storage: &dyn Storage) -> StdResult<Uint128> {
+    let fury = fury_denom(storage)?;
+    let reward: Uint128 = reward_amount(storage, &fury)?;
+    return Ok(reward);
+}
+
+fn query_config(storage: &dyn Storage) -> StdResult<Config> {
+    let config = CONFIG.load(storage)?;
+    return Ok(config);
+}
+
+fn query_get_fee_wallet(storage: &dyn Storage) -> StdResult<String> {
+    let address = FEE_WALLET.load(storage)?;
+    return Ok(address);
+}
+
+fn query_clubs(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubMetadata>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let clubs = REGISTERED_CLUBS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, club)| club))
+        .collect::<StdResult<Vec<_>>>()?;
+    return Ok(clubs);
+}
+
+fn query_claimable_unbonds(
+    storage: &dyn Storage,
+    env: Env,
+    staker: String,
+    club_name: String,
+) -> StdResult<ClaimableUnbondsResponse> {
+    let bonds = club_bonding_details()
+        .may_load(storage, (&club_name.clone(), &staker.clone()))?
+        .unwrap_or_default();
+    let mut matured_amount = Uint128::zero();
+    let mut unbonding_amount = Uint128::zero();
+    let mut next_maturity_timestamp: Option<Timestamp> = None;
+    for bond in bonds {
+        if bond_is_matured(&bond, &env) {
+            matured_amount += bond.bonded_amount;
+        } else {
+            unbonding_amount += bond.bonded_amount;
+            let maturity = bond
+                .bonding_start_timestamp
+                .plus_seconds(bond.bonding_duration);
+            match next_maturity_timestamp {
+                Some(next) if next <= maturity => {}
+                _ => next_maturity_timestamp = Some(maturity),
+            }
+        }
+    }
+    return Ok(ClaimableUnbondsResponse {
+        matured_amount: matured_amount,
+        unbonding_amount: unbonding_amount,
+        next_maturity_timestamp: next_maturity_timestamp,
+        early_exit_penalty: EARLY_EXIT_PENALTY.may_load(storage)?,
+    });
+}
+
+fn query_fee_exempt_addresses(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let addresses = FEE_EXEMPT_ADDRESSES
+        .keys(storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+    return Ok(addresses);
+}
+
+fn query_club_nft_contract(storage: &dyn Storage) -> StdResult<Option<Addr>> {
+    let address = CLUB_NFT_CONTRACT.may_load(storage)?;
+    return Ok(address);
+}
+
+/// Returns the holder of the club token, or None when no token exists for the club
+fn query_club_nft_owner(deps: Deps, club_name: String) -> StdResult<Option<String>> {
+    match query_club_nft_owner_of(deps, club_name)? {
+        Some(owner_of) => return Ok(Some(owner_of.owner)),
+        None => return Ok(None),
+    }
+}
+
+fn query_club_nft_owner_of(deps: Deps, club_name: String) -> StdResult<Option<OwnerOfResponse>> {
+    let nft_contract;
+    match CLUB_NFT_CONTRACT.may_load(deps.storage)? {
+        Some(nc) => nft_contract = nc,
+        None => return Ok(None),
+    }
+    // token not minted yet, e.g. for clubs bought before the contract was set
+    if !CLUB_NFT_MINTED.has(deps.storage, (&nft_contract, &club_name)) {
+        return Ok(None);
+    }
+    let owner_response: StdResult<OwnerOfResponse> =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: nft_contract.to_string(),
+            msg: to_binary(&Cw721QueryMsg::OwnerOf {
+                token_id: club_name,
+                include_expired: Some(false),
+            })?,
+        }));
+    match owner_response {
+        Ok(resp) => return Ok(Some(resp)),
+        // the token was burned
+        Err(StdError::NotFound { .. }) => return Ok(None),
+        Err(err) => return Err(err),
+    }
+}
+
+fn query_staker_rewards(deps: Deps, staker: String, club_name: String) -> StdResult<Uint128> {
+    // Get the exising stakes for this club
+    let mut stakes = Vec::new();
+    let all_stakes =
+        club_staking_details().may_load(deps.storage, (&club_name.clone(), &staker.clone()))?;
+    match all_stakes {
+        Some(some_stakes) => {
+            stakes = some_stakes;
+        }
+        None => {}
+    }
+    let mut amount = Uint128::zero();
+    for stake in stakes {
+        if staker == stake.staker_address {
+            amount += stake.reward_amount;
+        }
+    }
+    return Ok(amount);
+}
+
+fn query_club_ownership_details(
+    storage: &dyn Storage,
+    club_name: String,
+) -> StdResult<ClubOwnershipDetails> {
+    let cod = club_ownership_details().may_load(storage, club_name)?;
+    match cod {
+        Some(cod) => return Ok(cod),
+        None => return Err(StdError::generic_err("No ownership details found")),
+    };
+}
+
+pub fn query_club_previous_owner_details(
+    storage: &dyn Storage,
+    previous_owner: String,
+) -> StdResult<ClubPreviousOwnerDetails> {
+    let cod = CLUB_PREVIOUS_OWNER_DETAILS.may_load(storage, previous_owner)?;
+    match cod {
+        Some(cod) => return Ok(cod),
+        None => return Err(StdError::generic_err("No previous ownership details found")),
+    };
+}
+
+pub fn query_all_stakes_for_user(
+    storage: &dyn Storage,
+    user_address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubStakingDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after
+        .as_ref()
+        .map(|club_name| Bound::exclusive((club_name.as_str(), user_address.as_str())));
+    let mut all_stakes = Vec::new();
+    let entries = club_staking_details()
+        .idx
+        .staker
+        .prefix(user_address.clone())
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .filter(|item| match item {
+            Ok((_, staking_details)) => !staking_details.is_empty(),
+            Err(_) => true,
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, staking_details) in entries {
+        for stake in staking_details {
+            all_stakes.push(stake);
+        }
+    }
+    return Ok(all_stakes);
+}
+
+pub fn query_club_bonding_details_for_user(
+    storage: &dyn Storage,
+    club_name: String,
+    user_address: String,
+) -> StdResult<Vec<ClubBondingDetails>> {
+    let mut bonds: Vec<ClubBondingDetails> = Vec::new();
+    let cbd =
+        club_bonding_details().may_load(storage, (&club_name.clone(), &user_address.clone()))?;
+    match cbd {
+        Some(cbd) => {
             bonds = cbd;
         }
-        None => return Err(StdError::generic_err("No bonding details found")),
-    };
-    let mut all_bonds = Vec::new();
-    for bond in bonds {
-        if bond.bonder_address == user_address {
-            all_bonds.push(bond);
+        None => return Err(StdError::generic_err("No bonding details found")),
+    };
+    let mut all_bonds = Vec::new();
+    for bond in bonds {
+        if bond.bonder_address == user_address {
+            all_bonds.push(bond);
+        }
+    }
+    return Ok(all_bonds);
+}
+
+pub fn query_all_club_ownership_details(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubOwnershipDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let all_owners = club_ownership_details()
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, owner_details)| owner_details))
+        .collect::<StdResult<Vec<_>>>()?;
+    return Ok(all_owners);
+}
+
+pub fn query_all_previous_club_ownership_details(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubPreviousOwnerDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let pcod = CLUB_PREVIOUS_OWNER_DETAILS
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, previous_details)| previous_details))
+        .collect::<StdResult<Vec<_>>>()?;
+    return Ok(pcod);
+}
+
+pub fn query_club_ownership_details_for_owner(
+    storage: &dyn Storage,
+    owner_address: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<ClubOwnershipDetails>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(Bound::exclusive);
+    let all_owners = club_ownership_details()
+        .idx
+        .owner
+        .prefix(owner_address)
+        .range(storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, owner_details)| owner_details))
+        .collect::<StdResult<Vec<_>>>()?;
+    return Ok(all_owners);
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::coin;
+    use cosmwasm_std::testing::{
+        mock_dependencies, mock_env, mock_info, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{
+        coins, from_binary, Addr, ContractResult, CosmosMsg, OwnedDeps, StdError, SubMsg,
+        SystemError, SystemResult, WasmMsg,
+    };
+
+    use super::*;
+
+    /// Answers the pool price queries at 1 FURY = 1 USDC
+    fn mock_price_oracle(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        deps.querier.update_wasm(|query: &WasmQuery| match query {
+            WasmQuery::Smart { msg, .. } => match from_binary(msg) {
+                Ok(WasmswapQueryMsg::Token2ForToken1Price { token2_amount }) => {
+                    SystemResult::Ok(ContractResult::Ok(
+                        to_binary(&Token2ForToken1PriceResponse {
+                            token1_amount: token2_amount,
+                        })
+                        .unwrap(),
+                    ))
+                }
+                _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                    kind: "price query".to_string(),
+                }),
+            },
+            _ => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "price query".to_string(),
+            }),
+        });
+    }
+
+    /// Registers the clubs used by the tests
+    fn register_test_clubs(deps: &mut OwnedDeps<MockStorage, MockApi, MockQuerier>) {
+        for club_name in ["CLUB001", "CLUB002", "CLUB003"] {
+            register_club(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("admin11111", &[]),
+                ClubMetadata {
+                    club_name: club_name.to_string(),
+                    display_name: club_name.to_string(),
+                    sport: "football".to_string(),
+                    logo_uri: None,
+                    max_total_stake: None,
+                    price_override: None,
+                },
+            )
+            .unwrap();
+        }
+    }
+
+    #[test]
+    fn test_buying_of_club() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match query_res {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+                assert_eq!(cod.reward_amount, Uint128::from(CLUB_BUYING_REWARD_AMOUNT));
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_owner_claim_rewards() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        let result = buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+        println!("result = {:?}", result);
+        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match query_res {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+                assert_eq!(cod.reward_amount, Uint128::from(CLUB_BUYING_REWARD_AMOUNT));
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        claim_owner_rewards(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let queryResAfter = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryResAfter {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+                assert_eq!(cod.reward_amount, Uint128::from(0u128));
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_multiple_buying_of_club() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        let owner2_info = mock_info("owner002", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner2_info.clone(),
+            "owner002".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match query_res {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_a_club() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        let owner2_info = mock_info("owner002", &[coin(100000, "uusd")]);
+
+        println!("Now assigning the club to owner001");
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            SET_AUTO_STAKE,
+        );
+
+        let queryRes0 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryRes0 {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(0u128));
+                assert_eq!(cod.owner_released, false);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        println!("Now releasing the club from owner001");
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let queryRes1 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryRes1 {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(0u128));
+                assert_eq!(cod.owner_released, true);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        println!("Now assigning the club to owner002");
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner002".to_string(),
+            Some("owner001".to_string()),
+            "CLUB001".to_string(),
+            SET_AUTO_STAKE,
+        );
+
+        println!("Now releasing the club from owner002");
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner2_info.clone(),
+            "owner002".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let queryRes2 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryRes2 {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner002".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(0u128));
+                assert_eq!(cod.owner_released, true);
+                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
+                club_ownership_details().save(&mut deps.storage, "CLUB001".to_string(), &cod);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        println!("Now trying to assign the club to owner003 - should fail");
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner003".to_string(),
+            Some("owner002".to_string()),
+            "CLUB001".to_string(),
+            SET_AUTO_STAKE,
+        );
+
+        let queryRes3 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryRes3 {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner002".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(0u128));
+                assert_eq!(cod.owner_released, true);
+                assert_eq!(cod.start_timestamp, now.minus_seconds(22 * 24 * 60 * 60));
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_assign_stakes_to_a_club() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+
+        println!("Now assigning the club to owner001");
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            SET_AUTO_STAKE,
+        );
+
+        let queryRes0 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryRes0 {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(0u128));
+                assert_eq!(cod.owner_released, false);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        let mut stake_list: Vec<ClubStakingDetails> = Vec::new();
+        let mut user_address_list = Vec::new();
+        user_address_list.push("owner001".to_string());
+        for i in 1..7 {
+            let mut staker = String::default();
+            match i {
+                1 => {
+                    staker = "staker001".to_string();
+                }
+                2 => {
+                    staker = "staker002".to_string();
+                }
+                3 => {
+                    staker = "staker003".to_string();
+                }
+                4 => {
+                    staker = "staker004".to_string();
+                }
+                5 => {
+                    staker = "staker005".to_string();
+                }
+                6 => {
+                    staker = "staker006".to_string();
+                }
+                _ => {}
+            }
+            user_address_list.push(staker.clone());
+            println!("staker is {}", staker);
+            stake_list.push(ClubStakingDetails {
+                staker_address: staker,
+                staking_start_timestamp: now,
+                staked_amount: Uint128::from(330000u128),
+                staking_duration: CLUB_STAKING_DURATION,
+                club_name: "CLUB001".to_string(),
+                reward_amount: Uint128::from(CLUB_STAKING_REWARD_AMOUNT),
+                auto_stake: SET_AUTO_STAKE,
+                lock_tier: LockTier::NoLock,
+                lock_end_timestamp: now,
+            });
+        }
+
+        let staker6Info = mock_info("staker006", &[coin(100000, "uusd")]);
+        assign_stakes_to_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            stake_list,
+            "CLUB001".to_string(),
+        );
+
+        let queryRes1 = query_all_stakes(&deps.storage, None, None);
+        match queryRes1 {
+            Ok(all_stakes) => {
+                println!("all stakes : {:?}", all_stakes);
+                assert_eq!(all_stakes.len(), 7);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_buying_of_club_after_releasing_by_prev_owner() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        let mut resp = buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+        println!("{:?}", resp);
+        resp = release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+        println!("{:?}", resp);
+
+        let now = mock_env().block.time; // today
+
+        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match query_res {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
+                club_ownership_details().save(&mut deps.storage, "CLUB001".to_string(), &cod);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let queryResAfterReleasing =
+            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryResAfterReleasing {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, true);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        let owner2_info = mock_info("owner002", &[coin(100000, "uusd")]);
+        let resp = buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner2_info.clone(),
+            "owner002".to_string(),
+            Some("owner001".to_string()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+        println!("{:?}", resp);
+        let queryResAfterSellingByPrevOwner =
+            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryResAfterSellingByPrevOwner {
+            Ok(cod) => {
+                assert_eq!(cod.owner_address, "owner002".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+    }
+
+    #[test]
+    fn test_claim_previous_owner_rewards() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        let mintingContractInfo = mock_info("minting_admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let now = mock_env().block.time; // today
+
+        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match query_res {
+            Ok(mut cod) => {
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
+                club_ownership_details().save(&mut deps.storage, "CLUB001".to_string(), &cod);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            mintingContractInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
+
+        let mut staker_list1 = Vec::new();
+        staker_list1.push("staker001".to_string());
+        staker_list1.push("owner001".to_string());
+        let club_name1 = "CLUB001".to_string();
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            staker_list1.clone(),
+            club_name1,
+            true,
+            true,
+        );
+
+        println!("releasing club");
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        );
+
+        let queryResAfterReleasing =
+            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryResAfterReleasing {
+            Ok(cod) => {
+                println!(
+                    "before - owner:{:?}, reward {:?}",
+                    cod.owner_address, cod.reward_amount
+                );
+                assert_eq!(cod.owner_address, "owner001".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, true);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        println!(
+            "pod:\n {:?}",
+            query_all_previous_club_ownership_details(&deps.storage, None, None)
+        );
+
+        println!("buy a club with new owner");
+        let owner2_info = mock_info("owner002", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner2_info.clone(),
+            "owner002".to_string(),
+            Some("owner001".to_string()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        let queryResAfterSellingByPrevOwner =
+            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
+        match queryResAfterSellingByPrevOwner {
+            Ok(cod) => {
+                println!(
+                    "after - owner:{:?}, reward {:?}",
+                    cod.owner_address, cod.reward_amount
+                );
+                assert_eq!(cod.owner_address, "owner002".to_string());
+                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
+                assert_eq!(cod.owner_released, false);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+        println!("checking previous owner details now");
+
+        /*
+        27 Feb 2022, Commenting this out - because the reward is now moved to stake
+                     so there will be no previous owner details
+
+        let queryPrevOwnerDetailsBeforeRewardClaim =
+            query_club_previous_owner_details(&mut deps.storage, "owner001".to_string());
+        match queryPrevOwnerDetailsBeforeRewardClaim {
+            Ok(pod) => {
+                println!(
+                    "before - owner:{:?}, reward {:?}",
+                    pod.previous_owner_address, pod.reward_amount
+                );
+                assert_eq!(pod.previous_owner_address, "owner001".to_string());
+                assert_eq!(pod.reward_amount, Uint128::from(10000u128));
+            }
+            Err(e) => {
+                println!("error parsing cpod header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+        */
+
+        println!(
+            "pod:\n {:?}",
+            query_all_previous_club_ownership_details(&deps.storage, None, None)
+        );
+
+        claim_previous_owner_rewards(deps.as_mut(), owner1_info.clone(), "owner001".to_string());
+        let queryPrevOwnerDetailsAfterRewardClaim =
+            query_club_previous_owner_details(&mut deps.storage, "owner001".to_string())
+                .unwrap_err();
+        assert_eq!(
+            queryPrevOwnerDetailsAfterRewardClaim,
+            (StdError::GenericErr {
+                msg: String::from("No previous ownership details found")
+            })
+        );
+
+        println!(
+            "pod:\n {:?}",
+            query_all_previous_club_ownership_details(&deps.storage, None, None)
+        );
+    }
+
+    #[test]
+    fn test_set_auto_stake_compounds_rewards() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
+
+        let mut staker_list1 = Vec::new();
+        staker_list1.push("staker001".to_string());
+        staker_list1.push("owner001".to_string());
+        let club_name1 = "CLUB001".to_string();
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            staker_list1.clone(),
+            club_name1,
+            true,
+            true,
+        );
+
+        // a wallet without a stake in the club cannot toggle auto stake
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("stranger001", &[]),
+            ExecuteMsg::SetAutoStake {
+                club_name: "CLUB001".to_string(),
+                enabled: true,
+            },
+        );
+        assert!(res.is_err());
+
+        // turning auto stake on compounds the accumulated reward up to the club cap
+        register_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            ClubMetadata {
+                club_name: "CLUB001".to_string(),
+                display_name: "CLUB001".to_string(),
+                sport: "football".to_string(),
+                logo_uri: None,
+                max_total_stake: Some(Uint128::from(500000u128)),
+                price_override: None,
+            },
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            ExecuteMsg::SetAutoStake {
+                club_name: "CLUB001".to_string(),
+                enabled: true,
+            },
+        )
+        .unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].auto_stake, true);
+        assert_eq!(stakes[0].reward_amount, Uint128::from(503000u128));
+        assert_eq!(stakes[0].staked_amount, Uint128::from(500000u128));
+        let club = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club.total_staked_amount, Uint128::from(500000u128));
+
+        // the club is full now, turning it on again only saves the setting
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            ExecuteMsg::SetAutoStake {
+                club_name: "CLUB001".to_string(),
+                enabled: true,
+            },
+        )
+        .unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].auto_stake, true);
+        assert_eq!(stakes[0].reward_amount, Uint128::from(503000u128));
+        assert_eq!(stakes[0].staked_amount, Uint128::from(500000u128));
+
+        // turning it off keeps the stake, later rewards accumulate again
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            ExecuteMsg::SetAutoStake {
+                club_name: "CLUB001".to_string(),
+                enabled: false,
+            },
+        )
+        .unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].auto_stake, false);
+        assert_eq!(stakes[0].staked_amount, Uint128::from(500000u128));
+        let club = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club.total_staked_amount, Uint128::from(500000u128));
+    }
+
+    #[test]
+    fn test_club_commission_shares_owner_reward() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        let commission_msg = |rate: u128| ExecuteMsg::SetClubCommission {
+            club_name: "CLUB001".to_string(),
+            commission_rate: Uint128::from(rate),
+        };
+        // only the owner sets the commission, by at most the max change rate
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            commission_msg(9500),
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            commission_msg(9000),
+        );
+        assert!(res.is_err());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            commission_msg(9500),
+        )
+        .unwrap();
+        let club = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club.commission_rate, Uint128::from(9500u128));
+        // once per reward period
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            commission_msg(9400),
+        );
+        assert!(res.is_err());
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            vec!["staker001".to_string(), "owner001".to_string()],
+            "CLUB001".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+
+        // the owner keeps 95% of the 3% owner reward, the staker gets the other 5% on top
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string(), "owner001".to_string()],
+        )
+        .unwrap();
+        for stake in stakes {
+            if stake.staker_address == "staker001" {
+                assert_eq!(stake.reward_amount, Uint128::from(971500u128));
+            }
+            if stake.staker_address == "owner001" {
+                assert_eq!(stake.reward_amount, Uint128::from(28500u128));
+            }
+        }
+
+        // a new owner can change the commission straight away
+        release_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            "CLUB001".to_string(),
+        )
+        .unwrap();
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner002".to_string(),
+            Some("owner001".to_string()),
+            "CLUB001".to_string(),
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            CLUB_COMMISSION_CHANGE_TIMESTAMP
+                .may_load(&deps.storage, "CLUB001".to_string())
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_partner_rewards_distributed_and_claimed() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        // only the administrator adds partner rewards
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("sponsor001", &[coin(1000, "uusdc")]),
+            ExecuteMsg::IncreasePartnerReward {},
+        );
+        assert_eq!(res.unwrap_err(), ContractError::Unauthorized {});
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("admin11111", &[coin(1000, "uusdc")]),
+            ExecuteMsg::IncreasePartnerReward {},
+        )
+        .unwrap();
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("partner_token", &[]),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "admin11111".to_string(),
+                amount: Uint128::from(2000u128),
+                msg: to_binary(&ReceivedMsg::IncreasePartnerReward {}).unwrap(),
+            }),
+        )
+        .unwrap();
+        assert_eq!(query_partner_rewards(&deps.storage).unwrap().len(), 2);
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            vec!["staker001".to_string(), "owner001".to_string()],
+            "CLUB001".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+
+        // every denom follows the same split as the FURY reward
+        let staker_owed = PARTNER_REWARDS_OWED
+            .load(&deps.storage, ("CLUB001", "staker001"))
+            .unwrap();
+        assert_eq!(
+            staker_owed,
+            vec![
+                DenomAmount {
+                    denom: Denom::Cw20(Addr::unchecked("partner_token")),
+                    amount: Uint128::from(1940u128),
+                },
+                DenomAmount {
+                    denom: Denom::Native("uusdc".to_string()),
+                    amount: Uint128::from(970u128),
+                },
+            ]
+        );
+        for partner_reward in query_partner_rewards(&deps.storage).unwrap() {
+            assert_eq!(partner_reward.amount, Uint128::zero());
         }
-    }
-    return Ok(all_bonds);
-}
+        // the owner commission is kept apart from the owner's share as a staker
+        let owner_owed = PARTNER_REWARDS_OWED
+            .load(&deps.storage, ("CLUB001", "owner001"))
+            .unwrap();
+        assert!(owner_owed.is_empty());
+        let owner_commission = OWNER_PARTNER_COMMISSION_OWED
+            .load(&deps.storage, ("CLUB001", "owner001"))
+            .unwrap();
+        assert_eq!(
+            owner_commission,
+            vec![
+                DenomAmount {
+                    denom: Denom::Cw20(Addr::unchecked("partner_token")),
+                    amount: Uint128::from(60u128),
+                },
+                DenomAmount {
+                    denom: Denom::Native("uusdc".to_string()),
+                    amount: Uint128::from(30u128),
+                },
+            ]
+        );
 
-pub fn query_all_club_ownership_details(
-    storage: &dyn Storage,
-) -> StdResult<Vec<ClubOwnershipDetails>> {
-    let mut all_owners = Vec::new();
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club_name in all_clubs {
-        let owner_details = CLUB_OWNERSHIP_DETAILS.load(storage, club_name)?;
-        all_owners.push(owner_details);
+        // the owner commission is paid out with the owner rewards
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            ExecuteMsg::ClaimOwnerRewards {
+                owner: "owner001".to_string(),
+                club_name: "CLUB001".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(res.messages.len(), 2);
+        assert_eq!(
+            res.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "owner001".to_string(),
+                amount: vec![coin(30, "uusdc")],
+            })
+        );
+        assert!(OWNER_PARTNER_COMMISSION_OWED
+            .may_load(&deps.storage, ("CLUB001", "owner001"))
+            .unwrap()
+            .is_none());
     }
-    return Ok(all_owners);
-}
 
-pub fn query_all_previous_club_ownership_details(
-    storage: &dyn Storage,
-) -> StdResult<Vec<ClubPreviousOwnerDetails>> {
-    let mut pcod = Vec::new();
-    let all_previous: Vec<String> = CLUB_PREVIOUS_OWNER_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for previous in all_previous {
-        let previous_details = CLUB_PREVIOUS_OWNER_DETAILS.load(storage, previous)?;
-        pcod.push(previous_details);
-    }
-    return Ok(pcod);
-}
+    #[test]
+    fn test_emission_schedule_streams_into_rounds() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
 
-pub fn query_club_ownership_details_for_owner(
-    storage: &dyn Storage,
-    owner_address: String,
-) -> StdResult<Vec<ClubOwnershipDetails>> {
-    let mut all_owners = Vec::new();
-    let all_clubs: Vec<String> = CLUB_OWNERSHIP_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
-    for club_name in all_clubs {
-        let owner_details = CLUB_OWNERSHIP_DETAILS.load(storage, club_name)?;
-        if owner_details.owner_address == owner_address {
-            all_owners.push(owner_details);
-        }
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        let mintingContractInfo = mock_info("minting_admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        let fund_msg = |amount: u128| {
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "admin11111".to_string(),
+                amount: Uint128::from(amount),
+                msg: to_binary(&ReceivedMsg::FundEmissionSchedule {
+                    reward_per_second: Uint128::from(10u128),
+                    start_timestamp: now,
+                    end_timestamp: now.plus_seconds(100),
+                })
+                .unwrap(),
+            })
+        };
+        // the schedule must be funded upfront for its whole duration
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mintingContractInfo.clone(),
+            fund_msg(999),
+        );
+        assert!(res.is_err());
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mintingContractInfo.clone(),
+            fund_msg(1000),
+        )
+        .unwrap();
+
+        let mut later_env = mock_env();
+        later_env.block.time = now.plus_seconds(40);
+        let schedule = query_schedule(&deps.storage, later_env.block.time).unwrap();
+        assert_eq!(schedule.pending_emissions, Uint128::from(400u128));
+        assert_eq!(schedule.remaining_emissions, Uint128::from(1000u128));
+        assert_eq!(schedule.reward_per_period, Uint128::from(864000u128));
+        assert_eq!(schedule.total_staked_amount, Uint128::from(33000u128));
+
+        // a round distributes what was emitted since the last round
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            later_env.clone(),
+            adminInfo.clone(),
+            vec!["staker001".to_string(), "owner001".to_string()],
+            "CLUB001".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].reward_amount, Uint128::from(388u128));
+        let schedule = query_schedule(&deps.storage, later_env.block.time).unwrap();
+        assert_eq!(schedule.pending_emissions, Uint128::zero());
+        assert_eq!(schedule.remaining_emissions, Uint128::from(600u128));
+
+        // no new schedule while the current one still has emissions left
+        let res = execute(
+            deps.as_mut(),
+            mock_env(),
+            mintingContractInfo.clone(),
+            fund_msg(1000),
+        );
+        assert!(res.is_err());
+
+        // once the current schedule has ended its pending emissions are settled
+        // and a new schedule can be funded
+        let reward_before = query_reward_amount(&deps.storage).unwrap();
+        let mut ended_env = mock_env();
+        ended_env.block.time = now.plus_seconds(200);
+        execute(
+            deps.as_mut(),
+            ended_env.clone(),
+            mintingContractInfo.clone(),
+            ExecuteMsg::Receive(Cw20ReceiveMsg {
+                sender: "admin11111".to_string(),
+                amount: Uint128::from(500u128),
+                msg: to_binary(&ReceivedMsg::FundEmissionSchedule {
+                    reward_per_second: Uint128::from(5u128),
+                    start_timestamp: now.plus_seconds(200),
+                    end_timestamp: now.plus_seconds(300),
+                })
+                .unwrap(),
+            }),
+        )
+        .unwrap();
+        let schedule = query_schedule(&deps.storage, ended_env.block.time).unwrap();
+        assert_eq!(schedule.pending_emissions, Uint128::zero());
+        assert_eq!(schedule.remaining_emissions, Uint128::from(500u128));
+        assert_eq!(
+            query_reward_amount(&deps.storage).unwrap(),
+            reward_before + Uint128::from(600u128)
+        );
     }
-    return Ok(all_owners);
-}
 
-#[cfg(test)]
-mod tests {
-    use cosmwasm_std::coin;
-    use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
-    use cosmwasm_std::{coins, from_binary, Addr, CosmosMsg, StdError, SubMsg, WasmMsg};
+    #[test]
+    fn test_voting_power_at_height() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
 
-    use super::*;
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        let mut later_env = mock_env();
+        later_env.block.height += 5;
+        stake_on_a_club(
+            deps.as_mut(),
+            later_env.clone(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(10000u128),
+            false,
+            None,
+        )
+        .unwrap();
+
+        let height = mock_env().block.height;
+        let voting_power = |deps: &OwnedDeps<_, _, _>, height: u64| -> Uint128 {
+            let res: VotingPowerAtHeightResponse = from_binary(
+                &query(
+                    deps.as_ref(),
+                    mock_env(),
+                    QueryMsg::VotingPowerAtHeight {
+                        address: "staker001".to_string(),
+                        height: Some(height),
+                    },
+                )
+                .unwrap(),
+            )
+            .unwrap();
+            res.power
+        };
+        // the power at a height is the stake at the start of that block
+        assert_eq!(voting_power(&deps, height), Uint128::zero());
+        assert_eq!(voting_power(&deps, height + 1), Uint128::from(33000u128));
+        assert_eq!(voting_power(&deps, height + 6), Uint128::from(43000u128));
+
+        let res: TotalPowerAtHeightResponse = from_binary(
+            &query(
+                deps.as_ref(),
+                later_env.clone(),
+                QueryMsg::TotalPowerAtHeight { height: None },
+            )
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(res.height, height + 5);
+        assert_eq!(res.power, Uint128::from(33000u128));
+    }
 
     #[test]
-    fn test_buying_of_club() {
+    fn test_claim_rewards_with_no_auto_stake() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -2436,7 +6221,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -2444,26 +6228,80 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            false, // NO AUTO STAKE
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33000u128),
+            false, // NO AUTO STAKE
+            None,
+        );
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+        let mut staker_list1 = Vec::new();
+        staker_list1.push("staker001".to_string());
+        staker_list1.push("owner001".to_string());
+        let club_name1 = "CLUB001".to_string();
+        calculate_and_distribute_rewards(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
-            Some(String::default()),
-            "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
+            adminInfo.clone(),
+            staker_list1.clone(),
+            club_name1,
+            true,
+            true,
         );
 
-        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match query_res {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
-                assert_eq!(cod.reward_amount, Uint128::from(CLUB_BUYING_REWARD_AMOUNT));
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let queryRes = query_all_stakes(&deps.storage, None, None);
+        match queryRes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
+                for stake in all_stakes {
+                    let staker_address = stake.staker_address;
+                    let reward_amount = stake.reward_amount;
+                    let staked_amount = stake.staked_amount;
+                    println!(
+                        "staker : {:?} reward_amount : {:?} staked_amount : {:?}",
+                        staker_address.clone(),
+                        reward_amount,
+                        staked_amount
+                    );
+                    if staker_address == "staker001" {
+                        assert_eq!(reward_amount, Uint128::from(970000u128));
+                        assert_eq!(staked_amount, Uint128::from(33000u128));
+                    }
+                    if staker_address == "owner001" {
+                        assert_eq!(staked_amount, Uint128::from(0u128));
+                        assert_eq!(reward_amount, Uint128::from(30000u128));
+                    }
+                }
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2473,8 +6311,9 @@ mod tests {
     }
 
     #[test]
-    fn test_owner_claim_rewards() {
+    fn test_multiple_staking_on_club_by_same_address() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -2495,7 +6334,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -2503,9 +6341,10 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        let result = buy_a_club(
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
             deps.as_mut(),
             mock_env(),
             owner1_info.clone(),
@@ -2515,36 +6354,53 @@ mod tests {
             Uint128::from(1000000u128),
             SET_AUTO_STAKE,
         );
-        println!("result = {:?}", result);
-        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match query_res {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
-                assert_eq!(cod.reward_amount, Uint128::from(CLUB_BUYING_REWARD_AMOUNT));
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
 
-        claim_owner_rewards(
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(33u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(11u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(42u128),
+            SET_AUTO_STAKE,
+            None,
         );
 
-        let queryResAfter = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryResAfter {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
-                assert_eq!(cod.reward_amount, Uint128::from(0u128));
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
+                for stake in all_stakes {
+                    if stake.staker_address == "staker001".to_string() {
+                        assert_eq!(stake.staked_amount, Uint128::from(86u128));
+                    } else {
+                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
+                    }
+                }
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2554,8 +6410,9 @@ mod tests {
     }
 
     #[test]
-    fn test_multiple_buying_of_club() {
+    fn test_immediate_partial_withdrawals_from_club() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -2576,7 +6433,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -2584,8 +6440,26 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+        set_early_exit_penalty(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            Some(EarlyExitPenalty {
+                penalty: Uint128::from(1000u128),
+                destination: PenaltyDestination::Burn,
+            }),
+        )
+        .unwrap();
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
         buy_a_club(
             deps.as_mut(),
             mock_env(),
@@ -2597,24 +6471,70 @@ mod tests {
             SET_AUTO_STAKE,
         );
 
-        let owner2_info = mock_info("owner002", &[coin(1000, "uusd")]);
-        buy_a_club(
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            owner2_info.clone(),
-            "owner002".to_string(),
-            Some(String::default()),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
+            Uint128::from(99u128),
             SET_AUTO_STAKE,
+            None,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(11u128),
+            IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(12u128),
+            IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(13u128),
+            IMMEDIATE_WITHDRAWAL,
         );
 
-        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match query_res {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
+                for stake in all_stakes {
+                    if stake.staker_address == "staker001".to_string() {
+                        assert_eq!(stake.staked_amount, Uint128::from(63u128));
+                    } else {
+                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
+                    }
+                }
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        let queryBonds = query_all_bonds(&deps.storage, None, None);
+        match queryBonds {
+            Ok(all_bonds) => {
+                assert_eq!(all_bonds.len(), 0);
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2624,8 +6544,9 @@ mod tests {
     }
 
     #[test]
-    fn test_assign_a_club() {
+    fn test_immediate_complete_withdrawals_from_club() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -2646,7 +6567,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -2654,84 +6574,92 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
-
-        let owner1_info = mock_info("owner001", &[coin(1000, "stake")]);
-        let owner2_info = mock_info("owner002", &[coin(1000, "stake")]);
-
-        println!("Now assigning the club to owner001");
-        assign_a_club(
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+        set_early_exit_penalty(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
+            Some(EarlyExitPenalty {
+                penalty: Uint128::from(1000u128),
+                destination: PenaltyDestination::Burn,
+            }),
+        )
+        .unwrap();
+
+        let owner1Info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1Info.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
+            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
         );
 
-        let queryRes0 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryRes0 {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(0u128));
-                assert_eq!(cod.owner_released, false);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        println!("Now releasing the club from owner001");
-        release_club(
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(99u128),
+            SET_AUTO_STAKE,
+            None,
         );
-
-        let queryRes1 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryRes1 {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(0u128));
-                assert_eq!(cod.owner_released, true);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        println!("Now assigning the club to owner002");
-        assign_a_club(
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(11u128),
+            IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
-            "owner002".to_string(),
-            Some("owner001".to_string()),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
-            SET_AUTO_STAKE,
+            Uint128::from(12u128),
+            IMMEDIATE_WITHDRAWAL,
         );
-
-        println!("Now releasing the club from owner002");
-        release_club(
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner2_info.clone(),
-            "owner002".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(13u128),
+            IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(63u128),
+            IMMEDIATE_WITHDRAWAL,
         );
 
-        let queryRes2 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryRes2 {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner002".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(0u128));
-                assert_eq!(cod.owner_released, true);
-                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
-                CLUB_OWNERSHIP_DETAILS.save(&mut deps.storage, "CLUB001".to_string(), &cod);
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2739,24 +6667,10 @@ mod tests {
             }
         }
 
-        println!("Now trying to assign the club to owner003 - should fail");
-        assign_a_club(
-            deps.as_mut(),
-            mock_env(),
-            adminInfo.clone(),
-            "owner003".to_string(),
-            Some("owner002".to_string()),
-            "CLUB001".to_string(),
-            SET_AUTO_STAKE,
-        );
-
-        let queryRes3 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryRes3 {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner002".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(0u128));
-                assert_eq!(cod.owner_released, true);
-                assert_eq!(cod.start_timestamp, now.minus_seconds(22 * 24 * 60 * 60));
+        let queryBonds = query_all_bonds(&deps.storage, None, None);
+        match queryBonds {
+            Ok(all_bonds) => {
+                assert_eq!(all_bonds.len(), 0);
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2766,8 +6680,9 @@ mod tests {
     }
 
     #[test]
-    fn test_assign_stakes_to_a_club() {
+    fn test_non_immediate_complete_withdrawals_from_club() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -2787,35 +6702,84 @@ mod tests {
             max_bonding_limit_per_user: 10u64,
             usdc_ibc_symbol: "uusd".to_string(),
         };
-        let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
+        let admin_info = mock_info("admin11111", &[]);
+        let minting_contract_info = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
+            admin_info.clone(),
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(1000, "stake")]);
-
-        println!("Now assigning the club to owner001");
-        assign_a_club(
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
+            owner1_info.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
+            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
         );
 
-        let queryRes0 = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryRes0 {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(0u128));
-                assert_eq!(cod.owner_released, false);
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(99u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(11u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(12u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(13u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(63u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2823,59 +6787,61 @@ mod tests {
             }
         }
 
-        let mut stake_list: Vec<ClubStakingDetails> = Vec::new();
-        let mut user_address_list = Vec::new();
-        user_address_list.push("owner001".to_string());
-        for i in 1..7 {
-            let mut staker = String::default();
-            match i {
-                1 => {
-                    staker = "staker001".to_string();
-                }
-                2 => {
-                    staker = "staker002".to_string();
-                }
-                3 => {
-                    staker = "staker003".to_string();
-                }
-                4 => {
-                    staker = "staker004".to_string();
-                }
-                5 => {
-                    staker = "staker005".to_string();
-                }
-                6 => {
-                    staker = "staker006".to_string();
+        let queryBonds = query_all_bonds(&deps.storage, None, None);
+        match queryBonds {
+            Ok(all_bonds) => {
+                assert_eq!(all_bonds.len(), 4);
+                for bond in all_bonds {
+                    if bond.bonded_amount != Uint128::from(11u128)
+                        && bond.bonded_amount != Uint128::from(12u128)
+                        && bond.bonded_amount != Uint128::from(13u128)
+                        && bond.bonded_amount != Uint128::from(63u128)
+                    {
+                        println!("bond is {:?} ", bond);
+                        assert_eq!(1, 2);
+                    }
                 }
-                _ => {}
             }
-            user_address_list.push(staker.clone());
-            println!("staker is {}", staker);
-            stake_list.push(ClubStakingDetails {
-                staker_address: staker,
-                staking_start_timestamp: now,
-                staked_amount: Uint128::from(330000u128),
-                staking_duration: CLUB_STAKING_DURATION,
-                club_name: "CLUB001".to_string(),
-                reward_amount: Uint128::from(CLUB_STAKING_REWARD_AMOUNT),
-                auto_stake: SET_AUTO_STAKE,
-            });
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
         }
-
-        let staker6Info = mock_info("staker006", &[coin(10, "stake")]);
-        assign_stakes_to_a_club(
+        let stakerInfo = mock_info("staker002", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
-            stake_list,
+            stakerInfo.clone(),
+            "staker002".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(99u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker002".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(11u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
 
-        let queryRes1 = query_all_stakes(&mut deps.storage, user_address_list);
-        match queryRes1 {
-            Ok(all_stakes) => {
-                println!("all stakes : {:?}", all_stakes);
-                assert_eq!(all_stakes.len(), 7);
+        let queryBonds = query_club_bonding_details_for_user(
+            &mut deps.storage,
+            "CLUB001".to_string(),
+            "staker002".to_string(),
+        );
+        match queryBonds {
+            Ok(all_bonds) => {
+                assert_eq!(all_bonds.len(), 1);
+                for bond in all_bonds {
+                    if bond.bonded_amount != Uint128::from(11u128) {
+                        println!("bond is {:?} ", bond);
+                        assert_eq!(1, 2);
+                    }
+                }
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2885,15 +6851,16 @@ mod tests {
     }
 
     #[test]
-    fn test_buying_of_club_after_releasing_by_prev_owner() {
+    fn test_non_immediate_complete_withdrawals_from_club_with_scheduled_refunds() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
             admin_address: "admin11111".to_string(),
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
-            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_fee_collector_wallet: "feecollector11111".to_string(),
             club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
             reward_periodicity: 24 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
@@ -2907,67 +6874,81 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
-            instantiate_msg,
-        )
-        .unwrap();
-
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        let mut resp = buy_a_club(
+            adminInfo.clone(),
+            instantiate_msg,
+        );
+        register_test_clubs(&mut deps);
+
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner1_info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(99u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(11u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
-            Some(String::default()),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
+            Uint128::from(12u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
-        println!("{:?}", resp);
-        resp = release_club(
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(13u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
-        println!("{:?}", resp);
-
-        let now = mock_env().block.time; // today
-
-        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match query_res {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
-                CLUB_OWNERSHIP_DETAILS.save(&mut deps.storage, "CLUB001".to_string(), &cod);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        release_club(
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(63u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
 
-        let queryResAfterReleasing =
-            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryResAfterReleasing {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, true);
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -2975,36 +6956,76 @@ mod tests {
             }
         }
 
-        let owner2_info = mock_info("owner002", &[coin(0, "uusd")]);
-        let resp = buy_a_club(
-            deps.as_mut(),
-            mock_env(),
-            owner2_info.clone(),
-            "owner002".to_string(),
-            Some("owner001".to_string()),
-            "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
-        );
-        println!("{:?}", resp);
-        let queryResAfterSellingByPrevOwner =
-            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryResAfterSellingByPrevOwner {
-            Ok(cod) => {
-                assert_eq!(cod.owner_address, "owner002".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
+        let now = mock_env().block.time; // today
+
+        let query_bonds = query_all_bonds(&deps.storage, None, None);
+        let club_name = "CLUB001".to_string();
+        match query_bonds {
+            Ok(all_bonds) => {
+                let existing_bonds = all_bonds.clone();
+                let mut updated_bonds = Vec::new();
+                assert_eq!(existing_bonds.len(), 4);
+                for user_addr in user_address_list.clone() {
+                    for bond in existing_bonds.clone() {
+                        let mut updated_bond = bond.clone();
+                        if updated_bond.bonded_amount != Uint128::from(11u128)
+                            && updated_bond.bonded_amount != Uint128::from(12u128)
+                            && updated_bond.bonded_amount != Uint128::from(13u128)
+                            && updated_bond.bonded_amount != Uint128::from(63u128)
+                        {
+                            println!("updated_bond is {:?} ", updated_bond);
+                            assert_eq!(1, 2);
+                        }
+                        if updated_bond.bonded_amount == Uint128::from(63u128) {
+                            updated_bond.bonding_start_timestamp =
+                                now.minus_seconds(8 * 24 * 60 * 60);
+                        }
+                        updated_bonds.push(updated_bond);
+                    }
+                    club_bonding_details().save(
+                        &mut deps.storage,
+                        (&club_name.clone(), &user_addr.clone()),
+                        &updated_bonds,
+                    );
+                }
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
                 assert_eq!(1, 2);
             }
         }
+
+        /*
+                Commenting out as this is no longer used, 6 Apr 2022
+
+                periodically_refund_stakeouts(deps.as_mut(), mock_env(), adminInfo);
+
+                let queryBondsAfterPeriodicRefund = query_all_bonds(&deps.storage, None, None);
+                match queryBondsAfterPeriodicRefund {
+                    Ok(all_bonds) => {
+                        assert_eq!(all_bonds.len(), 3);
+                        for bond in all_bonds {
+                            if bond.bonded_amount != Uint128::from(11u128)
+                                && bond.bonded_amount != Uint128::from(12u128)
+                                && bond.bonded_amount != Uint128::from(13u128)
+                            {
+                                println!("bond is {:?} ", bond);
+                                assert_eq!(1, 2);
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        println!("error parsing header: {:?}", e);
+                        assert_eq!(1, 2);
+                    }
+                }
+        */
     }
 
     #[test]
-    fn test_claim_previous_owner_rewards() {
+    fn test_non_immediate_partial_withdrawals_from_club() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -3025,7 +7046,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -3033,9 +7053,10 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+        let owner1_info = mock_info("owner001", &[coin(100000, "uusd")]);
+        let result = buy_a_club(
             deps.as_mut(),
             mock_env(),
             owner1_info.clone(),
@@ -3045,175 +7066,92 @@ mod tests {
             Uint128::from(1000000u128),
             SET_AUTO_STAKE,
         );
-
-        release_club(
-            deps.as_mut(),
-            mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
-            "CLUB001".to_string(),
-        );
-
-        let now = mock_env().block.time; // today
-
-        let query_res = query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match query_res {
-            Ok(mut cod) => {
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                cod.start_timestamp = now.minus_seconds(22 * 24 * 60 * 60);
-                CLUB_OWNERSHIP_DETAILS.save(&mut deps.storage, "CLUB001".to_string(), &cod);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
+        println!("buy_a_club result = {:?}", result);
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(33u128),
+            Uint128::from(99u128),
             SET_AUTO_STAKE,
+            None,
         );
-
-        increase_reward_amount(
-            deps.as_mut(),
-            mock_env(),
-            mintingContractInfo.clone(),
-            "reward_from abc".to_string(),
-            Uint128::from(1000000u128),
-        );
-
-        let mut staker_list1 = Vec::new();
-        staker_list1.push("staker001".to_string());
-        staker_list1.push("owner001".to_string());
-        let club_name1 = "CLUB001".to_string();
-        calculate_and_distribute_rewards(
-            deps.as_mut(),
-            mock_env(),
-            adminInfo.clone(),
-            staker_list1.clone(),
-            club_name1,
-            true,
-            true,
-        );
-
-        println!("releasing club");
-        release_club(
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
+            Uint128::from(11u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
-
-        let queryResAfterReleasing =
-            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryResAfterReleasing {
-            Ok(cod) => {
-                println!(
-                    "before - owner:{:?}, reward {:?}",
-                    cod.owner_address, cod.reward_amount
-                );
-                assert_eq!(cod.owner_address, "owner001".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, true);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        println!(
-            "pod:\n {:?}",
-            query_all_previous_club_ownership_details(&mut deps.storage)
-        );
-
-        println!("buy a club with new owner");
-        let owner2_info = mock_info("owner002", &[coin(0, "uusd")]);
-        buy_a_club(
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            owner2_info.clone(),
-            "owner002".to_string(),
-            Some("owner001".to_string()),
+            stakerInfo.clone(),
+            "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
+            Uint128::from(12u128),
+            NO_IMMEDIATE_WITHDRAWAL,
         );
-
-        let queryResAfterSellingByPrevOwner =
-            query_club_ownership_details(&mut deps.storage, "CLUB001".to_string());
-        match queryResAfterSellingByPrevOwner {
-            Ok(cod) => {
-                println!(
-                    "after - owner:{:?}, reward {:?}",
-                    cod.owner_address, cod.reward_amount
-                );
-                assert_eq!(cod.owner_address, "owner002".to_string());
-                assert_eq!(cod.price_paid, Uint128::from(1000000u128));
-                assert_eq!(cod.owner_released, false);
+        let result = withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(13u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        );
+        println!("result = {:?}", result);
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("owner001".to_string());
+        let query_stakes = query_all_stakes(&deps.storage, None, None);
+        match query_stakes {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 2);
+                for stake in all_stakes {
+                    if stake.staker_address == "staker001".to_string() {
+                        assert_eq!(stake.staked_amount, Uint128::from(63u128));
+                    } else {
+                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
+                    }
+                }
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
                 assert_eq!(1, 2);
             }
         }
-        println!("checking previous owner details now");
 
-        /*
-        27 Feb 2022, Commenting this out - because the reward is now moved to stake
-                     so there will be no previous owner details
-
-        let queryPrevOwnerDetailsBeforeRewardClaim =
-            query_club_previous_owner_details(&mut deps.storage, "owner001".to_string());
-        match queryPrevOwnerDetailsBeforeRewardClaim {
-            Ok(pod) => {
-                println!(
-                    "before - owner:{:?}, reward {:?}",
-                    pod.previous_owner_address, pod.reward_amount
-                );
-                assert_eq!(pod.previous_owner_address, "owner001".to_string());
-                assert_eq!(pod.reward_amount, Uint128::from(10000u128));
+        let queryBonds = query_all_bonds(&deps.storage, None, None);
+        match queryBonds {
+            Ok(all_bonds) => {
+                assert_eq!(all_bonds.len(), 3);
+                for bond in all_bonds {
+                    if bond.bonded_amount != Uint128::from(11u128)
+                        && bond.bonded_amount != Uint128::from(12u128)
+                        && bond.bonded_amount != Uint128::from(13u128)
+                    {
+                        println!("bond is {:?} ", bond);
+                        assert_eq!(1, 2);
+                    }
+                }
             }
             Err(e) => {
-                println!("error parsing cpod header: {:?}", e);
+                println!("error parsing header: {:?}", e);
                 assert_eq!(1, 2);
             }
         }
-        */
-
-        println!(
-            "pod:\n {:?}",
-            query_all_previous_club_ownership_details(&mut deps.storage)
-        );
-
-        claim_previous_owner_rewards(deps.as_mut(), owner1_info.clone(), "owner001".to_string());
-        let queryPrevOwnerDetailsAfterRewardClaim =
-            query_club_previous_owner_details(&mut deps.storage, "owner001".to_string())
-                .unwrap_err();
-        assert_eq!(
-            queryPrevOwnerDetailsAfterRewardClaim,
-            (StdError::GenericErr {
-                msg: String::from("No previous ownership details found")
-            })
-        );
-
-        println!(
-            "pod:\n {:?}",
-            query_all_previous_club_ownership_details(&mut deps.storage)
-        );
     }
 
     #[test]
-    fn test_claim_rewards_with_no_auto_stake() {
+    fn test_distribute_rewards() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -3221,8 +7159,9 @@ mod tests {
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
             club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
-            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
-            reward_periodicity: 24 * 60 * 60u64,
+            //club_reward_next_timestamp: now.minus_seconds(8 * 60 * 60),
+            club_reward_next_timestamp: now.minus_seconds(1),
+            reward_periodicity: 5 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
             bonding_duration: 5 * 60u64,
             owner_release_locking_duration: 24 * 60 * 60u64,
@@ -3235,6 +7174,7 @@ mod tests {
         };
         let adminInfo = mock_info("admin11111", &[]);
         let mintingContractInfo = mock_info("minting_admin11111", &[]);
+
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -3242,30 +7182,136 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
+        let owner1Info = mock_info("owner001", &[coin(100000, "uusd")]);
         buy_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
+            owner1Info.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
             Uint128::from(1000000u128),
-            false, // NO AUTO STAKE
+            SET_AUTO_STAKE,
+        );
+        let owner2Info = mock_info("owner002", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner2Info.clone(),
+            "owner002".to_string(),
+            Some(String::default()),
+            "CLUB002".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        );
+        let owner3Info = mock_info("owner003", &[coin(100000, "uusd")]);
+        buy_a_club(
+            deps.as_mut(),
+            mock_env(),
+            owner3Info.clone(),
+            "owner003".to_string(),
+            Some(String::default()),
+            "CLUB003".to_string(),
+            Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
         );
 
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
+        let staker1Info = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
+            staker1Info.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(33000u128),
-            false, // NO AUTO STAKE
+            Uint128::from(330000u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        let staker2Info = mock_info("staker002", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker2Info.clone(),
+            "staker002".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(110000u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        let staker3Info = mock_info("staker003", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker3Info.clone(),
+            "staker003".to_string(),
+            "CLUB002".to_string(),
+            Uint128::from(420000u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        let staker4Info = mock_info("staker004", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker4Info.clone(),
+            "staker004".to_string(),
+            "CLUB002".to_string(),
+            Uint128::from(100000u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        let staker5Info = mock_info("staker005", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker5Info.clone(),
+            "staker005".to_string(),
+            "CLUB003".to_string(),
+            Uint128::from(820000u128),
+            SET_AUTO_STAKE,
+            None,
+        );
+
+        let staker6Info = mock_info("staker006", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker6Info.clone(),
+            "staker006".to_string(),
+            "CLUB003".to_string(),
+            Uint128::from(50000u128),
+            SET_AUTO_STAKE,
+            None,
         );
 
+        let mut user_address_list = Vec::new();
+        user_address_list.push("staker001".to_string());
+        user_address_list.push("staker002".to_string());
+        user_address_list.push("staker003".to_string());
+        user_address_list.push("staker004".to_string());
+        user_address_list.push("staker005".to_string());
+        user_address_list.push("staker006".to_string());
+        user_address_list.push("owner001".to_string());
+        user_address_list.push("owner002".to_string());
+        user_address_list.push("owner003".to_string());
+        let queryRes0 = query_all_stakes(&deps.storage, None, None);
+        match queryRes0 {
+            Ok(all_stakes) => {
+                assert_eq!(all_stakes.len(), 9);
+                println!("all stakes : {:?}", all_stakes);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
         increase_reward_amount(
             deps.as_mut(),
             mock_env(),
@@ -3273,45 +7319,121 @@ mod tests {
             "reward_from abc".to_string(),
             Uint128::from(1000000u128),
         );
+        println!("stakes before distribution");
+        let queryRes00 = query_all_stakes(&deps.storage, None, None);
+        match queryRes00 {
+            Ok(all_stakes) => {
+                println!("all stakes : {:?}", all_stakes);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
 
-        let mut staker_list1 = Vec::new();
-        staker_list1.push("staker001".to_string());
-        staker_list1.push("owner001".to_string());
+        let mut queryReward = query_reward_amount(&mut deps.storage);
+        println!("reward amount before distribution: {:?}", queryReward);
         let club_name1 = "CLUB001".to_string();
         calculate_and_distribute_rewards(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            staker_list1.clone(),
+            user_address_list.clone(),
             club_name1,
             true,
+            false,
+        );
+        println!("stakes after first distribution");
+        let queryRes01 = query_all_stakes(&deps.storage, None, None);
+        match queryRes01 {
+            Ok(all_stakes) => {
+                println!("all stakes : {:?}", all_stakes);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        queryReward = query_reward_amount(&mut deps.storage);
+        println!("reward amount after first distribution: {:?}", queryReward);
+        let club_name2 = "CLUB002".to_string();
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            user_address_list.clone(),
+            club_name2,
+            false,
+            false,
+        );
+        println!("stakes after second distribution");
+        let queryRes01 = query_all_stakes(&deps.storage, None, None);
+        match queryRes01 {
+            Ok(all_stakes) => {
+                println!("all stakes : {:?}", all_stakes);
+            }
+            Err(e) => {
+                println!("error parsing header: {:?}", e);
+                assert_eq!(1, 2);
+            }
+        }
+
+        queryReward = query_reward_amount(&mut deps.storage);
+        println!("reward amount after second distribution: {:?}", queryReward);
+        let club_name3 = "CLUB003".to_string();
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            user_address_list.clone(),
+            club_name3,
+            false,
             true,
         );
 
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let queryRes = query_all_stakes(&mut deps.storage, user_address_list);
+        queryReward = query_reward_amount(&mut deps.storage);
+        println!("reward amount after third distribution: {:?}", queryReward);
+        println!("stakes after third distribution");
+        let queryRes = query_all_stakes(&deps.storage, None, None);
         match queryRes {
             Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
+                assert_eq!(all_stakes.len(), 9);
+                println!("all stakes : {:?}", all_stakes);
                 for stake in all_stakes {
                     let staker_address = stake.staker_address;
-                    let reward_amount = stake.reward_amount;
                     let staked_amount = stake.staked_amount;
                     println!(
-                        "staker : {:?} reward_amount : {:?} staked_amount : {:?}",
+                        "staker : {:?} staked_amount : {:?}",
                         staker_address.clone(),
-                        reward_amount,
                         staked_amount
                     );
                     if staker_address == "staker001" {
-                        assert_eq!(reward_amount, Uint128::from(970000u128));
-                        assert_eq!(staked_amount, Uint128::from(33000u128));
+                        assert_eq!(staked_amount, Uint128::from(470655u128));
+                    }
+                    if staker_address == "staker002" {
+                        assert_eq!(staked_amount, Uint128::from(156885u128));
+                    }
+                    if staker_address == "staker003" {
+                        assert_eq!(staked_amount, Uint128::from(599016u128));
+                    }
+                    if staker_address == "staker004" {
+                        assert_eq!(staked_amount, Uint128::from(142622u128));
+                    }
+                    if staker_address == "staker005" {
+                        assert_eq!(staked_amount, Uint128::from(1348588u128));
+                    }
+                    if staker_address == "staker006" {
+                        assert_eq!(staked_amount, Uint128::from(82230u128));
                     }
                     if staker_address == "owner001" {
-                        assert_eq!(staked_amount, Uint128::from(0u128));
-                        assert_eq!(reward_amount, Uint128::from(30000u128));
+                        assert_eq!(staked_amount, Uint128::from(10000u128));
+                    }
+                    if staker_address == "owner002" {
+                        assert_eq!(staked_amount, Uint128::from(10000u128));
+                    }
+                    if staker_address == "owner003" {
+                        assert_eq!(staked_amount, Uint128::from(10000u128));
                     }
                 }
             }
@@ -3320,20 +7442,42 @@ mod tests {
                 assert_eq!(1, 2);
             }
         }
-    }
 
-    #[test]
-    fn test_multiple_staking_on_club_by_same_address() {
-        let mut deps = mock_dependencies();
-        let now = mock_env().block.time; // today
+        // test another attempt to calculate and distribute at the same time
+
+        increase_reward_amount(
+            deps.as_mut(),
+            mock_env(),
+            mintingContractInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        );
+
+        /*
+                let club_name2 = "CLUB001".to_string();
+                let err = execute(
+                    deps.as_mut(),
+                    mock_env(),
+                    adminInfo.clone(),
+                    ExecuteMsg::CalculateAndDistributeRewards {},
+                )
+                .unwrap_err();
+
+                assert_eq!(
+                    err,
+                    (ContractError::Std(StdError::GenericErr {
+                        msg: String::from("Time for Reward not yet arrived")
+                    }))
+                );
 
+        // test by preponing club_reward_next_timestamp
         let instantiate_msg = InstantiateMsg {
             admin_address: "admin11111".to_string(),
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
             club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
             club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
-            reward_periodicity: 24 * 60 * 60u64,
+            reward_periodicity: 5 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
             bonding_duration: 5 * 60u64,
             owner_release_locking_duration: 24 * 60 * 60u64,
@@ -3344,8 +7488,6 @@ mod tests {
             max_bonding_limit_per_user: 10u64,
             usdc_ibc_symbol: "uusd".to_string(),
         };
-        let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -3353,73 +7495,111 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
-            Some(String::default()),
-            "CLUB001".to_string(),
-            Uint128::from(1000000u128),
+            staker4Info.clone(),
+            "staker004".to_string(),
+            "CLUB002".to_string(),
+            Uint128::from(100000u128),
             SET_AUTO_STAKE,
+            None,
         );
-
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
+            staker4Info.clone(),
+            "staker004".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(33u128),
+            Uint128::from(500000u128),
             SET_AUTO_STAKE,
+            None,
         );
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(11u128),
+            staker4Info.clone(),
+            "staker004".to_string(),
+            "CLUB003".to_string(),
+            Uint128::from(126718u128),
             SET_AUTO_STAKE,
+            None,
         );
-        stake_on_a_club(
+        */
+
+        increase_reward_amount(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(42u128),
-            SET_AUTO_STAKE,
+            mintingContractInfo.clone(),
+            "reward_from def".to_string(),
+            Uint128::from(1000000u128),
         );
 
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list);
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-                for stake in all_stakes {
-                    if stake.staker_address == "staker001".to_string() {
-                        assert_eq!(stake.staked_amount, Uint128::from(86u128));
-                    } else {
-                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+        let queryReward = query_reward_amount(&mut deps.storage);
+        println!("reward amount is {:?}", queryReward);
+        let club_name1 = "CLUB001".to_string();
+        let res1 = calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            user_address_list.clone(),
+            club_name1,
+            true,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            res1,
+            (ContractError::Std(StdError::GenericErr {
+                msg: String::from("Time for Reward not yet arrived")
+            }))
+        );
+        println!("");
+        println!("");
+        let club_name2 = "CLUB002".to_string();
+        let res2 = calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            user_address_list.clone(),
+            club_name2,
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert_eq!(
+            res2,
+            (ContractError::Std(StdError::GenericErr {
+                msg: String::from("Time for Reward not yet arrived")
+            }))
+        );
+        println!("");
+        println!("");
+        let club_name3 = "CLUB003".to_string();
+        let res3 = calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            user_address_list.clone(),
+            club_name3,
+            false,
+            true,
+        )
+        .unwrap_err();
+        assert_eq!(
+            res3,
+            (ContractError::Std(StdError::GenericErr {
+                msg: String::from("Time for Reward not yet arrived")
+            }))
+        );
     }
 
     #[test]
-    fn test_immediate_partial_withdrawals_from_club() {
+    fn test_club_owner_follows_nft_holder() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -3440,7 +7620,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -3448,93 +7627,60 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+        set_club_nft_contract(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
-            "owner001".to_string(),
-            Some(String::default()),
-            "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
-        );
+            adminInfo.clone(),
+            "club_nft11111".to_string(),
+        )
+        .unwrap();
 
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
-        stake_on_a_club(
+        // the club token is minted to the first owner
+        let rsp = assign_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
+            adminInfo.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(99u128),
             SET_AUTO_STAKE,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
-            mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(11u128),
-            IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
-            mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(12u128),
-            IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
-            mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(13u128),
-            IMMEDIATE_WITHDRAWAL,
-        );
-
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-                for stake in all_stakes {
-                    if stake.staker_address == "staker001".to_string() {
-                        assert_eq!(stake.staked_amount, Uint128::from(63u128));
-                    } else {
-                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let queryBonds = query_all_bonds(&mut deps.storage, user_address_list.clone());
-        match queryBonds {
-            Ok(all_bonds) => {
-                assert_eq!(all_bonds.len(), 0);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+        )
+        .unwrap();
+        assert_eq!(rsp.messages.len(), 1);
+
+        // the club token has been traded to owner002 on a marketplace
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(
+                to_binary(&OwnerOfResponse {
+                    owner: "owner002".to_string(),
+                    approvals: vec![],
+                })
+                .unwrap(),
+            ))
+        });
+
+        sync_club_owner(deps.as_mut(), mock_env(), "CLUB001".to_string()).unwrap();
+
+        let cod = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(cod.owner_address, "owner002".to_string());
+        assert_eq!(cod.owner_released, false);
+
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["owner002".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes.len(), 1);
+        assert_eq!(stakes[0].staked_amount, Uint128::zero());
     }
 
     #[test]
-    fn test_immediate_complete_withdrawals_from_club() {
+    fn test_fee_exempt_address() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -3555,7 +7701,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -3563,95 +7708,208 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
 
-        let owner1Info = mock_info("owner001", &[coin(1000, "stake")]);
-        buy_a_club(
+        assign_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1Info.clone(),
+            adminInfo.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
-        );
+        )
+        .unwrap();
 
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
-        stake_on_a_club(
+        let stakerInfo = mock_info("staker001", &[]);
+        let err = stake_on_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(99u128),
+            Uint128::from(100000u128),
             SET_AUTO_STAKE,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientFees {
+                required: Uint128::from(1800u128),
+                received: Uint128::zero(),
+            }
         );
-        withdraw_stake_from_a_club(
+
+        // only the admin manages the exemption list
+        let err = add_fee_exempt_address(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(11u128),
-            IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        add_fee_exempt_address(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
+            adminInfo.clone(),
             "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(12u128),
-            IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        assert_eq!(
+            query_fee_exempt_addresses(&deps.storage, None, None).unwrap(),
+            vec!["staker001".to_string()]
         );
-        withdraw_stake_from_a_club(
+        add_fee_exempt_address(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(13u128),
-            IMMEDIATE_WITHDRAWAL,
+            adminInfo.clone(),
+            "staker002".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            query_fee_exempt_addresses(&deps.storage, None, Some(1)).unwrap(),
+            vec!["staker001".to_string()]
         );
-        withdraw_stake_from_a_club(
+        assert_eq!(
+            query_fee_exempt_addresses(&deps.storage, Some("staker001".to_string()), None).unwrap(),
+            vec!["staker002".to_string()]
+        );
+        remove_fee_exempt_address(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "staker002".to_string(),
+        )
+        .unwrap();
+
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(63u128),
-            IMMEDIATE_WITHDRAWAL,
+            Uint128::from(100000u128),
+            SET_AUTO_STAKE,
+            None,
+        )
+        .unwrap();
+
+        remove_fee_exempt_address(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "staker001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(
+            query_fee_exempt_addresses(&deps.storage, None, None)
+                .unwrap()
+                .len(),
+            0
         );
+    }
 
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+    #[test]
+    fn test_estimate_fees() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
 
-        let queryBonds = query_all_bonds(&mut deps.storage, user_address_list.clone());
-        match queryBonds {
-            Ok(all_bonds) => {
-                assert_eq!(all_bonds.len(), 0);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+
+        let estimate = query_estimate_fees(
+            deps.as_ref(),
+            FeeAction::StakeOnAClub,
+            Uint128::from(100000u128),
+            None,
+        )
+        .unwrap();
+        assert_eq!(estimate.usdc_equivalent, Uint128::from(100000u128));
+        assert_eq!(estimate.platform_fees_usdc, Uint128::from(1000u128));
+        assert_eq!(estimate.transaction_fees_usdc, Uint128::from(300u128));
+        assert_eq!(estimate.control_fees_usdc, Uint128::from(500u128));
+        assert_eq!(estimate.total_fees_usdc, Uint128::from(1800u128));
+        assert_eq!(estimate.total_fees_fury, Uint128::from(1800u128));
+
+        // rounding happens per fee so the total is the sum of the rounded parts
+        let estimate = query_estimate_fees(
+            deps.as_ref(),
+            FeeAction::StakeOnAClub,
+            Uint128::from(999u128),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            estimate.total_fees_usdc,
+            estimate.platform_fees_usdc
+                + estimate.transaction_fees_usdc
+                + estimate.control_fees_usdc
+        );
+        assert_eq!(estimate.total_fees_usdc, Uint128::from(15u128));
+        assert_eq!(estimate.total_fees_fury, Uint128::from(15u128));
+
+        // buying a club is always charged on the club price
+        let estimate =
+            query_estimate_fees(deps.as_ref(), FeeAction::BuyAClub, Uint128::zero(), None).unwrap();
+        assert_eq!(estimate.fury_amount, Uint128::from(1000000u128));
+        assert_eq!(estimate.control_fees_usdc, Uint128::zero());
+        assert_eq!(estimate.total_fees_usdc, Uint128::from(13000u128));
+
+        // the binary query gives the same total
+        let fees = query_platform_fees(
+            deps.as_ref(),
+            to_binary(&ExecuteMsg::StakeOnAClub {
+                staker: "staker001".to_string(),
+                club_name: "CLUB001".to_string(),
+                amount: Uint128::from(100000u128),
+                auto_stake: SET_AUTO_STAKE,
+                lock_tier: None,
+            })
+            .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(fees, Uint128::from(1800u128));
     }
 
     #[test]
-    fn test_non_immediate_complete_withdrawals_from_club() {
+    fn test_lock_tier_reward_weight_and_early_unlock_penalty() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -3671,161 +7929,151 @@ mod tests {
             max_bonding_limit_per_user: 10u64,
             usdc_ibc_symbol: "uusd".to_string(),
         };
-        let admin_info = mock_info("admin11111", &[]);
-        let minting_contract_info = mock_info("minting_admin11111", &[]);
+        let adminInfo = mock_info("admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
-            admin_info.clone(),
+            adminInfo.clone(),
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+        assign_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
+            adminInfo.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
-        );
+            false,
+        )
+        .unwrap();
 
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
+        let staker1Info = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(99u128),
-            SET_AUTO_STAKE,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
-            mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(11u128),
-            NO_IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
-            mock_env(),
-            stakerInfo.clone(),
+            staker1Info.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(12u128),
-            NO_IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
+            Uint128::from(100000u128),
+            false,
+            None,
+        )
+        .unwrap();
+        let staker2Info = mock_info("staker002", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
-            "staker001".to_string(),
+            staker2Info.clone(),
+            "staker002".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(13u128),
-            NO_IMMEDIATE_WITHDRAWAL,
-        );
-        withdraw_stake_from_a_club(
+            Uint128::from(100000u128),
+            false,
+            Some(LockTier::Days180),
+        )
+        .unwrap();
+
+        increase_reward_amount(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
+            adminInfo.clone(),
+            "reward_from abc".to_string(),
+            Uint128::from(1000000u128),
+        )
+        .unwrap();
+        let staker_list = vec![
             "staker001".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(63u128),
-            NO_IMMEDIATE_WITHDRAWAL,
-        );
-
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let queryBonds = query_all_bonds(&mut deps.storage, user_address_list.clone());
-        match queryBonds {
-            Ok(all_bonds) => {
-                assert_eq!(all_bonds.len(), 4);
-                for bond in all_bonds {
-                    if bond.bonded_amount != Uint128::from(11u128)
-                        && bond.bonded_amount != Uint128::from(12u128)
-                        && bond.bonded_amount != Uint128::from(13u128)
-                        && bond.bonded_amount != Uint128::from(63u128)
-                    {
-                        println!("bond is {:?} ", bond);
-                        assert_eq!(1, 2);
-                    }
-                }
+            "staker002".to_string(),
+            "owner001".to_string(),
+        ];
+        calculate_and_distribute_rewards(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            staker_list.clone(),
+            "CLUB001".to_string(),
+            true,
+            true,
+        )
+        .unwrap();
+
+        // 1.5x weight for the 180 day lock: 250000 weighted stake in total
+        let stakes =
+            query_club_staking_details(&deps.storage, "CLUB001".to_string(), staker_list).unwrap();
+        for stake in stakes {
+            if stake.staker_address == "staker001" {
+                assert_eq!(stake.reward_amount, Uint128::from(388000u128));
             }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
+            if stake.staker_address == "staker002" {
+                assert_eq!(stake.reward_amount, Uint128::from(582000u128));
+                assert_eq!(stake.lock_tier, LockTier::Days180);
             }
         }
-        let stakerInfo = mock_info("staker002", &[coin(10, "stake")]);
-        stake_on_a_club(
+        assert_eq!(query_reward_amount(&deps.storage).unwrap(), Uint128::zero());
+
+        // an active lock cannot be shortened
+        let err = stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
+            staker2Info.clone(),
             "staker002".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(99u128),
-            SET_AUTO_STAKE,
+            Uint128::from(100u128),
+            false,
+            Some(LockTier::Days30),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Cannot shorten an active stake lock"),
+            })
         );
+
+        // withdrawing before the lock ends costs 10% which goes to the reward pool
         withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            stakerInfo.clone(),
+            staker2Info.clone(),
             "staker002".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(11u128),
+            Uint128::from(10000u128),
             NO_IMMEDIATE_WITHDRAWAL,
-        );
-
-        let queryBonds = query_club_bonding_details_for_user(
-            &mut deps.storage,
+        )
+        .unwrap();
+        let bonds = query_club_bonding_details_for_user(
+            &deps.storage,
             "CLUB001".to_string(),
             "staker002".to_string(),
+        )
+        .unwrap();
+        assert_eq!(bonds[0].bonded_amount, Uint128::from(9000u128));
+        assert_eq!(
+            query_reward_amount(&deps.storage).unwrap(),
+            Uint128::from(1000u128)
         );
-        match queryBonds {
-            Ok(all_bonds) => {
-                assert_eq!(all_bonds.len(), 1);
-                for bond in all_bonds {
-                    if bond.bonded_amount != Uint128::from(11u128) {
-                        println!("bond is {:?} ", bond);
-                        assert_eq!(1, 2);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
     }
 
     #[test]
-    fn test_non_immediate_complete_withdrawals_from_club_with_scheduled_refunds() {
+    fn test_stake_and_buy_with_cw20_send() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
             admin_address: "admin11111".to_string(),
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
-            club_fee_collector_wallet: "feecollector11111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
             club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
             reward_periodicity: 24 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
@@ -3839,156 +8087,332 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
             instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+
+        // only the fury token may deliver a cw20 send
+        let tokenInfo = mock_info("minting_admin11111", &[]);
+        let buy_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "owner001".to_string(),
+            amount: Uint128::from(1020000u128),
+            msg: to_binary(&ReceivedMsg::BuyAClub {
+                club_name: "CLUB001".to_string(),
+            })
+            .unwrap(),
+        });
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("other_token11111", &[]),
+            buy_msg.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+
+        // price 1000000 plus 1.3% fees, the excess is refunded
+        let rsp = execute(deps.as_mut(), mock_env(), tokenInfo.clone(), buy_msg).unwrap();
+        assert_eq!(
+            rsp.messages[1],
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "minting_admin11111".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "owner001".to_string(),
+                    amount: Uint128::from(7000u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
         );
+        let ownership = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(ownership.owner_address, "owner001".to_string());
+
+        // a wallet stakes on behalf of a beneficiary, 1.8% fees are kept from the stake
+        let stake_msg = ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "wallet001".to_string(),
+            amount: Uint128::from(100000u128),
+            msg: to_binary(&ReceivedMsg::StakeOnAClub {
+                club_name: "CLUB001".to_string(),
+                auto_stake: SET_AUTO_STAKE,
+                beneficiary: Some("staker001".to_string()),
+            })
+            .unwrap(),
+        });
+        execute(deps.as_mut(), mock_env(), tokenInfo.clone(), stake_msg).unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string(), "wallet001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes.len(), 1);
+        assert_eq!(stakes[0].staker_address, "staker001".to_string());
+        assert_eq!(stakes[0].staked_amount, Uint128::from(98200u128));
+    }
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        buy_a_club(
+    #[test]
+    fn test_unbonding_queue_claim_and_early_exit() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
-        );
+        )
+        .unwrap();
 
-        let stakerInfo = mock_info("staker001", &[coin(10, "stake")]);
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(99u128),
+            Uint128::from(1000u128),
             SET_AUTO_STAKE,
-        );
-        withdraw_stake_from_a_club(
-            deps.as_mut(),
+            None,
+        )
+        .unwrap();
+        for amount in [100u128, 200u128, 300u128] {
+            withdraw_stake_from_a_club(
+                deps.as_mut(),
+                mock_env(),
+                stakerInfo.clone(),
+                "staker001".to_string(),
+                "CLUB001".to_string(),
+                Uint128::from(amount),
+                NO_IMMEDIATE_WITHDRAWAL,
+            )
+            .unwrap();
+        }
+
+        // the first two bonds have matured
+        let mut bonds = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
+            "staker001".to_string(),
+        )
+        .unwrap();
+        for bond in bonds.iter_mut() {
+            if bond.bonded_amount != Uint128::from(300u128) {
+                bond.bonding_start_timestamp = now.minus_seconds(24 * 60 * 60);
+            }
+        }
+        club_bonding_details()
+            .save(&mut deps.storage, ("CLUB001", "staker001"), &bonds)
+            .unwrap();
+
+        let claimable = query_claimable_unbonds(
+            &deps.storage,
             mock_env(),
-            stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(11u128),
-            NO_IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        assert_eq!(claimable.matured_amount, Uint128::from(300u128));
+        assert_eq!(claimable.unbonding_amount, Uint128::from(300u128));
+        assert_eq!(
+            claimable.next_maturity_timestamp,
+            Some(now.plus_seconds(5 * 60))
         );
-        withdraw_stake_from_a_club(
+        assert_eq!(claimable.early_exit_penalty, None);
+
+        // without early exit only matured bonds can be withdrawn immediately
+        let err = withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(12u128),
-            NO_IMMEDIATE_WITHDRAWAL,
+            Uint128::from(400u128),
+            IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Not Sufficient Matured Unstaked Bonds"),
+            })
         );
-        withdraw_stake_from_a_club(
+
+        let rsp = claim_unbonded_stake(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(13u128),
-            NO_IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.messages[0],
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "minting_admin11111".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "staker001".to_string(),
+                    amount: Uint128::from(300u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
         );
-        withdraw_stake_from_a_club(
+        let bonds = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
+            "staker001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(bonds.len(), 1);
+        assert_eq!(bonds[0].bonded_amount, Uint128::from(300u128));
+
+        // leaving early costs 10%, sent to the reward pool
+        set_early_exit_penalty(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            Some(EarlyExitPenalty {
+                penalty: Uint128::from(1000u128),
+                destination: PenaltyDestination::RewardPool,
+            }),
+        )
+        .unwrap();
+        let rsp = withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(63u128),
-            NO_IMMEDIATE_WITHDRAWAL,
-        );
-
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let now = mock_env().block.time; // today
-
-        let query_bonds = query_all_bonds(&mut deps.storage, user_address_list.clone());
-        let club_name = "CLUB001".to_string();
-        match query_bonds {
-            Ok(all_bonds) => {
-                let existing_bonds = all_bonds.clone();
-                let mut updated_bonds = Vec::new();
-                assert_eq!(existing_bonds.len(), 4);
-                for user_addr in user_address_list.clone() {
-                    for bond in existing_bonds.clone() {
-                        let mut updated_bond = bond.clone();
-                        if updated_bond.bonded_amount != Uint128::from(11u128)
-                            && updated_bond.bonded_amount != Uint128::from(12u128)
-                            && updated_bond.bonded_amount != Uint128::from(13u128)
-                            && updated_bond.bonded_amount != Uint128::from(63u128)
-                        {
-                            println!("updated_bond is {:?} ", updated_bond);
-                            assert_eq!(1, 2);
-                        }
-                        if updated_bond.bonded_amount == Uint128::from(63u128) {
-                            updated_bond.bonding_start_timestamp =
-                                now.minus_seconds(8 * 24 * 60 * 60);
-                        }
-                        updated_bonds.push(updated_bond);
-                    }
-                    CLUB_BONDING_DETAILS.save(
-                        &mut deps.storage,
-                        (&club_name.clone(), &user_addr.clone()),
-                        &updated_bonds,
-                    );
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        /*
-                Commenting out as this is no longer used, 6 Apr 2022
-
-                periodically_refund_stakeouts(deps.as_mut(), mock_env(), adminInfo);
-
-                let queryBondsAfterPeriodicRefund = query_all_bonds(&mut deps.storage, user_address_list.clone());
-                match queryBondsAfterPeriodicRefund {
-                    Ok(all_bonds) => {
-                        assert_eq!(all_bonds.len(), 3);
-                        for bond in all_bonds {
-                            if bond.bonded_amount != Uint128::from(11u128)
-                                && bond.bonded_amount != Uint128::from(12u128)
-                                && bond.bonded_amount != Uint128::from(13u128)
-                            {
-                                println!("bond is {:?} ", bond);
-                                assert_eq!(1, 2);
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        println!("error parsing header: {:?}", e);
-                        assert_eq!(1, 2);
-                    }
-                }
-        */
+            Uint128::from(500u128),
+            IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.messages[0],
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "minting_admin11111".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "staker001".to_string(),
+                    amount: Uint128::from(450u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            query_reward_amount(&deps.storage).unwrap(),
+            Uint128::from(50u128)
+        );
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].staked_amount, Uint128::from(200u128));
+        // no bonds are left, so the entry has been removed
+        let err = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
+            "staker001".to_string(),
+        )
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("No bonding details found"));
+
+        // a locked stake only pays the early unlock penalty, not the early exit penalty too
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(800u128),
+            SET_AUTO_STAKE,
+            Some(LockTier::Days30),
+        )
+        .unwrap();
+        let rsp = withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
+            IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.messages[0],
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: "minting_admin11111".to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "staker001".to_string(),
+                    amount: Uint128::from(90u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            })
+        );
+        assert_eq!(
+            query_reward_amount(&deps.storage).unwrap(),
+            Uint128::from(60u128)
+        );
     }
 
     #[test]
-    fn test_non_immediate_partial_withdrawals_from_club() {
+    fn test_rebond() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -4009,7 +8433,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -4017,102 +8440,213 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
 
-        let owner1_info = mock_info("owner001", &[coin(0, "uusd")]);
-        let result = buy_a_club(
+        assign_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1_info.clone(),
+            adminInfo.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
-        );
-        println!("buy_a_club result = {:?}", result);
-        let stakerInfo = mock_info("staker001", &[coin(10, "uusd")]);
+        )
+        .unwrap();
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner002".to_string(),
+            Some(String::default()),
+            "CLUB002".to_string(),
+            SET_AUTO_STAKE,
+        )
+        .unwrap();
+
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(99u128),
+            Uint128::from(1000u128),
             SET_AUTO_STAKE,
-        );
+            None,
+        )
+        .unwrap();
         withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(11u128),
+            Uint128::from(100u128),
             NO_IMMEDIATE_WITHDRAWAL,
-        );
+        )
+        .unwrap();
         withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(12u128),
+            Uint128::from(200u128),
             NO_IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        let mut bonds = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
+            "staker001".to_string(),
+        )
+        .unwrap();
+        for bond in bonds.iter_mut() {
+            if bond.bonded_amount == Uint128::from(100u128) {
+                bond.bonding_start_timestamp = now.minus_seconds(60);
+            }
+        }
+        club_bonding_details()
+            .save(&mut deps.storage, ("CLUB001", "staker001"), &bonds)
+            .unwrap();
+
+        let err = rebond(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "CLUB001".to_string(),
+            Uint128::from(301u128),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Excess amount demanded for rebonding"),
+            })
         );
-        let result = withdraw_stake_from_a_club(
+
+        // the newest bond of 200 goes first, then 50 of the older one
+        rebond(
             deps.as_mut(),
             mock_env(),
             stakerInfo.clone(),
+            "CLUB001".to_string(),
+            Uint128::from(250u128),
+            None,
+        )
+        .unwrap();
+        let bonds = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
             "staker001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(bonds.len(), 1);
+        assert_eq!(bonds[0].bonded_amount, Uint128::from(50u128));
+        assert_eq!(bonds[0].bonding_start_timestamp, now.minus_seconds(60));
+        let stakes = query_club_staking_details(
+            &deps.storage,
             "CLUB001".to_string(),
-            Uint128::from(13u128),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].staked_amount, Uint128::from(950u128));
+        assert_eq!(stakes[0].auto_stake, SET_AUTO_STAKE);
+
+        // the rest is rebonded on another club
+        rebond(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "CLUB001".to_string(),
+            Uint128::from(50u128),
+            Some("CLUB002".to_string()),
+        )
+        .unwrap();
+        // no bonds are left, so the entry has been removed
+        let err = query_club_bonding_details_for_user(
+            &deps.storage,
+            "CLUB001".to_string(),
+            "staker001".to_string(),
+        )
+        .unwrap_err();
+        assert_eq!(err, StdError::generic_err("No bonding details found"));
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB002".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].staked_amount, Uint128::from(50u128));
+        let club2 = query_club_ownership_details(&deps.storage, "CLUB002".to_string()).unwrap();
+        assert_eq!(club2.total_staked_amount, Uint128::from(50u128));
+        let club1 = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club1.total_staked_amount, Uint128::from(950u128));
+
+        // rebonding into another club counts as a stake move for the cooldown
+        withdraw_stake_from_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
             NO_IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
+        let err = rebond(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
+            Some("CLUB002".to_string()),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Stake was moved too recently"),
+            })
         );
-        println!("result = {:?}", result);
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("owner001".to_string());
-        let query_stakes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match query_stakes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 2);
-                for stake in all_stakes {
-                    if stake.staker_address == "staker001".to_string() {
-                        assert_eq!(stake.staked_amount, Uint128::from(63u128));
-                    } else {
-                        assert_eq!(stake.staked_amount, Uint128::from(0u128));
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let queryBonds = query_all_bonds(&mut deps.storage, user_address_list.clone());
-        match queryBonds {
-            Ok(all_bonds) => {
-                assert_eq!(all_bonds.len(), 3);
-                for bond in all_bonds {
-                    if bond.bonded_amount != Uint128::from(11u128)
-                        && bond.bonded_amount != Uint128::from(12u128)
-                        && bond.bonded_amount != Uint128::from(13u128)
-                    {
-                        println!("bond is {:?} ", bond);
-                        assert_eq!(1, 2);
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+        let err = move_stake(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "CLUB001".to_string(),
+            "CLUB002".to_string(),
+            Uint128::from(100u128),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Stake was moved too recently"),
+            })
+        );
+        rebond(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
+            None,
+        )
+        .unwrap();
     }
 
     #[test]
-    fn test_distribute_rewards() {
+    fn test_move_stake_between_clubs() {
         let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
         let now = mock_env().block.time; // today
 
         let instantiate_msg = InstantiateMsg {
@@ -4120,9 +8654,8 @@ mod tests {
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
             club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
-            //club_reward_next_timestamp: now.minus_seconds(8 * 60 * 60),
-            club_reward_next_timestamp: now.minus_seconds(1),
-            reward_periodicity: 5 * 60 * 60u64,
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
             bonding_duration: 5 * 60u64,
             owner_release_locking_duration: 24 * 60 * 60u64,
@@ -4134,8 +8667,6 @@ mod tests {
             usdc_ibc_symbol: "uusd".to_string(),
         };
         let adminInfo = mock_info("admin11111", &[]);
-        let mintingContractInfo = mock_info("minting_admin11111", &[]);
-
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -4143,295 +8674,300 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
 
-        let owner1Info = mock_info("owner001", &[coin(1000, "stake")]);
-        buy_a_club(
+        assign_a_club(
             deps.as_mut(),
             mock_env(),
-            owner1Info.clone(),
+            adminInfo.clone(),
             "owner001".to_string(),
             Some(String::default()),
             "CLUB001".to_string(),
-            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
-        );
-        let owner2Info = mock_info("owner002", &[coin(1000, "stake")]);
-        buy_a_club(
+        )
+        .unwrap();
+        assign_a_club(
             deps.as_mut(),
             mock_env(),
-            owner2Info.clone(),
+            adminInfo.clone(),
             "owner002".to_string(),
             Some(String::default()),
             "CLUB002".to_string(),
-            Uint128::from(1000000u128),
-            SET_AUTO_STAKE,
-        );
-        let owner3Info = mock_info("owner003", &[coin(1000, "stake")]);
-        buy_a_club(
-            deps.as_mut(),
-            mock_env(),
-            owner3Info.clone(),
-            "owner003".to_string(),
-            Some(String::default()),
-            "CLUB003".to_string(),
-            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
-        );
+        )
+        .unwrap();
 
-        let staker1Info = mock_info("staker001", &[coin(10, "stake")]);
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
         stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            staker1Info.clone(),
+            stakerInfo.clone(),
             "staker001".to_string(),
             "CLUB001".to_string(),
-            Uint128::from(330000u128),
+            Uint128::from(1000u128),
             SET_AUTO_STAKE,
-        );
+            None,
+        )
+        .unwrap();
 
-        let staker2Info = mock_info("staker002", &[coin(10, "stake")]);
-        stake_on_a_club(
+        move_stake(
             deps.as_mut(),
             mock_env(),
-            staker2Info.clone(),
-            "staker002".to_string(),
+            stakerInfo.clone(),
             "CLUB001".to_string(),
-            Uint128::from(110000u128),
-            SET_AUTO_STAKE,
-        );
-
-        let staker3Info = mock_info("staker003", &[coin(10, "stake")]);
-        stake_on_a_club(
-            deps.as_mut(),
-            mock_env(),
-            staker3Info.clone(),
-            "staker003".to_string(),
             "CLUB002".to_string(),
-            Uint128::from(420000u128),
-            SET_AUTO_STAKE,
-        );
-
-        let staker4Info = mock_info("staker004", &[coin(10, "stake")]);
-        stake_on_a_club(
-            deps.as_mut(),
-            mock_env(),
-            staker4Info.clone(),
-            "staker004".to_string(),
+            Uint128::from(400u128),
+        )
+        .unwrap();
+        let club1 = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club1.total_staked_amount, Uint128::from(600u128));
+        let club2 = query_club_ownership_details(&deps.storage, "CLUB002".to_string()).unwrap();
+        assert_eq!(club2.total_staked_amount, Uint128::from(400u128));
+        let stakes = query_club_staking_details(
+            &deps.storage,
             "CLUB002".to_string(),
-            Uint128::from(100000u128),
-            SET_AUTO_STAKE,
-        );
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
+        assert_eq!(stakes[0].staked_amount, Uint128::from(400u128));
+        assert_eq!(stakes[0].auto_stake, SET_AUTO_STAKE);
 
-        let staker5Info = mock_info("staker005", &[coin(10, "stake")]);
-        stake_on_a_club(
+        // only one move per reward period
+        let err = move_stake(
             deps.as_mut(),
             mock_env(),
-            staker5Info.clone(),
-            "staker005".to_string(),
-            "CLUB003".to_string(),
-            Uint128::from(820000u128),
-            SET_AUTO_STAKE,
+            stakerInfo.clone(),
+            "CLUB002".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Stake was moved too recently"),
+            })
         );
 
-        let staker6Info = mock_info("staker006", &[coin(10, "stake")]);
-        stake_on_a_club(
+        let mut env = mock_env();
+        env.block.time = now.plus_seconds(24 * 60 * 60);
+        let err = move_stake(
             deps.as_mut(),
-            mock_env(),
-            staker6Info.clone(),
-            "staker006".to_string(),
-            "CLUB003".to_string(),
-            Uint128::from(50000u128),
-            SET_AUTO_STAKE,
+            env.clone(),
+            stakerInfo.clone(),
+            "CLUB002".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(401u128),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Excess amount demanded for moving"),
+            })
         );
+        move_stake(
+            deps.as_mut(),
+            env.clone(),
+            stakerInfo.clone(),
+            "CLUB002".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100u128),
+        )
+        .unwrap();
+        let club1 = query_club_ownership_details(&deps.storage, "CLUB001".to_string()).unwrap();
+        assert_eq!(club1.total_staked_amount, Uint128::from(700u128));
+        let club2 = query_club_ownership_details(&deps.storage, "CLUB002".to_string()).unwrap();
+        assert_eq!(club2.total_staked_amount, Uint128::from(300u128));
+    }
 
-        let mut user_address_list = Vec::new();
-        user_address_list.push("staker001".to_string());
-        user_address_list.push("staker002".to_string());
-        user_address_list.push("staker003".to_string());
-        user_address_list.push("staker004".to_string());
-        user_address_list.push("staker005".to_string());
-        user_address_list.push("staker006".to_string());
-        user_address_list.push("owner001".to_string());
-        user_address_list.push("owner002".to_string());
-        user_address_list.push("owner003".to_string());
-        let queryRes0 = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match queryRes0 {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 9);
-                println!("all stakes : {:?}", all_stakes);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
+    #[test]
+    fn test_paginated_indexed_queries() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
 
-        increase_reward_amount(
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "reward_from abc".to_string(),
-            Uint128::from(1000000u128),
-        );
-        println!("stakes before distribution");
-        let queryRes00 = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match queryRes00 {
-            Ok(all_stakes) => {
-                println!("all stakes : {:?}", all_stakes);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        let mut queryReward = query_reward_amount(&mut deps.storage);
-        println!("reward amount before distribution: {:?}", queryReward);
-        let club_name1 = "CLUB001".to_string();
-        calculate_and_distribute_rewards(
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            user_address_list.clone(),
-            club_name1,
-            true,
-            false,
-        );
-        println!("stakes after first distribution");
-        let queryRes01 = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match queryRes01 {
-            Ok(all_stakes) => {
-                println!("all stakes : {:?}", all_stakes);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+
+        for (owner, club) in [
+            ("owner001", "CLUB001"),
+            ("owner002", "CLUB002"),
+            ("owner003", "CLUB003"),
+        ] {
+            assign_a_club(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                owner.to_string(),
+                Some(String::default()),
+                club.to_string(),
+                SET_AUTO_STAKE,
+            )
+            .unwrap();
         }
 
-        queryReward = query_reward_amount(&mut deps.storage);
-        println!("reward amount after first distribution: {:?}", queryReward);
-        let club_name2 = "CLUB002".to_string();
-        calculate_and_distribute_rewards(
+        let staker1Info = mock_info("staker001", &[coin(100000, "uusd")]);
+        for club in ["CLUB001", "CLUB002", "CLUB003"] {
+            stake_on_a_club(
+                deps.as_mut(),
+                mock_env(),
+                staker1Info.clone(),
+                "staker001".to_string(),
+                club.to_string(),
+                Uint128::from(1000u128),
+                SET_AUTO_STAKE,
+                None,
+            )
+            .unwrap();
+        }
+        let staker2Info = mock_info("staker002", &[coin(100000, "uusd")]);
+        stake_on_a_club(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
-            user_address_list.clone(),
-            club_name2,
-            false,
-            false,
-        );
-        println!("stakes after second distribution");
-        let queryRes01 = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match queryRes01 {
-            Ok(all_stakes) => {
-                println!("all stakes : {:?}", all_stakes);
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        queryReward = query_reward_amount(&mut deps.storage);
-        println!("reward amount after second distribution: {:?}", queryReward);
-        let club_name3 = "CLUB003".to_string();
-        calculate_and_distribute_rewards(
+            staker2Info.clone(),
+            "staker002".to_string(),
+            "CLUB002".to_string(),
+            Uint128::from(1000u128),
+            SET_AUTO_STAKE,
+            None,
+        )
+        .unwrap();
+        withdraw_stake_from_a_club(
             deps.as_mut(),
             mock_env(),
-            adminInfo.clone(),
-            user_address_list.clone(),
-            club_name3,
-            false,
-            true,
-        );
-
-        queryReward = query_reward_amount(&mut deps.storage);
-        println!("reward amount after third distribution: {:?}", queryReward);
-        println!("stakes after third distribution");
-        let queryRes = query_all_stakes(&mut deps.storage, user_address_list.clone());
-        match queryRes {
-            Ok(all_stakes) => {
-                assert_eq!(all_stakes.len(), 9);
-                println!("all stakes : {:?}", all_stakes);
-                for stake in all_stakes {
-                    let staker_address = stake.staker_address;
-                    let staked_amount = stake.staked_amount;
-                    println!(
-                        "staker : {:?} staked_amount : {:?}",
-                        staker_address.clone(),
-                        staked_amount
-                    );
-                    if staker_address == "staker001" {
-                        assert_eq!(staked_amount, Uint128::from(470655u128));
-                    }
-                    if staker_address == "staker002" {
-                        assert_eq!(staked_amount, Uint128::from(156885u128));
-                    }
-                    if staker_address == "staker003" {
-                        assert_eq!(staked_amount, Uint128::from(599016u128));
-                    }
-                    if staker_address == "staker004" {
-                        assert_eq!(staked_amount, Uint128::from(142622u128));
-                    }
-                    if staker_address == "staker005" {
-                        assert_eq!(staked_amount, Uint128::from(1348588u128));
-                    }
-                    if staker_address == "staker006" {
-                        assert_eq!(staked_amount, Uint128::from(82230u128));
-                    }
-                    if staker_address == "owner001" {
-                        assert_eq!(staked_amount, Uint128::from(10000u128));
-                    }
-                    if staker_address == "owner002" {
-                        assert_eq!(staked_amount, Uint128::from(10000u128));
-                    }
-                    if staker_address == "owner003" {
-                        assert_eq!(staked_amount, Uint128::from(10000u128));
-                    }
-                }
-            }
-            Err(e) => {
-                println!("error parsing header: {:?}", e);
-                assert_eq!(1, 2);
-            }
-        }
-
-        // test another attempt to calculate and distribute at the same time
+            staker1Info.clone(),
+            "staker001".to_string(),
+            "CLUB003".to_string(),
+            Uint128::from(10u128),
+            NO_IMMEDIATE_WITHDRAWAL,
+        )
+        .unwrap();
 
-        increase_reward_amount(
+        // stakes of a user come from the staker index, a page at a time
+        let page1 =
+            query_all_stakes_for_user(&deps.storage, "staker001".to_string(), None, Some(2))
+                .unwrap();
+        assert_eq!(page1.len(), 2);
+        assert_eq!(page1[0].club_name, "CLUB001".to_string());
+        assert_eq!(page1[1].club_name, "CLUB002".to_string());
+        let page2 = query_all_stakes_for_user(
+            &deps.storage,
+            "staker001".to_string(),
+            Some("CLUB002".to_string()),
+            Some(2),
+        )
+        .unwrap();
+        assert_eq!(page2.len(), 1);
+        assert_eq!(page2[0].club_name, "CLUB003".to_string());
+
+        // all stakes are ordered by club and staker
+        let page1 = query_all_stakes(&deps.storage, None, Some(3)).unwrap();
+        assert_eq!(page1.len(), 3);
+        assert_eq!(page1[2].club_name, "CLUB002".to_string());
+        assert_eq!(page1[2].staker_address, "owner002".to_string());
+        let page2 = query_all_stakes(
+            &deps.storage,
+            Some(("CLUB002".to_string(), "owner002".to_string())),
+            None,
+        )
+        .unwrap();
+        assert_eq!(page2.len(), 4);
+        assert_eq!(page2[0].staker_address, "staker001".to_string());
+        assert_eq!(page2[1].staker_address, "staker002".to_string());
+
+        let bonds =
+            query_all_bonds_for_user(&deps.storage, "staker001".to_string(), None, None).unwrap();
+        assert_eq!(bonds.len(), 1);
+        assert_eq!(bonds[0].club_name, "CLUB003".to_string());
+        assert_eq!(query_all_bonds(&deps.storage, None, None).unwrap().len(), 1);
+
+        // clubs of an owner come from the owner index
+        let owned = query_club_ownership_details_for_owner(
+            &deps.storage,
+            "owner002".to_string(),
+            None,
+            None,
+        )
+        .unwrap();
+        assert_eq!(owned.len(), 1);
+        assert_eq!(owned[0].club_name, "CLUB002".to_string());
+        let clubs =
+            query_all_club_ownership_details(&deps.storage, Some("CLUB001".to_string()), Some(1))
+                .unwrap();
+        assert_eq!(clubs.len(), 1);
+        assert_eq!(clubs[0].club_name, "CLUB002".to_string());
+
+        // an owner cannot be assigned a second club
+        let err = assign_a_club(
             deps.as_mut(),
             mock_env(),
-            mintingContractInfo.clone(),
-            "reward_from abc".to_string(),
-            Uint128::from(1000000u128),
+            adminInfo.clone(),
+            "owner002".to_string(),
+            Some(String::default()),
+            "CLUB003".to_string(),
+            SET_AUTO_STAKE,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("buyer already owns this club"),
+            })
         );
+    }
+
+    #[test]
+    fn test_club_registry() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
 
-        /*
-                let club_name2 = "CLUB001".to_string();
-                let err = execute(
-                    deps.as_mut(),
-                    mock_env(),
-                    adminInfo.clone(),
-                    ExecuteMsg::CalculateAndDistributeRewards {},
-                )
-                .unwrap_err();
-
-                assert_eq!(
-                    err,
-                    (ContractError::Std(StdError::GenericErr {
-                        msg: String::from("Time for Reward not yet arrived")
-                    }))
-                );
-
-        // test by preponing club_reward_next_timestamp
         let instantiate_msg = InstantiateMsg {
             admin_address: "admin11111".to_string(),
             fury_token_address: "minting_admin11111".to_string(),
             pool_address: "pool_address1111".to_string(),
             club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
             club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
-            reward_periodicity: 5 * 60 * 60u64,
+            reward_periodicity: 24 * 60 * 60u64,
             club_price: Uint128::from(1000000u128),
             bonding_duration: 5 * 60u64,
             owner_release_locking_duration: 24 * 60 * 60u64,
@@ -4442,6 +8978,7 @@ mod tests {
             max_bonding_limit_per_user: 10u64,
             usdc_ibc_symbol: "uusd".to_string(),
         };
+        let adminInfo = mock_info("admin11111", &[]);
         instantiate(
             deps.as_mut(),
             mock_env(),
@@ -4449,100 +8986,412 @@ mod tests {
             instantiate_msg,
         )
         .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
 
-        stake_on_a_club(
+        // unregistered clubs cannot be bought or staked on
+        let owner1Info = mock_info("owner001", &[coin(100000, "uusd")]);
+        let err = buy_a_club(
             deps.as_mut(),
             mock_env(),
-            staker4Info.clone(),
-            "staker004".to_string(),
-            "CLUB002".to_string(),
-            Uint128::from(100000u128),
+            owner1Info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB999".to_string(),
+            Uint128::from(1000000u128),
             SET_AUTO_STAKE,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club is not registered"),
+            })
         );
-        stake_on_a_club(
+
+        let err = register_club(
             deps.as_mut(),
             mock_env(),
-            staker4Info.clone(),
-            "staker004".to_string(),
-            "CLUB001".to_string(),
-            Uint128::from(500000u128),
-            SET_AUTO_STAKE,
-        );
-        stake_on_a_club(
+            owner1Info.clone(),
+            ClubMetadata {
+                club_name: "CLUB004".to_string(),
+                display_name: "Club Four".to_string(),
+                sport: "cricket".to_string(),
+                logo_uri: Some("ipfs://club004".to_string()),
+                max_total_stake: Some(Uint128::from(500u128)),
+                price_override: Some(Uint128::from(2000000u128)),
+            },
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::Unauthorized {});
+        register_club(
             deps.as_mut(),
             mock_env(),
-            staker4Info.clone(),
-            "staker004".to_string(),
-            "CLUB003".to_string(),
-            Uint128::from(126718u128),
-            SET_AUTO_STAKE,
+            adminInfo.clone(),
+            ClubMetadata {
+                club_name: "CLUB004".to_string(),
+                display_name: "Club Four".to_string(),
+                sport: "cricket".to_string(),
+                logo_uri: Some("ipfs://club004".to_string()),
+                max_total_stake: Some(Uint128::from(500u128)),
+                price_override: Some(Uint128::from(2000000u128)),
+            },
+        )
+        .unwrap();
+        let estimate = query_estimate_fees(
+            deps.as_ref(),
+            FeeAction::BuyAClub,
+            Uint128::zero(),
+            Some("CLUB004".to_string()),
+        )
+        .unwrap();
+        assert_eq!(estimate.fury_amount, Uint128::from(2000000u128));
+
+        // stakes can not be assigned to an unregistered club
+        let err = assign_stakes_to_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            vec![],
+            "CLUB999".to_string(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club is not registered"),
+            })
         );
-        */
 
-        increase_reward_amount(
+        // the club price is overridden for this club
+        let err = buy_a_club(
             deps.as_mut(),
             mock_env(),
-            mintingContractInfo.clone(),
-            "reward_from def".to_string(),
+            owner1Info.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB004".to_string(),
             Uint128::from(1000000u128),
+            SET_AUTO_STAKE,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club price is not matching"),
+            })
         );
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("owner001", &[coin(26000, "uusd")]),
+            ExecuteMsg::BuyAClub {
+                buyer: "owner001".to_string(),
+                seller: Some(String::default()),
+                club_name: "CLUB004".to_string(),
+                auto_stake: SET_AUTO_STAKE,
+            },
+        )
+        .unwrap();
 
-        let queryReward = query_reward_amount(&mut deps.storage);
-        println!("reward amount is {:?}", queryReward);
-        let club_name1 = "CLUB001".to_string();
-        let res1 = calculate_and_distribute_rewards(
+        // the total stake in the club is capped
+        let stakerInfo = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB004".to_string(),
+            Uint128::from(400u128),
+            SET_AUTO_STAKE,
+            None,
+        )
+        .unwrap();
+        let err = stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            stakerInfo.clone(),
+            "staker001".to_string(),
+            "CLUB004".to_string(),
+            Uint128::from(101u128),
+            SET_AUTO_STAKE,
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club stake limit reached"),
+            })
+        );
+        let err = assign_stakes_to_a_club(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            user_address_list.clone(),
-            club_name1,
-            true,
-            false,
+            vec![ClubStakingDetails {
+                staker_address: "staker002".to_string(),
+                staking_start_timestamp: mock_env().block.time,
+                staked_amount: Uint128::from(101u128),
+                staking_duration: CLUB_STAKING_DURATION,
+                club_name: "CLUB004".to_string(),
+                reward_amount: Uint128::zero(),
+                auto_stake: SET_AUTO_STAKE,
+                lock_tier: LockTier::NoLock,
+                lock_end_timestamp: mock_env().block.time,
+            }],
+            "CLUB004".to_string(),
         )
         .unwrap_err();
         assert_eq!(
-            res1,
-            (ContractError::Std(StdError::GenericErr {
-                msg: String::from("Time for Reward not yet arrived")
-            }))
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Club stake limit reached"),
+            })
         );
-        println!("");
-        println!("");
-        let club_name2 = "CLUB002".to_string();
-        let res2 = calculate_and_distribute_rewards(
+
+        let clubs = query_clubs(&deps.storage, Some("CLUB002".to_string()), None).unwrap();
+        assert_eq!(clubs.len(), 2);
+        assert_eq!(clubs[1].display_name, "Club Four".to_string());
+        assert_eq!(clubs[1].sport, "cricket".to_string());
+    }
+
+    #[test]
+    fn test_club_nft_owner_query_errors() {
+        let mut deps = mock_dependencies();
+        let nft_contract = Addr::unchecked("club_nft11111");
+        CLUB_NFT_CONTRACT
+            .save(&mut deps.storage, &nft_contract)
+            .unwrap();
+
+        // tokens not minted by this contract are not looked up
+        deps.querier.update_wasm(|_| {
+            SystemResult::Err(SystemError::NoSuchContract {
+                addr: "club_nft11111".to_string(),
+            })
+        });
+        let owner = query_club_nft_owner(deps.as_ref(), "CLUB001".to_string()).unwrap();
+        assert_eq!(owner, None);
+
+        // any failure for a minted token is passed on
+        CLUB_NFT_MINTED
+            .save(&mut deps.storage, (&nft_contract, "CLUB001"), &true)
+            .unwrap();
+        query_club_nft_owner(deps.as_ref(), "CLUB001".to_string()).unwrap_err();
+        let err = club_nft_message(
+            deps.as_mut(),
+            &mock_env(),
+            "CLUB001".to_string(),
+            "owner001".to_string(),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("No such contract"));
+
+        // the holder has to approve this contract before the token can be transferred
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(Binary::from(
+                br#"{"owner":"seller001","approvals":[]}"#.to_vec(),
+            )))
+        });
+        let err = club_nft_message(
+            deps.as_mut(),
+            &mock_env(),
+            "CLUB001".to_string(),
+            "owner001".to_string(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            StdError::GenericErr {
+                msg: String::from(
+                    "Club token holder has not approved this contract to transfer the token"
+                ),
+            }
+        );
+        deps.querier.update_wasm(|_| {
+            SystemResult::Ok(ContractResult::Ok(Binary::from(
+                br#"{"owner":"seller001","approvals":[{"spender":"cosmos2contract","expires":{"never":{}}}]}"#
+                    .to_vec(),
+            )))
+        });
+        let msg = club_nft_message(
+            deps.as_mut(),
+            &mock_env(),
+            "CLUB001".to_string(),
+            "owner001".to_string(),
+        )
+        .unwrap();
+        assert!(msg.is_some());
+    }
+
+    #[test]
+    fn test_expired_lock_bonus_not_weighted() {
+        let mut deps = mock_dependencies();
+        mock_price_oracle(&mut deps);
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            user_address_list.clone(),
-            club_name2,
+            instantiate_msg,
+        )
+        .unwrap();
+        register_test_clubs(&mut deps);
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "fee_wallet11111".to_string(),
+        )
+        .unwrap();
+
+        assign_a_club(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "owner001".to_string(),
+            Some(String::default()),
+            "CLUB001".to_string(),
+            false,
+        )
+        .unwrap();
+
+        let staker1Info = mock_info("staker001", &[coin(100000, "uusd")]);
+        stake_on_a_club(
+            deps.as_mut(),
+            mock_env(),
+            staker1Info.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100000u128),
             false,
+            Some(LockTier::Days30),
+        )
+        .unwrap();
+
+        // 1.1x weight while the 30 day lock is active
+        get_winning_clubs_details(&mut deps.storage, now).unwrap();
+        let weighted_stake = CLUB_WEIGHTED_STAKE_SNAPSHOT
+            .load(&deps.storage, "CLUB001".to_string())
+            .unwrap();
+        assert_eq!(weighted_stake, Uint128::from(110000u128));
+
+        // no bonus once the lock has ended
+        let lock_end = now.plus_seconds(LOCK_TIER_30_DAYS_DURATION);
+        get_winning_clubs_details(&mut deps.storage, lock_end).unwrap();
+        let weighted_stake = CLUB_WEIGHTED_STAKE_SNAPSHOT
+            .load(&deps.storage, "CLUB001".to_string())
+            .unwrap();
+        assert_eq!(weighted_stake, Uint128::from(100000u128));
+
+        // a top up just before the lock ends extends the lock by a full tier duration
+        let mut top_up_env = mock_env();
+        top_up_env.block.time = lock_end.minus_seconds(60);
+        stake_on_a_club(
+            deps.as_mut(),
+            top_up_env.clone(),
+            staker1Info.clone(),
+            "staker001".to_string(),
+            "CLUB001".to_string(),
+            Uint128::from(100000u128),
             false,
+            None,
         )
-        .unwrap_err();
+        .unwrap();
+        let stakes = query_club_staking_details(
+            &deps.storage,
+            "CLUB001".to_string(),
+            vec!["staker001".to_string()],
+        )
+        .unwrap();
         assert_eq!(
-            res2,
-            (ContractError::Std(StdError::GenericErr {
-                msg: String::from("Time for Reward not yet arrived")
-            }))
+            stakes[0].lock_end_timestamp,
+            top_up_env
+                .block
+                .time
+                .plus_seconds(LOCK_TIER_30_DAYS_DURATION)
         );
-        println!("");
-        println!("");
-        let club_name3 = "CLUB003".to_string();
-        let res3 = calculate_and_distribute_rewards(
+        get_winning_clubs_details(&mut deps.storage, lock_end).unwrap();
+        let weighted_stake = CLUB_WEIGHTED_STAKE_SNAPSHOT
+            .load(&deps.storage, "CLUB001".to_string())
+            .unwrap();
+        assert_eq!(weighted_stake, Uint128::from(220000u128));
+    }
+
+    #[test]
+    fn test_migrate_moves_fury_reward_into_rewards() {
+        let mut deps = mock_dependencies();
+        let now = mock_env().block.time; // today
+
+        let instantiate_msg = InstantiateMsg {
+            admin_address: "admin11111".to_string(),
+            fury_token_address: "minting_admin11111".to_string(),
+            pool_address: "pool_address1111".to_string(),
+            club_fee_collector_wallet: "club_fee_collector_wallet11111".to_string(),
+            club_reward_next_timestamp: now.minus_seconds(1 * 60 * 60),
+            reward_periodicity: 24 * 60 * 60u64,
+            club_price: Uint128::from(1000000u128),
+            bonding_duration: 5 * 60u64,
+            owner_release_locking_duration: 24 * 60 * 60u64,
+            platform_fees_collector_wallet: "platform_fee_collector_wallet_1111".to_string(),
+            platform_fees: Uint128::from(100u128),
+            transaction_fees: Uint128::from(30u128),
+            control_fees: Uint128::from(50u128),
+            max_bonding_limit_per_user: 10u64,
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            user_address_list.clone(),
-            club_name3,
-            false,
-            true,
+            instantiate_msg,
         )
-        .unwrap_err();
+        .unwrap();
+        LEGACY_REWARD
+            .save(&mut deps.storage, &Uint128::from(5000u128))
+            .unwrap();
+        LEGACY_REWARD_GIVEN_IN_CURRENT_TIMESTAMP
+            .save(&mut deps.storage, &Uint128::from(1000u128))
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
         assert_eq!(
-            res3,
-            (ContractError::Std(StdError::GenericErr {
-                msg: String::from("Time for Reward not yet arrived")
-            }))
+            query_reward_amount(&deps.storage).unwrap(),
+            Uint128::from(5000u128)
+        );
+        assert_eq!(
+            REWARDS_GIVEN_IN_CURRENT_TIMESTAMP
+                .load(&deps.storage, "cw20:minting_admin11111".to_string())
+                .unwrap(),
+            Uint128::from(1000u128)
         );
+        assert!(query_partner_rewards(&deps.storage).unwrap().is_empty());
+        assert_eq!(LEGACY_REWARD.may_load(&deps.storage).unwrap(), None);
     }
 }
@@ -5,7 +5,7 @@ use serde::{Deserialize, Serialize};
 
 use cw20::Cw20ReceiveMsg;
 
-use crate::state::{ClubStakingDetails, Config};
+use crate::state::{ClubStakingDetails, Config, EarlyExitPenalty, EmissionSchedule, LockTier};
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
 pub struct InstantiateMsg {
@@ -56,12 +56,14 @@ pub enum ExecuteMsg {
         club_name: String,
         auto_stake: bool,
     },
-    /// to Stake Tokens on a Club by generic public
+    /// to Stake Tokens on a Club by generic public.
+    /// A lock tier locks the whole stake in the club for a higher reward weight
     StakeOnAClub {
         staker: String,
         club_name: String,
         amount: Uint128,
         auto_stake: bool,
+        lock_tier: Option<LockTier>,
     },
     /// to Stake Tokens on a Club on behalf of a Staker by Administrator
     AssignStakesToAClub {
@@ -82,7 +84,9 @@ pub enum ExecuteMsg {
     ClaimPreviousOwnerRewards {
         previous_owner: String,
     },
-    /// to Un-stake Tokens , in two steps - 1) to a Bonded Stake and then 2) to Claim it after maturity
+    /// to Un-stake Tokens , in two steps - 1) to a Bonded Stake and then 2) to Claim it after maturity.
+    /// An immediate withdrawal is paid from matured bonds first; the rest leaves the
+    /// unbonding queue (and then the stake) early, against the early exit penalty
     StakeWithdrawFromAClub {
         staker: String,
         club_name: String,
@@ -111,6 +115,70 @@ pub enum ExecuteMsg {
     ChangeFeeWallet {
         address: String,
     },
+    /// Administrator exempts an address from platform fees
+    AddFeeExemptAddress {
+        address: String,
+    },
+    /// Administrator removes an address from the fee exemption list
+    RemoveFeeExemptAddress {
+        address: String,
+    },
+    /// Administrator sets the cw721 contract which mints one token per club
+    SetClubNftContract {
+        address: String,
+    },
+    /// Update the club owner from the current holder of the club token
+    SyncClubOwner {
+        club_name: String,
+    },
+    /// Fury sent to the contract with a ReceivedMsg
+    Receive(Cw20ReceiveMsg),
+    /// to Claim all matured bonds of a Staker in a Club
+    ClaimUnbondedStake {
+        staker: String,
+        club_name: String,
+    },
+    /// to Move bonded stake back to staking, newest bond first, on the same club
+    /// or on to_club when given
+    Rebond {
+        club_name: String,
+        amount: Uint128,
+        to_club: Option<String>,
+    },
+    /// to Move stake from one club to another without unbonding.
+    /// Allowed once per reward period for each staker
+    MoveStake {
+        from_club: String,
+        to_club: String,
+        amount: Uint128,
+    },
+    /// Administrator registers a club, or updates the metadata of a registered club
+    RegisterClub {
+        club_name: String,
+        display_name: String,
+        sport: String,
+        logo_uri: Option<String>,
+        max_total_stake: Option<Uint128>,
+        price_override: Option<Uint128>,
+    },
+    /// Administrator adds the native tokens sent with the message to the partner rewards,
+    /// distributed with the next rewards
+    IncreasePartnerReward {},
+    /// to Turn compounding of staker rewards on or off without restaking
+    SetAutoStake {
+        club_name: String,
+        enabled: bool,
+    },
+    /// Club owner sets the share of the owner reward it keeps, the rest goes to the
+    /// club stakers. Specified in percentage multiplied by 100, i.e. 100% = 10000
+    SetClubCommission {
+        club_name: String,
+        commission_rate: Uint128,
+    },
+    /// Administrator sets the early exit penalty, None disables early exit
+    SetEarlyExitPenalty {
+        early_exit: Option<EarlyExitPenalty>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -129,34 +197,178 @@ pub enum QueryMsg {
     ClubPreviousOwnershipDetails {
         previous_owner: String,
     },
+    /// Paginated by club name
     ClubOwnershipDetailsForOwner {
         owner_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Paginated by club name
+    AllClubOwnershipDetails {
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
-    AllClubOwnershipDetails {},
-    AllPreviousClubOwnershipDetails {},
+    /// Paginated by previous owner address
+    AllPreviousClubOwnershipDetails {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Paginated by (club name, staker address)
     AllStakes {
-        user_address_list: Vec<String>,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
     },
+    /// Paginated by club name
     AllStakesForUser {
         user_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
+    /// Paginated by (club name, bonder address)
     AllBonds {
-        user_address_list: Vec<String>,
+        start_after: Option<(String, String)>,
+        limit: Option<u32>,
+    },
+    /// Paginated by club name
+    AllBondsForUser {
+        user_address: String,
+        start_after: Option<String>,
+        limit: Option<u32>,
     },
     ClubBondingDetailsForUser {
         club_name: String,
         user_address: String,
     },
     RewardAmount {},
+    /// Partner rewards waiting for distribution.
+    /// Return type: Vec<DenomAmount>
+    PartnerRewards {},
+    /// Partner rewards owed to a staker in a club, paid out by ClaimStakerRewards.
+    /// Return type: Vec<DenomAmount>
+    PartnerRewardsOwed {
+        staker: String,
+        club_name: String,
+    },
+    /// Owner commission in partner rewards owed to the owner of a club, paid out by ClaimOwnerRewards.
+    /// Return type: Vec<DenomAmount>
+    OwnerPartnerCommissionOwed {
+        owner: String,
+        club_name: String,
+    },
     QueryPlatformFees {
         msg: Binary,
     },
+    /// Returns the fee breakdown for an action on the given amount of FURY.
+    /// For BuyAClub an amount of zero stands for the configured club price.
+    /// Return type: FeeEstimateResponse.
+    /// club_name is used for the club price when buying a club with amount 0
+    EstimateFees {
+        action: FeeAction,
+        amount: Uint128,
+        club_name: Option<String>,
+    },
     QueryStakerRewards {
         staker: String,
         club_name: String,
     },
     Config {},
     GetFeeWallet {},
+    ClubNftContract {},
+    /// Fee exempt addresses, paginated by address.
+    /// Return type: Vec<String>
+    FeeExemptAddresses {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Registered clubs, paginated by club name.
+    /// Return type: Vec<ClubMetadata>
+    Clubs {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Return type: ScheduleResponse
+    Schedule {},
+    /// Stake of the address across all clubs at the start of the given height,
+    /// the current height if None. Return type: VotingPowerAtHeightResponse
+    VotingPowerAtHeight {
+        address: String,
+        height: Option<u64>,
+    },
+    /// Stake across all clubs at the start of the given height,
+    /// the current height if None. Return type: TotalPowerAtHeightResponse
+    TotalPowerAtHeight {
+        height: Option<u64>,
+    },
+    /// Return type: ClaimableUnbondsResponse
+    ClaimableUnbonds {
+        staker: String,
+        club_name: String,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClaimableUnbondsResponse {
+    /// Bonded amount which can be claimed now
+    pub matured_amount: Uint128,
+    /// Bonded amount still in the unbonding period
+    pub unbonding_amount: Uint128,
+    /// When the next bond in the unbonding period matures
+    pub next_maturity_timestamp: Option<Timestamp>,
+    /// Penalty for leaving the unbonding period early, None if early exit is not allowed
+    pub early_exit_penalty: Option<EarlyExitPenalty>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ScheduleResponse {
+    /// None if no emission schedule was ever funded
+    pub schedule: Option<EmissionSchedule>,
+    /// Emitted since the last reward round, added to the reward at the next round
+    pub pending_emissions: Uint128,
+    /// Funded amount not emitted yet, including pending_emissions
+    pub remaining_emissions: Uint128,
+    /// Emissions for one reward period at the current rate
+    pub reward_per_period: Uint128,
+    /// Stake across all clubs the emissions are shared by
+    pub total_staked_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct VotingPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TotalPowerAtHeightResponse {
+    pub power: Uint128,
+    pub height: u64,
+}
+
+/// Actions which are charged platform fees
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum FeeAction {
+    BuyAClub,
+    StakeOnAClub,
+    StakeWithdrawFromAClub,
+    ClaimStakerRewards,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct FeeEstimateResponse {
+    /// FURY amount the fees are computed on
+    pub fury_amount: Uint128,
+    /// USDC equivalent of fury_amount as quoted by the pool, i.e. the price used
+    pub usdc_equivalent: Uint128,
+    pub platform_fees_usdc: Uint128,
+    pub transaction_fees_usdc: Uint128,
+    pub control_fees_usdc: Uint128,
+    /// Total to be sent in USDC with the message
+    pub total_fees_usdc: Uint128,
+    pub platform_fees_fury: Uint128,
+    pub transaction_fees_fury: Uint128,
+    pub control_fees_fury: Uint128,
+    pub total_fees_fury: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -164,6 +376,23 @@ pub enum QueryMsg {
 pub enum ReceivedMsg {
     /// Incoming Rewards for meant for distribution to Stakers and Owners
     IncreaseRewardAmount(IncreaseRewardAmountCommand),
+    /// Stake the sent Fury (less fees) on a Club, for the sender or a beneficiary
+    StakeOnAClub {
+        club_name: String,
+        auto_stake: bool,
+        beneficiary: Option<String>,
+    },
+    /// Buy a Club with the sent Fury, which must cover the club price and fees
+    BuyAClub { club_name: String },
+    /// Administrator adds the sent cw20 token to the partner rewards
+    IncreasePartnerReward {},
+    /// Administrator funds an emission schedule with the sent Fury, which must be
+    /// reward_per_second for the whole time from start_timestamp to end_timestamp
+    FundEmissionSchedule {
+        reward_per_second: Uint128,
+        start_timestamp: Timestamp,
+        end_timestamp: Timestamp,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
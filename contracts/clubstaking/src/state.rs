@@ -2,7 +2,10 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use cosmwasm_std::{Addr, Timestamp, Uint128};
-use cw_storage_plus::{Item, Map};
+use cw20::Denom;
+use cw_storage_plus::{
+    Index, IndexList, IndexedMap, Item, Map, MultiIndex, SnapshotItem, SnapshotMap, Strategy,
+};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
@@ -55,6 +58,16 @@ pub struct ClubOwnershipDetails {
 
     /// total amount staked across all stakes for this club
     pub total_staked_amount: Uint128,
+
+    /// Share of the owner reward kept by the owner, the rest goes to the club stakers.
+    /// Specified in percentage multiplied by 100, i.e. 100% = 10000
+    #[serde(default = "full_commission_rate")]
+    pub commission_rate: Uint128,
+}
+
+/// Owners keep the whole owner reward unless they set a lower commission
+pub fn full_commission_rate() -> Uint128 {
+    Uint128::from(10000u128)
 }
 
 /// Used to shift previous owner from ClubOwnerShipDetails to a new state variable -
@@ -91,6 +104,30 @@ pub struct ClubStakingDetails {
 
     /// whether rewards are auto-staked or do they need to be claimed
     pub auto_stake: bool,
+
+    /// lock tier chosen by the staker, sets the reward weight of the stake
+    #[serde(default)]
+    pub lock_tier: LockTier,
+
+    /// stake withdrawn before this time is charged an early unlock penalty
+    #[serde(default)]
+    pub lock_end_timestamp: Timestamp,
+}
+
+/// Lock period a staker commits to, longer locks get a higher reward weight
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum LockTier {
+    NoLock,
+    Days30,
+    Days90,
+    Days180,
+}
+
+impl Default for LockTier {
+    fn default() -> Self {
+        LockTier::NoLock
+    }
 }
 
 /// This is used for saving various bonding details for an unstaked club
@@ -119,22 +156,114 @@ pub struct WinningClubDetails {
     pub total_stake_across_all_clubs: Uint128,
     pub total_stake_in_winning_club: Uint128,
     pub winner_list: Vec<String>,
+    /// total stake across all clubs with lock tier reward weights applied
+    #[serde(default)]
+    pub total_weighted_stake_across_all_clubs: Uint128,
+}
+
+pub struct ClubOwnershipIndexes<'a> {
+    pub owner: MultiIndex<'a, String, ClubOwnershipDetails, String>,
+}
+
+impl<'a> IndexList<ClubOwnershipDetails> for ClubOwnershipIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<ClubOwnershipDetails>> + '_> {
+        let v: Vec<&dyn Index<ClubOwnershipDetails>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
 }
 
 /// Map of clubs and its owners. the key is club name and the
-/// ClubOwnershipDetails will contain information about the owner
-pub const CLUB_OWNERSHIP_DETAILS: Map<String, ClubOwnershipDetails> =
-    Map::new("club_ownership_details");
+/// ClubOwnershipDetails will contain information about the owner.
+/// Indexed by owner address
+pub fn club_ownership_details<'a>(
+) -> IndexedMap<'a, String, ClubOwnershipDetails, ClubOwnershipIndexes<'a>> {
+    let indexes = ClubOwnershipIndexes {
+        owner: MultiIndex::new(
+            |od: &ClubOwnershipDetails| od.owner_address.clone(),
+            "club_ownership_details",
+            "club_ownership_details__owner",
+        ),
+    };
+    IndexedMap::new("club_ownership_details", indexes)
+}
+
+pub struct ClubStakingIndexes<'a> {
+    pub staker: MultiIndex<'a, String, Vec<ClubStakingDetails>, (&'a str, &'a str)>,
+}
+
+impl<'a> IndexList<Vec<ClubStakingDetails>> for ClubStakingIndexes<'a> {
+    fn get_indexes(
+        &'_ self,
+    ) -> Box<dyn Iterator<Item = &'_ dyn Index<Vec<ClubStakingDetails>>> + '_> {
+        let v: Vec<&dyn Index<Vec<ClubStakingDetails>>> = vec![&self.staker];
+        Box::new(v.into_iter())
+    }
+}
 
 /// Map of clubs and its stakers. the key is club name and staker address and the
-/// ClubStakingDetails will contain information about the stakers and amount staked
-pub const CLUB_STAKING_DETAILS: Map<(&str, &str), Vec<ClubStakingDetails>> =
-    Map::new("club_staking_details");
+/// ClubStakingDetails will contain information about the stakers and amount staked.
+/// Indexed by staker address
+pub fn club_staking_details<'a>(
+) -> IndexedMap<'a, (&'a str, &'a str), Vec<ClubStakingDetails>, ClubStakingIndexes<'a>> {
+    let indexes = ClubStakingIndexes {
+        staker: MultiIndex::new(
+            |stakes: &Vec<ClubStakingDetails>| match stakes.first() {
+                Some(stake) => stake.staker_address.clone(),
+                None => String::default(),
+            },
+            "club_staking_details",
+            "club_staking_details__staker",
+        ),
+    };
+    IndexedMap::new("club_staking_details", indexes)
+}
+
+pub struct ClubBondingIndexes<'a> {
+    pub bonder: MultiIndex<'a, String, Vec<ClubBondingDetails>, (&'a str, &'a str)>,
+}
+
+impl<'a> IndexList<Vec<ClubBondingDetails>> for ClubBondingIndexes<'a> {
+    fn get_indexes(
+        &'_ self,
+    ) -> Box<dyn Iterator<Item = &'_ dyn Index<Vec<ClubBondingDetails>>> + '_> {
+        let v: Vec<&dyn Index<Vec<ClubBondingDetails>>> = vec![&self.bonder];
+        Box::new(v.into_iter())
+    }
+}
 
 /// Map of clubs and its bonders. the key is club name and (un)staker address and the
-/// ClubBondingDetails will contain information about the bonders and amount bonded
-pub const CLUB_BONDING_DETAILS: Map<(&str, &str), Vec<ClubBondingDetails>> =
-    Map::new("club_bonding_details");
+/// ClubBondingDetails will contain information about the bonders and amount bonded.
+/// Indexed by bonder address
+pub fn club_bonding_details<'a>(
+) -> IndexedMap<'a, (&'a str, &'a str), Vec<ClubBondingDetails>, ClubBondingIndexes<'a>> {
+    let indexes = ClubBondingIndexes {
+        bonder: MultiIndex::new(
+            |bonds: &Vec<ClubBondingDetails>| match bonds.first() {
+                Some(bond) => bond.bonder_address.clone(),
+                None => String::default(),
+            },
+            "club_bonding_details",
+            "club_bonding_details__bonder",
+        ),
+    };
+    IndexedMap::new("club_bonding_details", indexes)
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ClubMetadata {
+    pub club_name: String,
+    pub display_name: String,
+    pub sport: String,
+    pub logo_uri: Option<String>,
+    /// Maximum total stake in the club, None for no limit
+    pub max_total_stake: Option<Uint128>,
+    /// Price in Fury for Buying this Club, None for the configured club_price
+    pub price_override: Option<Uint128>,
+}
+
+/// Clubs registered by the administrator, the key is club name.
+/// Only registered clubs can be bought, assigned and staked on
+pub const REGISTERED_CLUBS: Map<String, ClubMetadata> = Map::new("registered_clubs");
 
 /// Map of previous owners and their reward points. the key is owner address and the
 /// ClubPreviousOwnerDetails will contain information about the
@@ -142,17 +271,117 @@ pub const CLUB_BONDING_DETAILS: Map<(&str, &str), Vec<ClubBondingDetails>> =
 pub const CLUB_PREVIOUS_OWNER_DETAILS: Map<String, ClubPreviousOwnerDetails> =
     Map::new("club_previous_owner_details");
 
-pub const REWARD: Item<Uint128> = Item::new("staking_reward");
-pub const REWARD_GIVEN_IN_CURRENT_TIMESTAMP: Item<Uint128> =
+/// FURY reward before it moved into REWARDS, only read by migrate
+pub const LEGACY_REWARD: Item<Uint128> = Item::new("staking_reward");
+/// FURY reward given before it moved into REWARDS_GIVEN_IN_CURRENT_TIMESTAMP, only read by migrate
+pub const LEGACY_REWARD_GIVEN_IN_CURRENT_TIMESTAMP: Item<Uint128> =
     Item::new("reward_given_in_current_timestamp");
 pub const CLUB_REWARD_NEXT_TIMESTAMP: Item<Timestamp> = Item::new("club_reward_next_timestamp");
 
+/// FURY rewards funded upfront and streamed into the reward rounds
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EmissionSchedule {
+    pub reward_per_second: Uint128,
+    pub start_timestamp: Timestamp,
+    pub end_timestamp: Timestamp,
+    /// Total amount funded for the schedule, reward_per_second for its whole duration
+    pub funded_amount: Uint128,
+    /// Amount already moved into REWARDS
+    pub emitted_amount: Uint128,
+    /// Emissions up to this time have been moved into REWARDS
+    pub last_emission_timestamp: Timestamp,
+}
+
+pub const EMISSION_SCHEDULE: Item<EmissionSchedule> = Item::new("emission_schedule");
+
+/// An amount of a reward token
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenomAmount {
+    pub denom: Denom,
+    pub amount: Uint128,
+}
+
+/// Rewards waiting for distribution, the key is made from the denom.
+/// The FURY reward is kept under the FURY token, other denoms are partner rewards
+pub const REWARDS: Map<String, DenomAmount> = Map::new("rewards");
+/// Rewards given so far in the current distribution round, the key is made from the denom
+pub const REWARDS_GIVEN_IN_CURRENT_TIMESTAMP: Map<String, Uint128> =
+    Map::new("rewards_given_in_current_timestamp");
+/// Partner rewards owed to a staker for its stake, the key is (club name, staker address)
+pub const PARTNER_REWARDS_OWED: Map<(&str, &str), Vec<DenomAmount>> =
+    Map::new("partner_rewards_owed");
+/// Owner commission in partner rewards owed to a club owner,
+/// the key is (club name, owner address)
+pub const OWNER_PARTNER_COMMISSION_OWED: Map<(&str, &str), Vec<DenomAmount>> =
+    Map::new("owner_partner_commission_owed");
+
 /// Snapshot of ranking by stakes
 pub const CLUB_STAKING_SNAPSHOT: Map<String, Uint128> = Map::new("club_staking_snapshot");
 
+/// Extra reward weight of locked stakes in a club, the key is (club name, lock end
+/// timestamp in seconds). The weighted stake of the club is total_staked_amount plus
+/// the bonus of the locks which have not ended yet
+pub const CLUB_LOCK_BONUS_STAKE: Map<(&str, u64), Uint128> =
+    Map::new("club_lock_bonus_stake_by_lock_end");
+
+/// Snapshot of the weighted stake of each club, taken with the winning club details
+pub const CLUB_WEIGHTED_STAKE_SNAPSHOT: Map<String, Uint128> =
+    Map::new("club_weighted_stake_snapshot");
+
 // fee wallet
 pub const FEE_WALLET: Item<String> = Item::new("config_new_fee_wallet");
 
+/// Addresses which are not charged platform fees, managed by the administrator
+pub const FEE_EXEMPT_ADDRESSES: Map<String, bool> = Map::new("fee_exempt_addresses");
+
+/// cw721 contract holding one token per club (token_id is the club name).
+/// When set, the token holder is treated as the club owner
+pub const CLUB_NFT_CONTRACT: Item<Addr> = Item::new("club_nft_contract");
+
+/// Club tokens minted by this contract, the key is (nft contract, club name)
+pub const CLUB_NFT_MINTED: Map<(&Addr, &str), bool> = Map::new("club_nft_minted");
+
+/// Last time each staker moved stake between clubs, the key is staker address
+pub const LAST_STAKE_MOVE_TIMESTAMP: Map<String, Timestamp> = Map::new("last_stake_move_timestamp");
+
+/// Last time the commission of each club was changed, the key is club name
+pub const CLUB_COMMISSION_CHANGE_TIMESTAMP: Map<String, Timestamp> =
+    Map::new("club_commission_change_timestamp");
+
+/// Stake of each address across all clubs, checkpointed as its voting power
+pub const STAKED_BALANCES: SnapshotMap<String, Uint128> = SnapshotMap::new(
+    "staked_balances",
+    "staked_balances__checkpoints",
+    "staked_balances__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Stake across all clubs, checkpointed as the total voting power
+pub const STAKED_TOTAL: SnapshotItem<Uint128> = SnapshotItem::new(
+    "staked_total",
+    "staked_total__checkpoints",
+    "staked_total__changelog",
+    Strategy::EveryBlock,
+);
+
+/// Where the penalty for leaving the unbonding queue early goes
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PenaltyDestination {
+    Burn,
+    RewardPool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct EarlyExitPenalty {
+    ///Specified in percentage multiplied by 100, i.e. 100% = 10000 and 0.01% = 1
+    pub penalty: Uint128,
+    pub destination: PenaltyDestination,
+}
+
+/// Early exit from unbonding, only allowed when set by the administrator
+pub const EARLY_EXIT_PENALTY: Item<EarlyExitPenalty> = Item::new("early_exit_penalty");
+
 /// Snapshot of winning club details
 pub const WINNING_CLUB_DETAILS_SNAPSHOT: Item<WinningClubDetails> =
     Item::new("winning_club_details_snapshot");
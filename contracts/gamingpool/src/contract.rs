@@ -6,7 +6,8 @@ use std::str::FromStr;
 use cosmwasm_std::entry_point;
 use cosmwasm_std::OverflowOperation::Add;
 use cosmwasm_std::{
-    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response, StdResult, Uint128,
+    to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Reply, Response, StdError,
+    StdResult, Uint128,
 };
 use schemars::_serde_json::ser::State;
 
@@ -15,19 +16,25 @@ use cw20::Cw20QueryMsg;
 
 use crate::error::ContractError;
 use crate::execute::{
-    cancel_game, change_fee_wallet, claim_refund, claim_reward, create_pool, execute_sweep,
-    game_pool_bid_submit, game_pool_reward_distribute, lock_game, save_team_details,
-    set_platform_fee_wallets, set_pool_type_params, swap,
+    cancel_game, change_fee_wallet, claim_refund, claim_reward, create_game, create_pool,
+    create_private_pool, execute_sweep, finalize_ranking, game_pool_auto_bid_submit,
+    game_pool_bid_submit, game_pool_ranking_submit, game_pool_reward_distribute, lock, lock_game,
+    ranking_hash, revoke_results, save_team_details, set_dispute_settings,
+    set_platform_fee_wallets, set_pool_type_params, set_private_pool_settings,
+    set_results_providers, submit_ranking_hash, swap,
 };
 use crate::msg::{BalanceResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
 use crate::query::{
-    get_team_count_for_user_in_pool_type, query_all_pool_type_details, query_all_pools_in_game,
-    query_all_teams, query_game_details, query_game_result, query_get_fee_wallet,
-    query_pool_collection, query_pool_details, query_pool_team_details, query_pool_type_details,
-    query_refund, query_reward, query_swap_data_for_pool, query_team_details, query_total_fees,
+    get_team_count_for_user_in_pool_type, query_all_games, query_all_pool_type_details,
+    query_all_pools_in_game, query_all_teams, query_game_details, query_game_result,
+    query_get_fee_wallet, query_pool_collection, query_pool_details, query_pool_team_details,
+    query_pool_type_details, query_private_pool_settings, query_refund, query_results_providers,
+    query_reward, query_swap_data_for_pool, query_team_details, query_total_fees,
 };
 use crate::state::{
-    Config, GameDetails, GameResult, CONFIG, GAME_DETAILS, GAME_RESULT_DUMMY, SWAP_BALANCE_INFO,
+    Config, GameDetails, GameResult, PoolDetails, CONFIG, CURRENT_REWARD_FOR_POOL, GAME_DETAILS,
+    GAME_POOLS, GAME_RESULT_DUMMY, LEGACY_SWAP_BALANCE_INFO, OPEN_POOLS, POOL_DETAILS,
+    POOL_TYPE_DETAILS, SWAP_BALANCE_INFO,
 };
 
 // This is a comment
@@ -106,6 +113,9 @@ pub fn instantiate(
         &GameDetails {
             game_id: msg.game_id.clone(),
             game_status: GAME_POOL_OPEN,
+            open_until: 0u64,
+            start_time: 0u64,
+            result_deadline: 0u64,
         },
     )?;
     Ok(Response::default())
@@ -130,6 +140,7 @@ pub fn execute(
             max_teams_for_pool,
             max_teams_for_gamer,
             wallet_percentages,
+            prize_structure,
         } => set_pool_type_params(
             deps,
             env,
@@ -140,19 +151,68 @@ pub fn execute(
             max_teams_for_pool,
             max_teams_for_gamer,
             wallet_percentages,
+            prize_structure.unwrap_or_default(),
         ),
-        ExecuteMsg::CancelGame {} => cancel_game(deps, env, info),
-        ExecuteMsg::LockGame {} => lock_game(deps, env, info),
-        ExecuteMsg::CreatePool { pool_type } => create_pool(deps, env, info, pool_type),
-        ExecuteMsg::ClaimReward { gamer } => claim_reward(deps, info, gamer, env),
-        ExecuteMsg::ClaimRefund { gamer, max_spread } => {
-            claim_refund(deps, info, gamer, env, None, max_spread)
-        }
+        ExecuteMsg::CreateGame {
+            game_id,
+            open_until,
+            start_time,
+            result_deadline,
+        } => create_game(
+            deps,
+            env,
+            info,
+            game_id,
+            open_until,
+            start_time,
+            result_deadline,
+        ),
+        ExecuteMsg::CancelGame { game_id } => cancel_game(deps, env, info, game_id),
+        ExecuteMsg::LockGame { game_id } => lock_game(deps, env, info, game_id),
+        ExecuteMsg::Lock { game_id } => lock(deps, env, info, game_id),
+        ExecuteMsg::CreatePool {
+            pool_type,
+            game_id,
+            entry_currency,
+        } => create_pool(
+            deps,
+            env,
+            info,
+            pool_type,
+            game_id,
+            entry_currency.unwrap_or_default(),
+        ),
+        ExecuteMsg::SetPrivatePoolSettings {
+            creation_fee,
+            min_entry_fee,
+            max_entry_fee,
+        } => set_private_pool_settings(deps, info, creation_fee, min_entry_fee, max_entry_fee),
+        ExecuteMsg::CreatePrivatePool {
+            pool_type,
+            game_id,
+            invite_code_hash,
+            entry_fee,
+            entry_currency,
+        } => create_private_pool(
+            deps,
+            env,
+            info,
+            pool_type,
+            game_id,
+            invite_code_hash,
+            entry_fee,
+            entry_currency.unwrap_or_default(),
+        ),
+        ExecuteMsg::ClaimReward { gamer, game_id } => claim_reward(deps, info, gamer, game_id, env),
+        ExecuteMsg::ClaimRefund {
+            gamer,
+            game_id,
+            max_spread,
+        } => claim_refund(deps, info, gamer, game_id, env, None, max_spread),
         ExecuteMsg::GamePoolRewardDistribute {
             pool_id,
             game_winners,
             is_final_batch,
-            ust_for_rake,
             game_id,
         } => game_pool_reward_distribute(
             deps,
@@ -163,8 +223,27 @@ pub fn execute(
             game_winners,
             is_final_batch,
             false,
-            ust_for_rake,
         ),
+        ExecuteMsg::GamePoolRankingSubmit {
+            game_id,
+            pool_id,
+            ranking,
+        } => game_pool_ranking_submit(deps, env, info, game_id, pool_id, ranking, false),
+        ExecuteMsg::SetResultsProviders {
+            providers,
+            threshold,
+            signer_keys,
+        } => set_results_providers(deps, info, providers, threshold, signer_keys),
+        ExecuteMsg::SubmitRankingHash {
+            pool_id,
+            ranking_hash,
+        } => submit_ranking_hash(deps, info, pool_id, ranking_hash),
+        ExecuteMsg::FinalizeRanking {
+            game_id,
+            pool_id,
+            ranking,
+            signature,
+        } => finalize_ranking(deps, env, game_id, pool_id, ranking, signature, false),
         ExecuteMsg::GamePoolBidSubmitCommand {
             gamer,
             pool_type,
@@ -172,8 +251,40 @@ pub fn execute(
             team_id,
             amount,
             max_spread,
+            invite_code,
         } => game_pool_bid_submit(
-            deps, env, info, gamer, pool_type, pool_id, team_id, amount, false, max_spread,
+            deps,
+            env,
+            info,
+            gamer,
+            pool_type,
+            pool_id,
+            team_id,
+            amount,
+            false,
+            max_spread,
+            invite_code,
+        ),
+        ExecuteMsg::GamePoolAutoBidSubmitCommand {
+            gamer,
+            pool_type,
+            game_id,
+            team_id,
+            amount,
+            max_spread,
+            entry_currency,
+        } => game_pool_auto_bid_submit(
+            deps,
+            env,
+            info,
+            gamer,
+            pool_type,
+            game_id,
+            team_id,
+            amount,
+            false,
+            max_spread,
+            entry_currency.unwrap_or_default(),
         ),
         ExecuteMsg::Sweep { funds } => execute_sweep(deps, info, funds),
         ExecuteMsg::Swap {
@@ -182,6 +293,11 @@ pub fn execute(
             max_spread,
         } => swap(deps, env, info, amount, pool_id, max_spread),
         ExecuteMsg::ChangeFeeWallet { address } => change_fee_wallet(deps, env, info, address),
+        ExecuteMsg::SetDisputeSettings {
+            challenge_period,
+            arbiter,
+        } => set_dispute_settings(deps, info, challenge_period, arbiter),
+        ExecuteMsg::RevokeResults { pool_id } => revoke_results(deps, env, info, pool_id),
     }
 }
 
@@ -189,11 +305,54 @@ pub fn execute(
 // We can add expose specific state properties to
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn migrate(deps: DepsMut, _env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    // Swap details saved before SWAP_BALANCE_INFO got its own namespace are moved there,
+    // the entries holding the current reward of a pool stay where they are
+    let raw_pool_ids: Vec<Vec<u8>> = CURRENT_REWARD_FOR_POOL
+        .keys_raw(deps.storage, None, None, Order::Ascending)
+        .collect();
+    for raw_pool_id in raw_pool_ids {
+        let pool_id = String::from_utf8(raw_pool_id).map_err(StdError::invalid_utf8)?;
+        match LEGACY_SWAP_BALANCE_INFO.load(deps.storage, pool_id.clone()) {
+            Ok(swap_info) => {
+                SWAP_BALANCE_INFO.save(deps.storage, pool_id.clone(), &swap_info)?;
+                LEGACY_SWAP_BALANCE_INFO.remove(deps.storage, pool_id);
+            }
+            Err(_) => {}
+        }
+    }
+    // Pools are indexed by game, and public pools that still have room
+    // by game and pool type for auto bids
+    let all_pools: Vec<(String, PoolDetails)> = POOL_DETAILS
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (pool_id, pool) in all_pools {
+        GAME_POOLS.save(deps.storage, (&pool.game_id, &pool_id), &())?;
+        if pool.invite_code_hash.is_some() {
+            continue;
+        }
+        match POOL_TYPE_DETAILS.may_load(deps.storage, pool.pool_type.clone())? {
+            Some(pool_type_details) => {
+                if pool.current_teams_count >= pool_type_details.max_teams_for_pool {
+                    continue;
+                }
+            }
+            None => continue,
+        }
+        OPEN_POOLS.save(
+            deps.storage,
+            (
+                &pool.game_id,
+                &pool.pool_type,
+                pool_id.parse::<u128>().unwrap_or_default(),
+            ),
+            &pool.entry_currency,
+        )?;
+    }
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::PoolTeamDetails { pool_id, user } => {
             to_binary(&query_pool_team_details(deps.storage, pool_id, user)?)
@@ -205,19 +364,24 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::AllPoolTypeDetails {} => to_binary(&query_all_pool_type_details(deps.storage)?),
         QueryMsg::AllTeams { users } => to_binary(&query_all_teams(deps.storage, users)?),
         QueryMsg::QueryReward { gamer } => to_binary(&query_reward(deps.storage, gamer)?),
-        QueryMsg::QueryRefund { gamer } => to_binary(&query_refund(deps.storage, gamer)?),
+        QueryMsg::QueryRefund { gamer, game_id } => {
+            to_binary(&query_refund(deps.storage, env, gamer, game_id)?)
+        }
         QueryMsg::QueryGameResult {
             gamer,
             pool_id,
             team_id,
         } => to_binary(&query_game_result(deps, gamer, pool_id, team_id)?),
-        QueryMsg::GameDetails {} => to_binary(&query_game_details(deps.storage)?),
+        QueryMsg::GameDetails { game_id } => to_binary(&query_game_details(deps.storage, game_id)?),
+        QueryMsg::AllGames {} => to_binary(&query_all_games(deps.storage)?),
         QueryMsg::PoolTeamDetailsWithTeamId {
             pool_id,
             team_id,
             gamer,
         } => to_binary(&query_team_details(deps.storage, pool_id, team_id, gamer)?),
-        QueryMsg::AllPoolsInGame {} => to_binary(&query_all_pools_in_game(deps.storage)?),
+        QueryMsg::AllPoolsInGame { game_id } => {
+            to_binary(&query_all_pools_in_game(deps.storage, game_id)?)
+        }
         QueryMsg::PoolCollection { pool_id } => {
             to_binary(&query_pool_collection(deps.storage, pool_id)?)
         }
@@ -236,6 +400,22 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         }
         QueryMsg::GetTotalFees { amount } => to_binary(&query_total_fees(deps, amount)?),
         QueryMsg::GetFeeWallet {} => to_binary(&query_get_fee_wallet(deps)?),
+        QueryMsg::ResultsProviders {} => to_binary(&query_results_providers(deps.storage)?),
+        QueryMsg::PrivatePoolSettings {} => to_binary(&query_private_pool_settings(deps.storage)?),
+        QueryMsg::RankingHash {
+            game_id,
+            pool_id,
+            ranking,
+        } => {
+            let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+            to_binary(&ranking_hash(
+                &env,
+                game_id,
+                pool_id,
+                pool_details.results_revision,
+                ranking,
+            )?)
+        }
     }
 }
 
@@ -3,9 +3,9 @@ use std::ops::{Add, Div, Mul};
 use std::str::FromStr;
 
 use cosmwasm_std::{
-    from_binary, to_binary, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo,
-    Order, QueryRequest, Response, StdError, StdResult, Storage, SubMsg, Uint128, WasmMsg,
-    WasmQuery,
+    from_binary, to_binary, Addr, BankMsg, Binary, Coin, CosmosMsg, Decimal, DepsMut, Env,
+    MessageInfo, Order, QueryRequest, Response, StdError, StdResult, Storage, SubMsg, Uint128,
+    WasmMsg, WasmQuery,
 };
 
 use crate::contract::{
@@ -14,20 +14,25 @@ use crate::contract::{
     INITIAL_TEAM_POINTS, INITIAL_TEAM_RANK, NINETY_NINE_NINE_PERCENT, REWARDS_DISTRIBUTED,
     REWARDS_NOT_DISTRIBUTED, UNCLAIMED_REFUND, UNCLAIMED_REWARD,
 };
-use crate::msg::{BalanceResponse, ReceivedMsg};
+use crate::msg::{BalanceResponse, PoolAllocationResponse, RankingPayload, ReceivedMsg};
 use crate::query::{
-    get_team_count_for_user_in_pool_type, query_pool_details, query_pool_type_details,
+    default_game_id, game_pool_ids, get_team_count_for_user_in_pool_type, pool_entry_fee,
+    pool_refund_amount, query_pool_collection, query_pool_details, query_pool_type_details,
     query_swap_data_for_pool,
 };
 use crate::state::{
-    FeeDetails, GameDetails, GameResult, PoolDetails, PoolTeamDetails, PoolTypeDetails,
-    SwapBalanceDetails, WalletPercentage, WalletTransferDetails, CONFIG, CONTRACT_POOL_COUNT,
-    CURRENT_REWARD_FOR_POOL, FEE_WALLET, GAME_DETAILS, PLATFORM_WALLET_PERCENTAGES, POOL_DETAILS,
-    POOL_TEAM_DETAILS, POOL_TYPE_DETAILS, SWAP_BALANCE_INFO,
+    DisputeSettings, EntryCurrency, FeeDetails, GameDetails, GameResult, PoolDetails,
+    PoolTeamDetails, PoolTypeDetails, PrivatePoolSettings, PrizeStructure, ResultsProviders,
+    ResultsSignerKey, SignerKeyType, SwapBalanceDetails, TeamRank, WalletPercentage,
+    WalletTransferDetails, CONFIG, CONTRACT_POOL_COUNT, CURRENT_REWARD_FOR_POOL, DISPUTE_SETTINGS,
+    FEE_WALLET, GAME_DETAILS, GAME_POOLS, OPEN_POOLS, PLATFORM_WALLET_PERCENTAGES, POOL_DETAILS,
+    POOL_TEAM_DETAILS, POOL_TYPE_DETAILS, PRIVATE_POOL_SETTINGS, RANKING_HASH_VOTES,
+    RESULTS_PROVIDERS, SWAP_BALANCE_INFO,
 };
 use crate::ContractError;
 use cw20::Balance;
 use cw20::{Cw20ExecuteMsg, Cw20QueryMsg, Cw20ReceiveMsg, Denom};
+use sha2::{Digest, Sha256};
 
 use crate::util;
 use crate::util::{NORMAL_DECIMAL, THOUSAND};
@@ -92,6 +97,7 @@ pub fn set_pool_type_params(
     max_teams_for_pool: u32,
     max_teams_for_gamer: u32,
     wallet_percentages: Vec<WalletPercentage>,
+    prize_structure: PrizeStructure,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
@@ -109,10 +115,32 @@ pub fn set_pool_type_params(
         None => {}
     };
 
+    match prize_structure.clone() {
+        PrizeStructure::TopN { percentages } => {
+            let mut total_percentage = 0u128;
+            for percentage in percentages {
+                total_percentage += percentage as u128;
+            }
+            if total_percentage != HUNDRED_PERCENT {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Prize percentages must add up to hundred percent"),
+                }));
+            }
+        }
+        _ => {}
+    }
+
     let mut rake_list: Vec<WalletPercentage> = Vec::new();
+    let mut total_rake_percentage = 0u128;
     for wp in wallet_percentages {
+        total_rake_percentage += wp.percentage as u128;
         rake_list.push(wp);
     }
+    if total_rake_percentage > HUNDRED_PERCENT {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Rake percentages cannot exceed hundred percent"),
+        }));
+    }
     POOL_TYPE_DETAILS.save(
         deps.storage,
         pool_type.clone(),
@@ -123,19 +151,80 @@ pub fn set_pool_type_params(
             max_teams_for_pool: max_teams_for_pool,
             max_teams_for_gamer: max_teams_for_gamer,
             rake_list: rake_list,
+            prize_structure: prize_structure,
         },
     )?;
     return Ok(Response::default());
 }
 
-pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn create_game(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    open_until: u64,
+    start_time: u64,
+    result_deadline: u64,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
+    match gd {
+        Some(_gd) => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Game already exists"),
+            }));
+        }
+        None => {}
+    }
+    if open_until <= env.block.time.seconds() {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Bidding deadline must be in the future"),
+        }));
+    }
+    if start_time < open_until {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Game cant start before bidding is closed"),
+        }));
+    }
+    if result_deadline < start_time {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Result deadline cant be before the game starts"),
+        }));
+    }
+
+    GAME_DETAILS.save(
+        deps.storage,
+        game_id.clone(),
+        &GameDetails {
+            game_id: game_id.clone(),
+            game_status: GAME_POOL_OPEN,
+            open_until: open_until,
+            start_time: start_time,
+            result_deadline: result_deadline,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("game_id", game_id.clone())
+        .add_attribute("game_status", "GAME_POOL_OPEN".to_string()));
+}
+
+pub fn cancel_game(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
         return Err(ContractError::UnauthorizedInvoker {
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
 
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -164,16 +253,13 @@ pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Respon
         deps.storage,
         game_id.clone(),
         &GameDetails {
-            game_id: game_id.clone(),
             game_status: GAME_CANCELLED,
+            ..game.clone()
         },
     )?;
 
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
+    // Get all pools of the game
+    let all_pools = game_pool_ids(deps.storage, &game_id)?;
     for pool_id in all_pools {
         let mut pool;
         let pd = POOL_DETAILS.may_load(deps.storage, pool_id.clone())?;
@@ -207,15 +293,70 @@ pub fn cancel_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Respon
         .add_attribute("game_status", "GAME_CANCELLED".to_string()));
 }
 
-pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response, ContractError> {
+pub fn lock_game(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
         return Err(ContractError::UnauthorizedInvoker {
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
+    return _lock_game(deps, game_id);
+}
+
+// Anyone can lock the game once its bidding deadline has passed
+pub fn lock(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    game_id: String,
+) -> Result<Response, ContractError> {
+    let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
+    match gd {
+        Some(game) => {
+            if !is_bidding_deadline_passed(&game, &env) {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Cant lock this game before the bidding deadline"),
+                }));
+            }
+        }
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Game status cannot be retrieved"),
+            }));
+        }
+    }
+    return _lock_game(deps, game_id);
+}
+
+// A game without a deadline (open_until is 0) stays open until it is locked
+pub fn is_bidding_deadline_passed(game: &GameDetails, env: &Env) -> bool {
+    return game.open_until != 0 && env.block.time.seconds() >= game.open_until;
+}
+
+// A game without a result deadline (result_deadline is 0) never runs out of time for results
+pub fn is_result_deadline_passed(game: &GameDetails, env: &Env) -> bool {
+    return game.result_deadline != 0 && env.block.time.seconds() >= game.result_deadline;
+}
+
+// A pool is abandoned when the result deadline of its game has passed
+// without the final batch of rewards being distributed
+pub fn is_pool_abandoned(storage: &dyn Storage, pool: &PoolDetails, env: &Env) -> StdResult<bool> {
+    if pool.rewards_distributed == REWARDS_DISTRIBUTED {
+        return Ok(false);
+    }
+    let gd = GAME_DETAILS.may_load(storage, pool.game_id.clone())?;
+    match gd {
+        Some(game) => return Ok(is_result_deadline_passed(&game, env)),
+        None => return Ok(false),
+    }
+}
 
+fn _lock_game(deps: DepsMut, game_id: String) -> Result<Response, ContractError> {
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
     match gd {
@@ -238,16 +379,13 @@ pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response
         deps.storage,
         game_id.clone(),
         &GameDetails {
-            game_id: game_id.clone(),
             game_status: GAME_POOL_CLOSED,
+            ..game.clone()
         },
     )?;
 
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
+    // Get all pools of the game
+    let all_pools = game_pool_ids(deps.storage, &game_id)?;
     for pool_id in all_pools {
         let mut pool;
         let pd = POOL_DETAILS.may_load(deps.storage, pool_id.clone())?;
@@ -287,9 +425,37 @@ pub fn lock_game(deps: DepsMut, _env: Env, info: MessageInfo) -> Result<Response
 
 pub fn create_pool(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     pool_type: String,
+    game_id: String,
+    entry_currency: EntryCurrency,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let pool_id = _create_pool(
+        deps,
+        env,
+        pool_type,
+        game_id,
+        None,
+        None,
+        None,
+        entry_currency,
+    )?;
+    return Ok(Response::new().add_attribute("pool_id", pool_id.clone()));
+}
+
+pub fn set_private_pool_settings(
+    deps: DepsMut,
+    info: MessageInfo,
+    creation_fee: Uint128,
+    min_entry_fee: Uint128,
+    max_entry_fee: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
@@ -297,7 +463,130 @@ pub fn create_pool(
             invoker: info.sender.to_string(),
         });
     }
-    let game_id = config.game_id;
+    if min_entry_fee.is_zero() || min_entry_fee > max_entry_fee {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Invalid entry fee bounds for private pools"),
+        }));
+    }
+    PRIVATE_POOL_SETTINGS.save(
+        deps.storage,
+        &PrivatePoolSettings {
+            creation_fee: creation_fee,
+            min_entry_fee: min_entry_fee,
+            max_entry_fee: max_entry_fee,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("action", "set_private_pool_settings")
+        .add_attribute("creation_fee", creation_fee.to_string()));
+}
+
+// Any gamer can open a private pool of an existing pool type for their friends.
+// Only bids that carry the invite code matching invite_code_hash are accepted.
+pub fn create_private_pool(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_type: String,
+    game_id: String,
+    invite_code_hash: Binary,
+    entry_fee: Option<Uint128>,
+    entry_currency: EntryCurrency,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let pps = PRIVATE_POOL_SETTINGS.may_load(deps.storage)?;
+    let private_pool_settings;
+    match pps {
+        Some(pps) => {
+            private_pool_settings = pps;
+        }
+        None => {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Private pools are not enabled"),
+            }));
+        }
+    }
+    if POOL_TYPE_DETAILS
+        .may_load(deps.storage, pool_type.clone())?
+        .is_none()
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Cant get details for pool type"),
+        }));
+    }
+    if invite_code_hash.len() != 32 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Invite code hash must be a sha256 hash"),
+        }));
+    }
+    match entry_fee {
+        Some(entry_fee) => {
+            if entry_fee < private_pool_settings.min_entry_fee
+                || entry_fee > private_pool_settings.max_entry_fee
+            {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Entry fee is out of the allowed bounds"),
+                }));
+            }
+        }
+        None => {}
+    }
+    // The creation fee has to be paid exactly and goes to the fee wallet
+    let mut messages = Vec::new();
+    if private_pool_settings.creation_fee.is_zero() {
+        if !info.funds.is_empty() {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("No creation fee is charged for private pools"),
+            }));
+        }
+    } else {
+        if info.funds.len() != 1 {
+            return Err(ContractError::InvalidNumberOfCoinsSent {});
+        }
+        let balance = Balance::from(info.funds.clone());
+        let usdc_amount =
+            util::get_amount_of_denom(balance, Denom::Native(config.usdc_ibc_symbol.clone()))?;
+        if usdc_amount < private_pool_settings.creation_fee {
+            return Err(ContractError::InsufficientFeesUst {});
+        }
+        if usdc_amount > private_pool_settings.creation_fee {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Amount sent exceeds the creation fee"),
+            }));
+        }
+        let fee_wallet = FEE_WALLET.load(deps.storage)?;
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: fee_wallet,
+            amount: info.funds.clone(),
+        }));
+    }
+
+    let pool_id = _create_pool(
+        deps,
+        env,
+        pool_type,
+        game_id,
+        Some(info.sender.clone()),
+        Some(invite_code_hash),
+        entry_fee,
+        entry_currency,
+    )?;
+    return Ok(Response::new()
+        .add_attribute("pool_id", pool_id.clone())
+        .add_attribute("creator", info.sender.to_string())
+        .add_messages(messages));
+}
+
+fn _create_pool(
+    deps: DepsMut,
+    env: Env,
+    pool_type: String,
+    game_id: String,
+    creator: Option<Addr>,
+    invite_code_hash: Option<Binary>,
+    entry_fee: Option<Uint128>,
+    entry_currency: EntryCurrency,
+) -> Result<String, ContractError> {
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
     match gd {
@@ -315,6 +604,11 @@ pub fn create_pool(
             msg: String::from("Game is not open for bidding"),
         }));
     }
+    if is_bidding_deadline_passed(&game, &env) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Bidding deadline for this game has passed"),
+        }));
+    }
 
     let dummy_wallet = String::from(DUMMY_WALLET);
     let address = deps.api.addr_validate(dummy_wallet.clone().as_str())?;
@@ -338,6 +632,7 @@ pub fn create_pool(
     )?;
     count += Uint128::from(1u128);
     let pool_id_str: String = count.to_string();
+    let is_public_pool = invite_code_hash.is_none();
 
     POOL_DETAILS.save(
         deps.storage,
@@ -350,9 +645,23 @@ pub fn create_pool(
             rewards_distributed: REWARDS_NOT_DISTRIBUTED,
             pool_refund_status: false,
             pool_reward_status: false,
+            results_finalized_at: 0,
+            creator: creator,
+            invite_code_hash: invite_code_hash,
+            entry_fee: entry_fee,
+            entry_currency: entry_currency,
+            results_revision: 0,
         },
     )?;
-    return Ok(Response::new().add_attribute("pool_id", pool_id_str.clone()));
+    GAME_POOLS.save(deps.storage, (&game_id, &pool_id_str), &())?;
+    if is_public_pool {
+        OPEN_POOLS.save(
+            deps.storage,
+            (&game_id, &pool_type, count.u128()),
+            &entry_currency,
+        )?;
+    }
+    return Ok(pool_id_str);
 }
 
 pub fn query_platform_fees(
@@ -385,6 +694,7 @@ pub fn game_pool_bid_submit(
     amount: Uint128,
     testing: bool,
     max_spread: Option<Decimal>,
+    invite_code: Option<String>,
 ) -> Result<Response, ContractError> {
     //Check if gamer is same as invoker
     if gamer != info.sender {
@@ -396,7 +706,13 @@ pub fn game_pool_bid_submit(
     let config = CONFIG.load(deps.storage)?;
     // Calculate
     let platform_fee = config.platform_fee; //  Should be in %
-    let game_id = config.clone().game_id;
+    let mut pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    if pool_type != pool_details.pool_type {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Pool type does not match the pool"),
+        }));
+    }
+    let game_id = pool_details.game_id.clone();
     let mut messages = Vec::new(); //  Use this to append any execute messaages in the funciton
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -415,9 +731,35 @@ pub fn game_pool_bid_submit(
             msg: String::from("Game is not open for bidding"),
         }));
     }
+    if is_bidding_deadline_passed(&game, &env) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Bidding deadline for this game has passed"),
+        }));
+    }
+
+    match pool_details.invite_code_hash.clone() {
+        Some(invite_code_hash) => {
+            let code_matches;
+            match invite_code {
+                Some(invite_code) => {
+                    code_matches = Sha256::digest(invite_code.as_bytes()).to_vec()
+                        == invite_code_hash.to_vec();
+                }
+                None => {
+                    code_matches = false;
+                }
+            }
+            if !code_matches {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Invalid invite code for this private pool"),
+                }));
+            }
+        }
+        None => {}
+    }
 
     let pool_type_details;
-    let ptd = POOL_TYPE_DETAILS.may_load(deps.storage, pool_type.clone())?;
+    let ptd = POOL_TYPE_DETAILS.may_load(deps.storage, pool_details.pool_type.clone())?;
     match ptd.clone() {
         Some(ptd) => {
             pool_type_details = ptd;
@@ -428,6 +770,7 @@ pub fn game_pool_bid_submit(
             }));
         }
     }
+    let entry_fee = pool_entry_fee(deps.storage, &pool_details)?;
     let required_platform_fee_ust;
     let transaction_fee;
     match testing {
@@ -436,11 +779,7 @@ pub fn game_pool_bid_submit(
             transaction_fee = Uint128::zero();
         }
         false => {
-            let fee_details = query_platform_fees(
-                pool_type_details.pool_fee,
-                platform_fee,
-                config.transaction_fee,
-            )?;
+            let fee_details = query_platform_fees(entry_fee, platform_fee, config.transaction_fee)?;
             required_platform_fee_ust = fee_details.platform_fee;
             transaction_fee = fee_details.transaction_fee;
         }
@@ -451,21 +790,32 @@ pub fn game_pool_bid_submit(
             return Err(ContractError::InvalidNumberOfCoinsSent {});
         }
 
+        // USDC entries are sent along with the fees
+        let mut required_usdc = required_platform_fee_ust + transaction_fee;
+        if pool_details.entry_currency == EntryCurrency::Usdc {
+            required_usdc += amount;
+        }
         let balance = Balance::from(info.funds);
         let usdc_amount =
             util::get_amount_of_denom(balance, Denom::Native(config.usdc_ibc_symbol.clone()))?;
-        if usdc_amount < required_platform_fee_ust + transaction_fee {
+        if usdc_amount < required_usdc {
             return Err(ContractError::InsufficientFeesUst {});
         }
+        // Any USDC above the entry fee and the fees would be kept without being counted
+        if pool_details.entry_currency == EntryCurrency::Usdc && usdc_amount > required_usdc {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Amount sent exceeds the entry fee and the platform fee"),
+            }));
+        }
     }
 
-    let mut pool_fee: Uint128 = pool_type_details.pool_fee;
-    if !testing {
+    let mut pool_fee: Uint128 = entry_fee;
+    if !testing && pool_details.entry_currency == EntryCurrency::Fury {
         let pool_fee_response: Token1ForToken2PriceResponse =
             deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: config.clone().pool_address.to_string(),
                 msg: to_binary(&FanfuryswapQueryMsg::Token1ForToken2Price {
-                    token1_amount: pool_type_details.pool_fee,
+                    token1_amount: entry_fee,
                 })?,
             }))?;
 
@@ -473,9 +823,19 @@ pub fn game_pool_bid_submit(
     }
     let max_teams_for_pool = pool_type_details.max_teams_for_pool;
     let max_teams_for_gamer = pool_type_details.max_teams_for_gamer;
-    let amount_required =
-        pool_fee * (Uint128::from(NINETY_NINE_NINE_PERCENT)) / (Uint128::from(HUNDRED_PERCENT));
-    if amount < amount_required {
+    // FURY bids get a small tolerance for price movement, USDC entries pay exactly the fee
+    let amount_matches;
+    match pool_details.entry_currency {
+        EntryCurrency::Fury => {
+            amount_matches = amount
+                >= pool_fee * (Uint128::from(NINETY_NINE_NINE_PERCENT))
+                    / (Uint128::from(HUNDRED_PERCENT));
+        }
+        EntryCurrency::Usdc => {
+            amount_matches = amount == pool_fee;
+        }
+    }
+    if !amount_matches {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Amount being bid does not match the pool fee and the platform fee"),
         }));
@@ -501,7 +861,6 @@ pub fn game_pool_bid_submit(
     }
 
     let pool_id_return;
-    let mut pool_details = query_pool_details(deps.storage, pool_id.clone())?;
 
     // check if the pool can accomodate the team
     if pool_details.current_teams_count < max_teams_for_pool {
@@ -511,13 +870,10 @@ pub fn game_pool_bid_submit(
             deps.storage,
             pool_id.clone(),
             &PoolDetails {
-                pool_type: pool_type.clone(),
                 pool_id: pool_id.clone(),
-                game_id: pool_details.game_id.clone(),
-                current_teams_count: pool_details.current_teams_count,
-                rewards_distributed: pool_details.rewards_distributed,
                 pool_refund_status: false,
                 pool_reward_status: false,
+                ..pool_details.clone()
             },
         )?;
         // Now save the team details
@@ -536,12 +892,28 @@ pub fn game_pool_bid_submit(
             INITIAL_TEAM_POINTS,
             INITIAL_TEAM_RANK,
         )?;
+        // A full pool can no longer be allocated to auto bids
+        if pool_details.current_teams_count >= max_teams_for_pool {
+            OPEN_POOLS.remove(
+                deps.storage,
+                (
+                    &pool_details.game_id,
+                    &pool_details.pool_type,
+                    pool_id.parse::<u128>().unwrap_or_default(),
+                ),
+            );
+        }
     } else {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("pool max team limit reached "),
         }));
     }
 
+    // The pool collects USDC so there is nothing to swap
+    if pool_details.entry_currency == EntryCurrency::Usdc {
+        return Ok(Response::new().add_attribute("pool_id", pool_id_return.clone()));
+    }
+
     // Sending Fury token to the contract
     let transfer_msg = Cw20ExecuteMsg::TransferFrom {
         owner: info.sender.into_string(),
@@ -630,6 +1002,103 @@ pub fn game_pool_bid_submit(
         .add_messages(messages));
 }
 
+// Bids into the first open pool of the pool type, creating the next pool when all are full.
+// The assigned pool id is returned in the response data.
+pub fn game_pool_auto_bid_submit(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    gamer: String,
+    pool_type: String,
+    game_id: String,
+    team_id: String,
+    amount: Uint128,
+    testing: bool,
+    max_spread: Option<Decimal>,
+    entry_currency: EntryCurrency,
+) -> Result<Response, ContractError> {
+    //Check if gamer is same as invoker
+    if gamer != info.sender {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let pool_id = allocate_pool(
+        deps.branch(),
+        env.clone(),
+        gamer.clone(),
+        pool_type.clone(),
+        game_id.clone(),
+        entry_currency,
+    )?;
+    let rsp = game_pool_bid_submit(
+        deps,
+        env,
+        info,
+        gamer,
+        pool_type,
+        pool_id.clone(),
+        team_id,
+        amount,
+        testing,
+        max_spread,
+        None,
+    )?;
+    return Ok(rsp.set_data(to_binary(&PoolAllocationResponse { pool_id: pool_id })?));
+}
+
+// Finds the first public pool of the pool type that can take another team of this gamer
+pub fn allocate_pool(
+    deps: DepsMut,
+    env: Env,
+    gamer: String,
+    pool_type: String,
+    game_id: String,
+    entry_currency: EntryCurrency,
+) -> Result<String, ContractError> {
+    let pool_type_details = query_pool_type_details(deps.storage, pool_type.clone())?;
+    // Only the open public pools of this game and pool type are looked at, lowest pool id first
+    let open_pools: Vec<(u128, EntryCurrency)> = OPEN_POOLS
+        .prefix((&game_id, &pool_type))
+        .range(deps.storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (pool_number, pool_currency) in open_pools {
+        if pool_currency != entry_currency {
+            continue;
+        }
+        let pool = query_pool_details(deps.storage, pool_number.to_string())?;
+        if pool.current_teams_count >= pool_type_details.max_teams_for_pool {
+            continue;
+        }
+        let mut user_team_count = 0;
+        let ptd = POOL_TEAM_DETAILS.may_load(deps.storage, (&pool.pool_id.clone(), &gamer))?;
+        match ptd {
+            Some(all_teams) => {
+                for team in all_teams {
+                    if team.gamer_address == gamer {
+                        user_team_count += 1;
+                    }
+                }
+            }
+            None => {}
+        }
+        if user_team_count >= pool_type_details.max_teams_for_gamer {
+            continue;
+        }
+        return Ok(pool.pool_id);
+    }
+    return _create_pool(
+        deps,
+        env,
+        pool_type,
+        game_id,
+        None,
+        None,
+        None,
+        entry_currency,
+    );
+}
+
 pub fn save_team_details(
     storage: &mut dyn Storage,
     _env: Env,
@@ -682,6 +1151,7 @@ pub fn claim_reward(
     deps: DepsMut,
     info: MessageInfo,
     gamer: String,
+    game_id: Option<String>,
     env: Env,
 ) -> Result<Response, ContractError> {
     let gamer_addr = deps.api.addr_validate(&gamer)?;
@@ -692,12 +1162,11 @@ pub fn claim_reward(
         });
     }
 
+    let game_id = default_game_id(deps.storage, game_id)?;
     let mut user_reward = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
+    let mut user_reward_usdc = Uint128::zero();
+    // Get all pools of the game
+    let all_pools = game_pool_ids(deps.storage, &game_id)?;
     for pool_id in all_pools {
         // Get the existing teams for this pool
         let mut pool_details: PoolDetails = Default::default();
@@ -710,10 +1179,16 @@ pub fn claim_reward(
                 continue;
             }
         }
-        if !pool_details.pool_reward_status {
+        // Rewards can only be claimed once the final batch is in
+        if !pool_details.pool_reward_status
+            || pool_details.rewards_distributed != REWARDS_DISTRIBUTED
+        {
             continue;
         }
-        let mut pool_team_details;
+        if is_in_dispute_window(deps.storage, &pool_details, &env)? {
+            continue;
+        }
+        let mut pool_team_details;
         match POOL_TEAM_DETAILS.load(deps.storage, (&*pool_id.clone(), &gamer.clone())) {
             Ok(some) => {
                 pool_team_details = some;
@@ -726,7 +1201,14 @@ pub fn claim_reward(
         for team_details in pool_team_details {
             if !team_details.claimed_reward {
                 let mut updated_team = team_details.clone();
-                user_reward += team_details.reward_amount;
+                match pool_details.entry_currency {
+                    EntryCurrency::Fury => {
+                        user_reward += team_details.reward_amount;
+                    }
+                    EntryCurrency::Usdc => {
+                        user_reward_usdc += team_details.reward_amount;
+                    }
+                }
                 updated_team.claimed_reward = true;
                 updated_details.push(updated_team);
             } else {
@@ -738,7 +1220,7 @@ pub fn claim_reward(
         }
     }
 
-    if user_reward == Uint128::zero() {
+    if user_reward == Uint128::zero() && user_reward_usdc == Uint128::zero() {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("No reward for this user"),
         }));
@@ -753,14 +1235,17 @@ pub fn claim_reward(
     //         fury_count: user_reward,
     //     },
     // )?;
-    let expected_usdc_response: Token2ForToken1PriceResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: config.clone().pool_address.to_string(),
-            msg: to_binary(&FanfuryswapQueryMsg::Token2ForToken1Price {
-                token2_amount: user_reward,
-            })?,
-        }))?;
-    let user_reward_in_ust = expected_usdc_response.token1_amount;
+    let mut user_reward_in_ust = user_reward_usdc;
+    if !user_reward.is_zero() {
+        let expected_usdc_response: Token2ForToken1PriceResponse =
+            deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                contract_addr: config.clone().pool_address.to_string(),
+                msg: to_binary(&FanfuryswapQueryMsg::Token2ForToken1Price {
+                    token2_amount: user_reward,
+                })?,
+            }))?;
+        user_reward_in_ust += expected_usdc_response.token1_amount;
+    }
     let fee_details = query_platform_fees(
         user_reward_in_ust,
         config.platform_fee,
@@ -787,17 +1272,30 @@ pub fn claim_reward(
     });
     messages.push(r);
 
-    let transfer_msg = Cw20ExecuteMsg::Transfer {
-        recipient: info.sender.into_string(),
-        amount: user_reward,
-    };
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.fury_token_address.to_string(),
-        msg: to_binary(&transfer_msg)?,
-        funds: vec![],
-    }));
+    if !user_reward.is_zero() {
+        let transfer_msg = Cw20ExecuteMsg::Transfer {
+            recipient: info.sender.to_string(),
+            amount: user_reward,
+        };
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.fury_token_address.to_string(),
+            msg: to_binary(&transfer_msg)?,
+            funds: vec![],
+        }));
+    }
+    // Rewards of USDC pools are paid back in USDC
+    if !user_reward_usdc.is_zero() {
+        messages.push(CosmosMsg::Bank(BankMsg::Send {
+            to_address: info.sender.to_string(),
+            amount: vec![Coin {
+                denom: config.usdc_ibc_symbol.clone(),
+                amount: user_reward_usdc,
+            }],
+        }));
+    }
     return Ok(Response::new()
         .add_attribute("amount", user_reward.to_string())
+        .add_attribute("usdc_amount", user_reward_usdc.to_string())
         .add_attribute("action", "reward")
         .add_messages(messages));
 }
@@ -813,6 +1311,7 @@ pub fn claim_refund(
     deps: DepsMut,
     info: MessageInfo,
     gamer: String,
+    game_id: Option<String>,
     env: Env,
     testing: Option<bool>,
     max_spread: Option<Decimal>,
@@ -827,13 +1326,15 @@ pub fn claim_refund(
         });
     }
     let config = CONFIG.load(deps.storage)?;
-    // Get all pools
-
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(deps.storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
+    let game_id = default_game_id(deps.storage, game_id)?;
+    // Get all pools of the game
+    let all_pools = game_pool_ids(deps.storage, &game_id)?;
     let mut total_refund_amount = Uint128::zero();
+    let mut usdc_refund_amount = Uint128::zero();
+    let mut swapped_refund_amount = Uint128::zero();
+    let mut swapped_fury_refund = Uint128::zero();
+    let mut rake_refund_amount = Uint128::zero();
+    let mut refunded_entry_fees = Uint128::zero();
     for pool_id in all_pools {
         let mut pool_details: PoolDetails = Default::default();
         let pd = POOL_DETAILS.load(deps.storage, pool_id.clone());
@@ -846,24 +1347,62 @@ pub fn claim_refund(
             }
         }
         if !pool_details.pool_refund_status {
-            continue;
+            if !is_pool_abandoned(deps.storage, &pool_details, &env)? {
+                continue;
+            }
+            // No results came in on time so the pool is refunded instead
+            pool_details.pool_refund_status = true;
+            pool_details.pool_reward_status = false;
+            POOL_DETAILS.save(deps.storage, pool_id.clone(), &pool_details)?;
+        }
+        let entry_fee = pool_entry_fee(deps.storage, &pool_details)?;
+        let refund_amount = pool_refund_amount(deps.storage, &pool_details)?;
+        // A FURY pool whose collection was already swapped is refunded from the swapped FURY,
+        // the part kept aside for the rake is still held in USDC
+        let mut swap_info = None;
+        if pool_details.entry_currency == EntryCurrency::Fury {
+            match SWAP_BALANCE_INFO.may_load(deps.storage, pool_id.clone())? {
+                Some(swap)
+                    if !swap.exchange_rate.is_zero()
+                        && !(swap.ust_amount_swapped + swap.ust_for_rake).is_zero() =>
+                {
+                    swap_info = Some(swap);
+                }
+                _ => {}
+            }
         }
-        let pool_type = POOL_TYPE_DETAILS.load(deps.storage, pool_details.pool_type)?;
-        let refund_amount = pool_type.pool_fee;
         let pool_team_details =
             POOL_TEAM_DETAILS.load(deps.storage, (pool_id.as_ref(), &gamer.clone()));
         match pool_team_details {
             Ok(some) => {
                 let mut updated_details = Vec::new();
                 for team_details in some {
-                    if !team_details.claimed_refund {
+                    if team_details.claimed_reward {
+                        // Rewards claimed from an earlier batch are not refunded
+                        updated_details.push(team_details);
+                    } else if !team_details.claimed_refund {
                         let mut updated_team = team_details.clone();
                         updated_team.refund_amount = refund_amount;
                         total_refund_amount += refund_amount;
+                        refunded_entry_fees += entry_fee;
+                        if pool_details.entry_currency == EntryCurrency::Usdc {
+                            usdc_refund_amount += refund_amount;
+                        }
+                        if let Some(swap) = &swap_info {
+                            let swapped_amount = refund_amount.multiply_ratio(
+                                swap.ust_amount_swapped,
+                                swap.ust_amount_swapped + swap.ust_for_rake,
+                            );
+                            swapped_refund_amount += refund_amount;
+                            rake_refund_amount += refund_amount - swapped_amount;
+                            swapped_fury_refund +=
+                                swapped_amount.multiply_ratio(swap.exchange_rate, HUNDRED_PERCENT);
+                        }
                         updated_team.claimed_refund = true;
                         updated_details.push(updated_team);
                     } else {
-                        return Err(ContractError::RefundAlreadyClaimed {});
+                        // Refunded by an earlier claim, only the new refunds are paid
+                        updated_details.push(team_details);
                     }
                 }
                 if !updated_details.is_empty() {
@@ -884,7 +1423,7 @@ pub fn claim_refund(
         }));
     }
     let refund_details = query_platform_fees(
-        total_refund_amount,
+        refunded_entry_fees,
         config.platform_fee,
         config.transaction_fee,
     )?;
@@ -908,52 +1447,69 @@ pub fn claim_refund(
     //     to: Option::from(info.sender.to_string()),
     // };
 
-    let mut swap_fee = Uint128::zero();
-    // Swap fee should be platform+transaction fee for the transaction
-    if !testing_status {
-        // swap_fee = deps.querier.query_wasm_smart(
-        //     config.clone().astro_proxy_address,
-        //     &QueryMsgSimulation::QueryPlatformFees {
-        //         msg: to_binary(&swap_message)?
-        //     },
-        // )?;
-
-        let swap_config_response: FanfuryswapConfigResponse =
+    // Entries paid in FURY are swapped back to FURY, USDC entries are sent back as they are
+    let fury_refund_amount = total_refund_amount - usdc_refund_amount - swapped_refund_amount;
+    let mut final_amount = Uint128::zero();
+    if !fury_refund_amount.is_zero() {
+        let mut swap_fee = Uint128::zero();
+        // Swap fee should be platform+transaction fee for the transaction
+        if !testing_status {
+            // swap_fee = deps.querier.query_wasm_smart(
+            //     config.clone().astro_proxy_address,
+            //     &QueryMsgSimulation::QueryPlatformFees {
+            //         msg: to_binary(&swap_message)?
+            //     },
+            // )?;
+
+            let swap_config_response: FanfuryswapConfigResponse =
+                deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+                    contract_addr: config.clone().pool_address.to_string(),
+                    msg: to_binary(&FanfuryswapQueryMsg::Config {})?,
+                }))?;
+
+            swap_fee = fury_refund_amount
+                * Uint128::from(swap_config_response.platform_fee + swap_config_response.tx_fee)
+                / Uint128::from(THOUSAND);
+        }
+        let expected_fury_response: Token1ForToken2PriceResponse =
             deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
                 contract_addr: config.clone().pool_address.to_string(),
-                msg: to_binary(&FanfuryswapQueryMsg::Config {})?,
+                msg: to_binary(&FanfuryswapQueryMsg::Token1ForToken2Price {
+                    token1_amount: fury_refund_amount,
+                })?,
             }))?;
-
-        swap_fee = total_refund_amount
-            * Uint128::from(swap_config_response.platform_fee + swap_config_response.tx_fee)
-            / Uint128::from(THOUSAND);
+        //let final_amount = ust_asset.amount.clone().add(swap_fee).add(tax);
+        final_amount = fury_refund_amount.add(swap_fee);
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.pool_address.to_string(),
+            msg: to_binary(&FanfuryswapExecuteMsg::Swap {
+                input_token: TokenSelect::Token1,
+                input_amount: fury_refund_amount,
+                min_output: expected_fury_response.token2_amount,
+                fee_amount: swap_fee,
+                expiration: None,
+            })?,
+            funds: vec![Coin {
+                denom: config.usdc_ibc_symbol.clone(),
+                amount: final_amount,
+            }],
+        }));
     }
-    let expected_fury_response: Token1ForToken2PriceResponse =
-        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
-            contract_addr: config.clone().pool_address.to_string(),
-            msg: to_binary(&FanfuryswapQueryMsg::Token1ForToken2Price {
-                token1_amount: total_refund_amount,
+    if !swapped_fury_refund.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: config.fury_token_address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: info.sender.to_string(),
+                amount: swapped_fury_refund,
             })?,
-        }))?;
-    //let final_amount = ust_asset.amount.clone().add(swap_fee).add(tax);
-    let final_amount = total_refund_amount.add(swap_fee);
-    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: config.pool_address.to_string(),
-        msg: to_binary(&FanfuryswapExecuteMsg::Swap {
-            input_token: TokenSelect::Token1,
-            input_amount: total_refund_amount,
-            min_output: expected_fury_response.token2_amount,
-            fee_amount: swap_fee,
-            expiration: None,
-        })?,
-        funds: vec![Coin {
-            denom: config.usdc_ibc_symbol.clone(),
-            amount: final_amount,
-        }],
-    }));
+            funds: vec![],
+        }));
+    }
     let refund = Coin {
         denom: config.usdc_ibc_symbol.clone(),
-        amount: refund_in_ust_fees,
+        amount: refund_in_ust_fees
+            .add(usdc_refund_amount)
+            .add(rake_refund_amount),
     };
     let mut refund_: Vec<Coin> = vec![];
     refund_.push(refund);
@@ -963,20 +1519,21 @@ pub fn claim_refund(
     }));
     return Ok(Response::new()
         .add_attribute("amount", final_amount.to_string())
+        .add_attribute("usdc_amount", usdc_refund_amount.to_string())
+        .add_attribute("swapped_fury_amount", swapped_fury_refund.to_string())
         .add_attribute("action", "refund")
         .add_messages(messages));
 }
 
 pub fn game_pool_reward_distribute(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     info: MessageInfo,
     game_id: String,
     pool_id: String,
     game_winners: Vec<GameResult>,
     is_final_batch: bool,
     testing: bool,
-    ust_for_rake: Uint128,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     if info.sender != config.admin_address {
@@ -984,9 +1541,30 @@ pub fn game_pool_reward_distribute(
             invoker: info.sender.to_string(),
         });
     }
+    ensure_no_results_providers(deps.storage)?;
+    return _distribute_pool_rewards(
+        deps,
+        env,
+        game_id,
+        pool_id,
+        game_winners,
+        is_final_batch,
+        testing,
+    );
+}
+
+fn _distribute_pool_rewards(
+    deps: DepsMut,
+    env: Env,
+    game_id: String,
+    pool_id: String,
+    game_winners: Vec<GameResult>,
+    is_final_batch: bool,
+    testing: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
     let platform_fee_in_percentage = config.platform_fee;
     let platform_fee;
-    let game_id = config.game_id.clone();
 
     let gd = GAME_DETAILS.may_load(deps.storage, game_id.clone())?;
     let game;
@@ -1029,31 +1607,43 @@ pub fn game_pool_reward_distribute(
         deps.storage,
         game_id.clone(),
         &GameDetails {
-            game_id: game_id.clone(),
             game_status: game_status,
+            ..game.clone()
         },
     )?;
 
     let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    if pool_details.game_id != game_id {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Pool does not belong to this game"),
+        }));
+    }
+    if is_pool_abandoned(deps.storage, &pool_details, &env)? {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Result deadline for this game has passed"),
+        }));
+    }
     if pool_details.rewards_distributed == REWARDS_DISTRIBUTED {
         return Err(ContractError::Std(StdError::GenericErr {
             msg: String::from("Rewards are already distributed for this pool"),
         }));
     }
     let pool_count = pool_details.current_teams_count;
-    let pool_type = pool_details.pool_type;
+    let pool_type = pool_details.pool_type.clone();
+    let mut results_finalized_at = pool_details.results_finalized_at;
+    if is_final_batch {
+        results_finalized_at = env.block.time.seconds();
+    }
 
     POOL_DETAILS.save(
         deps.storage,
         pool_id.clone(),
         &PoolDetails {
-            game_id: game_id.clone(),
-            pool_id: pool_id.clone(),
-            pool_type: pool_type.clone(),
-            current_teams_count: pool_details.current_teams_count,
             rewards_distributed: reward_status,
             pool_refund_status: false,
             pool_reward_status: true,
+            results_finalized_at: results_finalized_at,
+            ..pool_details.clone()
         },
     )?;
 
@@ -1078,21 +1668,37 @@ pub fn game_pool_reward_distribute(
 
     let pool_fee: Uint128 = pool_type_details.pool_fee;
 
+    let (distributable_reward, ust_for_rake) =
+        pool_distributable_reward(deps.storage, pool_id.clone(), testing)?;
+
     let mut winner_rewards = Uint128::zero();
+    let mut batch_winners: Vec<(String, String)> = Vec::new();
     let winners = game_winners.clone();
     for winner in winners {
+        let winner_key = (winner.gamer_address.clone(), winner.team_id.clone());
+        if batch_winners.contains(&winner_key) {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Duplicate winner in the batch"),
+            }));
+        }
+        batch_winners.push(winner_key);
         winner_rewards += winner.reward_amount;
     }
+    // Each gamer's teams are updated once even if several of them won
+    let mut winning_gamers: Vec<String> = Vec::new();
+    for (gamer_address, _team_id) in batch_winners {
+        if !winning_gamers.contains(&gamer_address) {
+            winning_gamers.push(gamer_address);
+        }
+    }
 
     let mut wallet_transfer_details: Vec<WalletTransferDetails> = Vec::new();
 
     let mut reward_given_so_far = Uint128::zero();
     let mut all_teams: Vec<PoolTeamDetails> = Vec::new();
-    for winner in game_winners.clone().into_iter() {
-        let ptd = POOL_TEAM_DETAILS.may_load(
-            deps.storage,
-            (&pool_id.clone(), winner.gamer_address.as_ref()),
-        )?;
+    for gamer_address in winning_gamers {
+        let ptd =
+            POOL_TEAM_DETAILS.may_load(deps.storage, (&pool_id.clone(), gamer_address.as_ref()))?;
         match ptd {
             Some(ptd) => {
                 all_teams = ptd;
@@ -1112,6 +1718,11 @@ pub fn game_pool_reward_distribute(
                     && team.team_id == winner.team_id
                     && team.game_id == game_id.clone()
                 {
+                    if !team.reward_amount.is_zero() {
+                        return Err(ContractError::Std(StdError::GenericErr {
+                            msg: String::from("Reward already assigned to this team"),
+                        }));
+                    }
                     updated_team.reward_amount = winner.reward_amount;
                     reward_given_so_far += winner.reward_amount;
                     println!(
@@ -1124,7 +1735,7 @@ pub fn game_pool_reward_distribute(
         }
         POOL_TEAM_DETAILS.save(
             deps.storage,
-            (&pool_id.clone(), winner.gamer_address.as_ref()),
+            (&pool_id.clone(), gamer_address.as_ref()),
             &updated_teams,
         )?;
     }
@@ -1132,29 +1743,32 @@ pub fn game_pool_reward_distribute(
     let reward_total;
     match current_reward {
         Ok(some) => {
-            let total_current = some.add(reward_given_so_far.clone());
-            CURRENT_REWARD_FOR_POOL.save(deps.storage, pool_id.clone(), &total_current)?;
-            reward_total = total_current;
+            reward_total = some.add(reward_given_so_far.clone());
         }
         Err(_) => {
             reward_total = reward_given_so_far;
-            CURRENT_REWARD_FOR_POOL.save(deps.storage, pool_id.clone(), &reward_given_so_far)?;
         }
     }
+    if reward_total > distributable_reward {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Rewards exceed the distributable amount for this pool"),
+        }));
+    }
+    CURRENT_REWARD_FOR_POOL.save(deps.storage, pool_id.clone(), &reward_total)?;
     // let mut swap_info = query_swap_data_for_pool(deps.storage, "1".to_string().clone())?;
     let rsp;
     // Transfer rake_amount to all the rake wallets. Can also be only one rake wallet
     if is_final_batch {
         // Only when we are on the final batch and UST for rake is not zero we perform this
         if !ust_for_rake.is_zero() {
+            // The rake is paid only once even if the results get revoked and resubmitted
+            let mut swap_info = query_swap_data_for_pool(deps.storage, pool_id.clone())?;
+            swap_info.ust_for_rake = Uint128::zero();
+            SWAP_BALANCE_INFO.save(deps.storage, pool_id.clone(), &swap_info)?;
+            let pool_collection = query_pool_collection(deps.storage, pool_id.clone())?;
             for wallet in pool_type_details.rake_list {
+                let proportionate_amount = rake_for_wallet(pool_collection, &wallet);
                 let wallet_address = wallet.wallet_address;
-                let rake_amount = ust_for_rake;
-                let proportionate_amount = rake_amount
-                    .checked_mul(Uint128::from(wallet.percentage))
-                    .unwrap_or_default()
-                    .checked_div(Uint128::from(100u128))
-                    .unwrap_or_default();
                 // Transfer proportionate_amount to the corresponding rake wallet
                 let transfer_detail = WalletTransferDetails {
                     wallet_address: wallet_address.clone(),
@@ -1181,6 +1795,523 @@ pub fn game_pool_reward_distribute(
         .add_attribute("pool_id", pool_id.clone()));
 }
 
+// Part of the pool collection paid to the rake wallet
+fn rake_for_wallet(pool_collection: Uint128, wallet: &WalletPercentage) -> Uint128 {
+    return pool_collection
+        .checked_mul(Uint128::from(wallet.percentage))
+        .unwrap_or_default()
+        .checked_div(Uint128::from(HUNDRED_PERCENT))
+        .unwrap_or_default();
+}
+
+// The rake is whatever part of the pool collection was not swapped to FURY
+// and the winners can only share the FURY that the swap actually returned.
+// Returns the distributable reward in FURY and the UST kept for the rake.
+pub fn pool_distributable_reward(
+    storage: &dyn Storage,
+    pool_id: String,
+    testing: bool,
+) -> Result<(Uint128, Uint128), ContractError> {
+    let sbi = SWAP_BALANCE_INFO.may_load(storage, pool_id.clone())?;
+    match sbi {
+        Some(swap_info) => {
+            let distributable_reward = swap_info
+                .ust_amount_swapped
+                .checked_mul(swap_info.exchange_rate)
+                .unwrap_or_default()
+                .checked_div(Uint128::from(HUNDRED_PERCENT))
+                .unwrap_or_default();
+            return Ok((distributable_reward, swap_info.ust_for_rake));
+        }
+        None => {
+            if !testing {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Pool collection has not been swapped yet"),
+                }));
+            }
+            let distributable_reward = query_pool_collection(storage, pool_id.clone())?;
+            return Ok((distributable_reward, Uint128::zero()));
+        }
+    }
+}
+
+// Prize for a single place (0 is the first place) as per the prize structure
+pub fn prize_for_position(
+    prize_structure: &PrizeStructure,
+    position: usize,
+    team_count: u32,
+    prize_pool: Uint128,
+) -> Uint128 {
+    match prize_structure {
+        PrizeStructure::WinnerTakesAll => {
+            if position == 0 {
+                return prize_pool;
+            }
+            return Uint128::zero();
+        }
+        PrizeStructure::TopN { percentages } => {
+            if position >= percentages.len() {
+                return Uint128::zero();
+            }
+            return prize_pool
+                .checked_mul(Uint128::from(percentages[position]))
+                .unwrap_or_default()
+                .checked_div(Uint128::from(HUNDRED_PERCENT))
+                .unwrap_or_default();
+        }
+        PrizeStructure::FiftyFifty => {
+            let winner_count = (team_count / 2) as usize;
+            if position >= winner_count {
+                return Uint128::zero();
+            }
+            return prize_pool
+                .checked_div(Uint128::from(winner_count as u128))
+                .unwrap_or_default();
+        }
+    }
+}
+
+// The admin only submits the final ranking of the pool. The rewards are
+// computed here from the prize structure of the pool type so that any
+// player can verify the payout. Tied teams share the prizes of the places
+// they occupy equally.
+pub fn game_pool_ranking_submit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    game_id: String,
+    pool_id: String,
+    ranking: Vec<TeamRank>,
+    testing: bool,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    ensure_no_results_providers(deps.storage)?;
+    return _distribute_ranking_rewards(deps, env, game_id, pool_id, ranking, testing);
+}
+
+fn _distribute_ranking_rewards(
+    deps: DepsMut,
+    env: Env,
+    game_id: String,
+    pool_id: String,
+    ranking: Vec<TeamRank>,
+    testing: bool,
+) -> Result<Response, ContractError> {
+    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    let pool_type_details = query_pool_type_details(deps.storage, pool_details.pool_type)?;
+    let (prize_pool, _ust_for_rake) =
+        pool_distributable_reward(deps.storage, pool_id.clone(), testing)?;
+
+    // Find the gamer behind each ranked team
+    let all_teams: Vec<(String, Vec<PoolTeamDetails>)> = POOL_TEAM_DETAILS
+        .prefix(&pool_id)
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|k| k.unwrap())
+        .collect();
+    let mut ranked_teams: Vec<(u64, String, String)> = Vec::new();
+    for team_rank in ranking {
+        if team_rank.rank == 0 {
+            return Err(ContractError::Std(StdError::GenericErr {
+                msg: String::from("Rank must start from 1"),
+            }));
+        }
+        let mut gamer_address = None;
+        for (gamer, teams) in all_teams.iter() {
+            for team in teams {
+                if team.team_id == team_rank.team_id {
+                    gamer_address = Some(gamer.clone());
+                }
+            }
+        }
+        let gamer;
+        match gamer_address {
+            Some(some) => {
+                gamer = some;
+            }
+            None => {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Ranked team not found in the pool"),
+                }));
+            }
+        }
+        for (_rank, team_id, _gamer) in ranked_teams.iter() {
+            if *team_id == team_rank.team_id {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Team is ranked more than once"),
+                }));
+            }
+        }
+        ranked_teams.push((team_rank.rank, team_rank.team_id, gamer));
+    }
+    ranked_teams.sort();
+
+    let mut game_winners: Vec<GameResult> = Vec::new();
+    let mut position = 0usize;
+    while position < ranked_teams.len() {
+        let rank = ranked_teams[position].0;
+        let mut tied_count = 0usize;
+        let mut tied_prize = Uint128::zero();
+        while position + tied_count < ranked_teams.len()
+            && ranked_teams[position + tied_count].0 == rank
+        {
+            tied_prize += prize_for_position(
+                &pool_type_details.prize_structure,
+                position + tied_count,
+                pool_details.current_teams_count,
+                prize_pool,
+            );
+            tied_count += 1;
+        }
+        let reward_amount = tied_prize
+            .checked_div(Uint128::from(tied_count as u128))
+            .unwrap_or_default();
+        for (team_rank, team_id, gamer) in ranked_teams[position..position + tied_count].iter() {
+            let mut updated_teams = Vec::new();
+            let teams = POOL_TEAM_DETAILS.load(deps.storage, (&pool_id.clone(), gamer.as_ref()))?;
+            for team in teams {
+                let mut updated_team = team.clone();
+                if team.team_id == *team_id {
+                    updated_team.team_rank = *team_rank;
+                }
+                updated_teams.push(updated_team);
+            }
+            POOL_TEAM_DETAILS.save(
+                deps.storage,
+                (&pool_id.clone(), gamer.as_ref()),
+                &updated_teams,
+            )?;
+            if !reward_amount.is_zero() {
+                game_winners.push(GameResult {
+                    gamer_address: gamer.clone(),
+                    team_id: team_id.clone(),
+                    reward_amount: reward_amount,
+                });
+            }
+        }
+        position += tied_count;
+    }
+
+    return _distribute_pool_rewards(deps, env, game_id, pool_id, game_winners, true, testing);
+}
+
+// Once results providers are set the admin can no longer report results alone
+fn ensure_no_results_providers(storage: &dyn Storage) -> Result<(), ContractError> {
+    let rp = RESULTS_PROVIDERS.may_load(storage)?;
+    match rp {
+        Some(rp) => {
+            if !rp.providers.is_empty() || !rp.signer_keys.is_empty() {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Results must be reported by the results providers"),
+                }));
+            }
+        }
+        None => {}
+    }
+    return Ok(());
+}
+
+pub fn set_results_providers(
+    deps: DepsMut,
+    info: MessageInfo,
+    providers: Vec<String>,
+    threshold: u32,
+    signer_keys: Vec<ResultsSignerKey>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let mut provider_addresses: Vec<Addr> = Vec::new();
+    for provider in providers {
+        let provider_address = deps.api.addr_validate(&provider)?;
+        if !provider_addresses.contains(&provider_address) {
+            provider_addresses.push(provider_address);
+        }
+    }
+    if !provider_addresses.is_empty()
+        && (threshold == 0 || threshold as usize > provider_addresses.len())
+    {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Threshold must be between 1 and the number of providers"),
+        }));
+    }
+
+    RESULTS_PROVIDERS.save(
+        deps.storage,
+        &ResultsProviders {
+            providers: provider_addresses.clone(),
+            threshold: threshold,
+            signer_keys: signer_keys,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("action", "set_results_providers")
+        .add_attribute("providers", provider_addresses.len().to_string())
+        .add_attribute("threshold", threshold.to_string()));
+}
+
+// The hash of the ranking payload that providers vote on and sign
+pub fn ranking_hash(
+    env: &Env,
+    game_id: String,
+    pool_id: String,
+    results_revision: u32,
+    ranking: Vec<TeamRank>,
+) -> StdResult<Binary> {
+    let payload = to_binary(&RankingPayload {
+        chain_id: env.block.chain_id.clone(),
+        contract_address: env.contract.address.to_string(),
+        game_id: game_id,
+        pool_id: pool_id,
+        results_revision: results_revision,
+        ranking: ranking,
+    })?;
+    let hash = Sha256::digest(payload.as_slice());
+    return Ok(Binary::from(hash.to_vec()));
+}
+
+pub fn submit_ranking_hash(
+    deps: DepsMut,
+    info: MessageInfo,
+    pool_id: String,
+    ranking_hash: Binary,
+) -> Result<Response, ContractError> {
+    let rp = RESULTS_PROVIDERS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    if !rp.providers.contains(&info.sender) {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    if pool_details.rewards_distributed == REWARDS_DISTRIBUTED {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Rewards are already distributed for this pool"),
+        }));
+    }
+    RANKING_HASH_VOTES.save(deps.storage, (&pool_id, &info.sender), &ranking_hash)?;
+    return Ok(Response::new()
+        .add_attribute("action", "submit_ranking_hash")
+        .add_attribute("pool_id", pool_id)
+        .add_attribute("provider", info.sender.to_string()));
+}
+
+// Anyone can finalize the ranking of a pool once enough providers have
+// submitted its hash or it carries a valid signature from a signer key
+pub fn finalize_ranking(
+    deps: DepsMut,
+    env: Env,
+    game_id: String,
+    pool_id: String,
+    ranking: Vec<TeamRank>,
+    signature: Option<Binary>,
+    testing: bool,
+) -> Result<Response, ContractError> {
+    let rp = RESULTS_PROVIDERS
+        .may_load(deps.storage)?
+        .unwrap_or_default();
+    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    let hash = ranking_hash(
+        &env,
+        game_id.clone(),
+        pool_id.clone(),
+        pool_details.results_revision,
+        ranking.clone(),
+    )?;
+
+    let mut finalized = false;
+    match signature {
+        Some(signature) => {
+            for signer_key in rp.signer_keys.iter() {
+                let verified;
+                match signer_key.key_type {
+                    SignerKeyType::Secp256k1 => {
+                        verified = deps
+                            .api
+                            .secp256k1_verify(&hash, &signature, &signer_key.public_key)
+                            .unwrap_or(false);
+                    }
+                    SignerKeyType::Ed25519 => {
+                        verified = deps
+                            .api
+                            .ed25519_verify(&hash, &signature, &signer_key.public_key)
+                            .unwrap_or(false);
+                    }
+                }
+                if verified {
+                    finalized = true;
+                    break;
+                }
+            }
+            if !finalized {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Signature does not match any results signer key"),
+                }));
+            }
+        }
+        None => {
+            let mut matching_votes = 0u32;
+            for provider in rp.providers.iter() {
+                let vote = RANKING_HASH_VOTES.may_load(deps.storage, (&pool_id, provider))?;
+                match vote {
+                    Some(vote) => {
+                        if vote == hash {
+                            matching_votes += 1;
+                        }
+                    }
+                    None => {}
+                }
+            }
+            if rp.threshold == 0 || matching_votes < rp.threshold {
+                return Err(ContractError::Std(StdError::GenericErr {
+                    msg: String::from("Not enough matching ranking hashes to finalize"),
+                }));
+            }
+        }
+    }
+    return _distribute_ranking_rewards(deps, env, game_id, pool_id, ranking, testing);
+}
+
+// Claims for a pool wait until its results can no longer be revoked
+pub fn is_in_dispute_window(
+    storage: &dyn Storage,
+    pool: &PoolDetails,
+    env: &Env,
+) -> StdResult<bool> {
+    let dispute_settings = DISPUTE_SETTINGS.may_load(storage)?.unwrap_or_default();
+    if dispute_settings.challenge_period == 0 {
+        return Ok(false);
+    }
+    if !pool.rewards_distributed {
+        return Ok(true);
+    }
+    let window_end = pool.results_finalized_at + dispute_settings.challenge_period;
+    return Ok(env.block.time.seconds() < window_end);
+}
+
+pub fn set_dispute_settings(
+    deps: DepsMut,
+    info: MessageInfo,
+    challenge_period: u64,
+    arbiter: Option<String>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if info.sender != config.admin_address {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+    let arbiter_address;
+    match arbiter {
+        Some(arbiter) => {
+            arbiter_address = Some(deps.api.addr_validate(&arbiter)?);
+        }
+        None => {
+            arbiter_address = None;
+        }
+    }
+    DISPUTE_SETTINGS.save(
+        deps.storage,
+        &DisputeSettings {
+            challenge_period: challenge_period,
+            arbiter: arbiter_address,
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("action", "set_dispute_settings")
+        .add_attribute("challenge_period", challenge_period.to_string()));
+}
+
+// Wipes the results of a pool so that they can be submitted again
+pub fn revoke_results(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    pool_id: String,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let dispute_settings = DISPUTE_SETTINGS.may_load(deps.storage)?.unwrap_or_default();
+    if info.sender != config.admin_address && Some(info.sender.clone()) != dispute_settings.arbiter
+    {
+        return Err(ContractError::UnauthorizedInvoker {
+            invoker: info.sender.to_string(),
+        });
+    }
+
+    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    if !pool_details.pool_reward_status || pool_details.results_finalized_at == 0 {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("No results to revoke for this pool"),
+        }));
+    }
+    if !is_in_dispute_window(deps.storage, &pool_details, &env)? {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Dispute window for this pool has closed"),
+        }));
+    }
+    // Revoked results could not be resubmitted anymore and the pool would be refunded
+    // after its rake was already paid
+    let game = GAME_DETAILS.load(deps.storage, pool_details.game_id.clone())?;
+    if is_result_deadline_passed(&game, &env) {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Result deadline for this game has passed"),
+        }));
+    }
+
+    let all_gamers: Vec<String> = POOL_TEAM_DETAILS
+        .prefix(&pool_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|k| k.unwrap())
+        .collect();
+    for gamer in all_gamers {
+        let teams = POOL_TEAM_DETAILS.load(deps.storage, (&pool_id.clone(), &gamer.clone()))?;
+        let mut updated_teams: Vec<PoolTeamDetails> = Vec::new();
+        for team in teams {
+            let mut updated_team = team.clone();
+            updated_team.reward_amount = Uint128::from(INITIAL_REWARD_AMOUNT);
+            updated_team.team_rank = INITIAL_TEAM_RANK;
+            updated_teams.push(updated_team);
+        }
+        POOL_TEAM_DETAILS.save(
+            deps.storage,
+            (&pool_id.clone(), &gamer.clone()),
+            &updated_teams,
+        )?;
+    }
+    let voters: Vec<Addr> = RANKING_HASH_VOTES
+        .prefix(&pool_id.clone())
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|k| k.unwrap())
+        .collect();
+    for voter in voters {
+        RANKING_HASH_VOTES.remove(deps.storage, (&pool_id.clone(), &voter));
+    }
+    CURRENT_REWARD_FOR_POOL.remove(deps.storage, pool_id.clone());
+
+    POOL_DETAILS.save(
+        deps.storage,
+        pool_id.clone(),
+        &PoolDetails {
+            rewards_distributed: REWARDS_NOT_DISTRIBUTED,
+            pool_reward_status: false,
+            results_finalized_at: 0,
+            results_revision: pool_details.results_revision + 1,
+            ..pool_details
+        },
+    )?;
+    return Ok(Response::new()
+        .add_attribute("action", "revoke_results")
+        .add_attribute("pool_id", pool_id.clone()));
+}
+
 pub fn _transfer_to_multiple_wallets(
     wallet_details: Vec<WalletTransferDetails>,
     action: String,
@@ -1223,18 +2354,35 @@ pub fn swap(
         });
     }
     let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
-    let pool_type_details = POOL_TYPE_DETAILS.load(deps.storage, pool_details.pool_type.clone())?;
     // This is the total funds we have in the pool as UST
-    let total_collection_in_pool = pool_type_details
-        .pool_fee
-        .checked_mul(Uint128::from(pool_details.current_teams_count))
-        .unwrap_or_default();
-    //  We need the amount to be less else there is no funds left for rake
-    let funds_for_rake;
-    if amount >= total_collection_in_pool {
-        funds_for_rake = Uint128::zero();
-    } else {
-        funds_for_rake = total_collection_in_pool - amount;
+    let total_collection_in_pool = query_pool_collection(deps.storage, pool_id.clone())?;
+    // The rake wallets get their share of the collection, only the rest is swapped
+    let pool_type_details = query_pool_type_details(deps.storage, pool_details.pool_type.clone())?;
+    let mut funds_for_rake = Uint128::zero();
+    for wallet in pool_type_details.rake_list.iter() {
+        funds_for_rake += rake_for_wallet(total_collection_in_pool, wallet);
+    }
+    if amount != total_collection_in_pool - funds_for_rake {
+        return Err(ContractError::Std(StdError::GenericErr {
+            msg: String::from("Swap amount must be the pool collection less the rake"),
+        }));
+    }
+    if pool_details.entry_currency == EntryCurrency::Usdc {
+        // Rewards are paid in USDC so the amount is only set aside at a 1:1 rate
+        SWAP_BALANCE_INFO.save(
+            deps.storage,
+            pool_id.clone(),
+            &SwapBalanceDetails {
+                balance_pre_swap: Uint128::zero(),
+                balance_post_swap: Uint128::zero(),
+                exchange_rate: Uint128::from(HUNDRED_PERCENT),
+                ust_amount_swapped: amount,
+                ust_for_rake: funds_for_rake,
+            },
+        )?;
+        return Ok(Response::new()
+            .add_attribute("pool_id", pool_id.clone())
+            .add_attribute("usdc_for_rewards", amount.to_string()));
     }
     let current_fury_balance: BalanceResponse = deps.querier.query_wasm_smart(
         config.clone().fury_token_address,
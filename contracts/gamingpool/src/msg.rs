@@ -4,7 +4,10 @@ use serde::{Deserialize, Serialize};
 
 use cw20::{Cw20ReceiveMsg, Logo};
 
-use crate::state::{GameResult, SwapBalanceDetails, WalletPercentage};
+use crate::state::{
+    EntryCurrency, GameResult, PrizeStructure, ResultsSignerKey, SwapBalanceDetails, TeamRank,
+    WalletPercentage,
+};
 use crate::ContractError;
 
 #[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
@@ -43,26 +46,75 @@ pub enum ExecuteMsg {
         max_teams_for_pool: u32,
         max_teams_for_gamer: u32,
         wallet_percentages: Vec<WalletPercentage>,
+        prize_structure: Option<PrizeStructure>,
+    },
+    CreateGame {
+        game_id: String,
+        open_until: u64,
+        start_time: u64,
+        result_deadline: u64,
+    },
+    CancelGame {
+        game_id: String,
+    },
+    LockGame {
+        game_id: String,
+    },
+    Lock {
+        game_id: String,
     },
-    CancelGame {},
-    LockGame {},
     CreatePool {
         pool_type: String,
+        game_id: String,
+        entry_currency: Option<EntryCurrency>,
+    },
+    SetPrivatePoolSettings {
+        creation_fee: Uint128,
+        min_entry_fee: Uint128,
+        max_entry_fee: Uint128,
+    },
+    CreatePrivatePool {
+        pool_type: String,
+        game_id: String,
+        invite_code_hash: Binary,
+        entry_fee: Option<Uint128>,
+        entry_currency: Option<EntryCurrency>,
     },
     ClaimReward {
         gamer: String,
+        game_id: Option<String>,
     },
     ClaimRefund {
         gamer: String,
+        game_id: Option<String>,
         max_spread: Option<Decimal>,
     },
     GamePoolRewardDistribute {
         pool_id: String,
         game_winners: Vec<GameResult>,
         is_final_batch: bool,
-        ust_for_rake: Uint128,
         game_id: String,
     },
+    GamePoolRankingSubmit {
+        game_id: String,
+        pool_id: String,
+        ranking: Vec<TeamRank>,
+    },
+    SetResultsProviders {
+        providers: Vec<String>,
+        threshold: u32,
+        signer_keys: Vec<ResultsSignerKey>,
+    },
+    SubmitRankingHash {
+        pool_id: String,
+        ranking_hash: Binary,
+    },
+    FinalizeRanking {
+        game_id: String,
+        pool_id: String,
+        ranking: Vec<TeamRank>,
+        signature: Option<Binary>,
+    },
     GamePoolBidSubmitCommand {
         gamer: String,
         pool_type: String,
@@ -70,6 +122,16 @@ pub enum ExecuteMsg {
         team_id: String,
         amount: Uint128,
         max_spread: Option<Decimal>,
+        invite_code: Option<String>,
+    },
+    GamePoolAutoBidSubmitCommand {
+        gamer: String,
+        pool_type: String,
+        game_id: String,
+        team_id: String,
+        amount: Uint128,
+        max_spread: Option<Decimal>,
+        entry_currency: Option<EntryCurrency>,
     },
     Sweep {
         funds: Vec<Coin>,
@@ -82,6 +144,13 @@ pub enum ExecuteMsg {
     ChangeFeeWallet {
         address: String,
     },
+    SetDisputeSettings {
+        challenge_period: u64,
+        arbiter: Option<String>,
+    },
+    RevokeResults {
+        pool_id: String,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -106,19 +175,25 @@ pub enum QueryMsg {
     },
     QueryRefund {
         gamer: String,
+        game_id: Option<String>,
     },
     QueryGameResult {
         gamer: String,
         pool_id: String,
         team_id: String,
     },
-    GameDetails {},
+    GameDetails {
+        game_id: Option<String>,
+    },
+    AllGames {},
     PoolTeamDetailsWithTeamId {
         pool_id: String,
         team_id: String,
         gamer: String,
     },
-    AllPoolsInGame {},
+    AllPoolsInGame {
+        game_id: Option<String>,
+    },
     PoolCollection {
         pool_id: String,
     },
@@ -134,6 +209,13 @@ pub enum QueryMsg {
         amount: Uint128,
     },
     GetFeeWallet {},
+    ResultsProviders {},
+    PrivatePoolSettings {},
+    RankingHash {
+        game_id: String,
+        pool_id: String,
+        ranking: Vec<TeamRank>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -148,6 +230,38 @@ pub struct GamePoolBidSubmitCommand {
     pub pool_type: String,
     pub pool_id: String,
     pub team_id: String,
+    pub invite_code: Option<String>,
+}
+
+/// The payload that results providers hash and sign
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RankingPayload {
+    /// Binds the payload to one deployment so it can not be replayed elsewhere
+    pub chain_id: String,
+    pub contract_address: String,
+    pub game_id: String,
+    pub pool_id: String,
+    pub results_revision: u32,
+    pub ranking: Vec<TeamRank>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct GameResultResponse {
+    pub gamer_address: String,
+    pub team_id: String,
+    pub reward_amount: Uint128,
+    /// The token the reward is paid in
+    pub entry_currency: EntryCurrency,
+    /// Time in seconds at which the results of the pool were final, 0 until then
+    pub results_finalized_at: u64,
+    /// Time in seconds from which the reward can be claimed, 0 until the results are final
+    pub claimable_from: u64,
+}
+
+/// Set as the response data of an automatic bid
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+pub struct PoolAllocationResponse {
+    pub pool_id: String,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
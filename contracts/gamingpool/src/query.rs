@@ -1,15 +1,36 @@
-use cosmwasm_std::{Deps, Order, StdError, StdResult, Storage, Uint128};
+use cosmwasm_std::{Deps, Env, Order, StdError, StdResult, Storage, Uint128};
 
 use crate::contract::{
     DUMMY_WALLET, INITIAL_TEAM_POINTS, INITIAL_TEAM_RANK, UNCLAIMED_REFUND, UNCLAIMED_REWARD,
 };
-use crate::execute::query_platform_fees;
+use crate::execute::{is_pool_abandoned, query_platform_fees};
+use crate::msg::GameResultResponse;
 use crate::state::{
-    FeeDetails, GameDetails, GameResult, PoolDetails, PoolTeamDetails, PoolTypeDetails,
-    SwapBalanceDetails, CONFIG, FEE_WALLET, GAME_DETAILS, GAME_RESULT_DUMMY, POOL_DETAILS,
-    POOL_TEAM_DETAILS, POOL_TYPE_DETAILS, SWAP_BALANCE_INFO,
+    FeeDetails, GameDetails, PoolDetails, PoolTeamDetails, PoolTypeDetails, PrivatePoolSettings,
+    ResultsProviders, SwapBalanceDetails, CONFIG, DISPUTE_SETTINGS, FEE_WALLET, GAME_DETAILS,
+    GAME_POOLS, GAME_RESULT_DUMMY, POOL_DETAILS, POOL_TEAM_DETAILS, POOL_TYPE_DETAILS,
+    PRIVATE_POOL_SETTINGS, RESULTS_PROVIDERS, SWAP_BALANCE_INFO,
 };
 
+// Queries and claims without a game id fall back to the game the contract was instantiated with
+pub fn default_game_id(storage: &dyn Storage, game_id: Option<String>) -> StdResult<String> {
+    match game_id {
+        Some(game_id) => return Ok(game_id),
+        None => {
+            let config = CONFIG.load(storage)?;
+            return Ok(config.game_id);
+        }
+    }
+}
+
+/// Ids of the pools created for the game
+pub fn game_pool_ids(storage: &dyn Storage, game_id: &str) -> StdResult<Vec<String>> {
+    GAME_POOLS
+        .prefix(game_id)
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<_>>>()
+}
+
 pub fn query_get_fee_wallet(deps: Deps) -> StdResult<String> {
     let address = FEE_WALLET.load(deps.storage)?;
     return Ok(address);
@@ -110,13 +131,16 @@ pub fn query_reward(storage: &dyn Storage, gamer: String) -> StdResult<Uint128>
     return Ok(user_reward);
 }
 
-pub fn query_refund(storage: &dyn Storage, gamer: String) -> StdResult<Uint128> {
+pub fn query_refund(
+    storage: &dyn Storage,
+    env: Env,
+    gamer: String,
+    game_id: Option<String>,
+) -> StdResult<Uint128> {
+    let game_id = default_game_id(storage, game_id)?;
     let mut user_refund = Uint128::zero();
-    // Get all pools
-    let all_pools: Vec<String> = POOL_DETAILS
-        .keys(storage, None, None, Order::Ascending)
-        .map(|k| k.unwrap())
-        .collect();
+    // Get all pools of the game
+    let all_pools = game_pool_ids(storage, &game_id)?;
     for pool_id in all_pools {
         let mut pool_details: PoolDetails = Default::default();
         let pd = POOL_DETAILS.load(storage, pool_id.clone());
@@ -128,10 +152,10 @@ pub fn query_refund(storage: &dyn Storage, gamer: String) -> StdResult<Uint128>
                 continue;
             }
         }
-        if !pool_details.pool_refund_status {
+        if !pool_details.pool_refund_status && !is_pool_abandoned(storage, &pool_details, &env)? {
             continue;
         }
-        let ptd = POOL_TYPE_DETAILS.load(storage, pool_details.pool_type)?;
+        let refund_amount = pool_refund_amount(storage, &pool_details)?;
         let mut teams = Vec::new();
         let all_teams = POOL_TEAM_DETAILS.may_load(storage, (&*pool_id.clone(), gamer.as_ref()))?;
         match all_teams {
@@ -141,8 +165,11 @@ pub fn query_refund(storage: &dyn Storage, gamer: String) -> StdResult<Uint128>
             None => {}
         }
         for team in teams {
-            if gamer == team.gamer_address && team.claimed_refund == UNCLAIMED_REFUND {
-                user_refund += ptd.pool_fee;
+            if gamer == team.gamer_address
+                && team.claimed_refund == UNCLAIMED_REFUND
+                && team.claimed_reward == UNCLAIMED_REWARD
+            {
+                user_refund += refund_amount;
             }
         }
     }
@@ -154,9 +181,9 @@ pub fn query_game_result(
     gamer: String,
     pool_id: String,
     team_id: String,
-) -> StdResult<GameResult> {
-    let config = CONFIG.load(deps.storage)?;
-    let game_id = config.game_id;
+) -> StdResult<GameResultResponse> {
+    let pool_details = query_pool_details(deps.storage, pool_id.clone())?;
+    let game_id = pool_details.game_id.clone();
 
     let mut reward_amount = Uint128::zero();
     let mut refund_amount = Uint128::zero();
@@ -203,7 +230,20 @@ pub fn query_game_result(
     game_result.gamer_address = gamer.clone();
     game_result.team_id = team_id.clone();
     game_result.reward_amount = reward_amount;
-    return Ok(game_result);
+
+    let mut claimable_from = 0u64;
+    if pool_details.results_finalized_at != 0 {
+        let dispute_settings = DISPUTE_SETTINGS.may_load(deps.storage)?.unwrap_or_default();
+        claimable_from = pool_details.results_finalized_at + dispute_settings.challenge_period;
+    }
+    return Ok(GameResultResponse {
+        gamer_address: game_result.gamer_address,
+        team_id: game_result.team_id,
+        reward_amount: game_result.reward_amount,
+        entry_currency: pool_details.entry_currency,
+        results_finalized_at: pool_details.results_finalized_at,
+        claimable_from: claimable_from,
+    });
 }
 
 pub fn query_pool_details(storage: &dyn Storage, pool_id: String) -> StdResult<PoolDetails> {
@@ -241,9 +281,11 @@ pub fn get_team_count_for_user_in_pool_type(
     return Ok(count);
 }
 
-pub fn query_game_details(storage: &dyn Storage) -> StdResult<GameDetails> {
-    let config = CONFIG.load(storage)?;
-    let game_id = config.game_id;
+pub fn query_game_details(
+    storage: &dyn Storage,
+    game_id: Option<String>,
+) -> StdResult<GameDetails> {
+    let game_id = default_game_id(storage, game_id)?;
 
     let game_detail = GAME_DETAILS.may_load(storage, game_id)?;
     match game_detail {
@@ -267,20 +309,30 @@ pub fn query_team_details(
     return Err(StdError::generic_err("Pool Team Details not found"));
 }
 
-pub fn query_all_pools_in_game(storage: &dyn Storage) -> StdResult<Vec<PoolDetails>> {
-    let config = CONFIG.load(storage)?;
-    let game_id = config.game_id;
-
-    let mut all_pool_details = Vec::new();
-    let all_pools: Vec<String> = POOL_DETAILS
+pub fn query_all_games(storage: &dyn Storage) -> StdResult<Vec<GameDetails>> {
+    let mut all_games = Vec::new();
+    let all_game_ids: Vec<String> = GAME_DETAILS
         .keys(storage, None, None, Order::Ascending)
         .map(|k| k.unwrap())
         .collect();
+    for game_id in all_game_ids {
+        let game = GAME_DETAILS.load(storage, game_id)?;
+        all_games.push(game);
+    }
+    return Ok(all_games);
+}
+
+pub fn query_all_pools_in_game(
+    storage: &dyn Storage,
+    game_id: Option<String>,
+) -> StdResult<Vec<PoolDetails>> {
+    let game_id = default_game_id(storage, game_id)?;
+
+    let mut all_pool_details = Vec::new();
+    let all_pools = game_pool_ids(storage, &game_id)?;
     for pool_name in all_pools {
         let pool_details = POOL_DETAILS.load(storage, pool_name)?;
-        if pool_details.game_id == game_id {
-            all_pool_details.push(pool_details);
-        }
+        all_pool_details.push(pool_details);
     }
     return Ok(all_pool_details);
 }
@@ -293,17 +345,7 @@ pub fn query_pool_collection(storage: &dyn Storage, pool_id: String) -> StdResul
         None => return Err(StdError::generic_err("No pool details found")),
     };
 
-    let ptd = POOL_TYPE_DETAILS.may_load(storage, pool.pool_type.clone())?;
-    let pool_type;
-    match ptd {
-        Some(ptd) => {
-            pool_type = ptd;
-        }
-        None => return Err(StdError::generic_err("No pool type details found")),
-    };
-
-    let pool_collection = pool_type
-        .pool_fee
+    let pool_collection = pool_entry_fee(storage, &pool)?
         .checked_mul(Uint128::from(pool.current_teams_count))
         .unwrap_or_default();
     return Ok(pool_collection);
@@ -316,3 +358,45 @@ pub fn query_swap_data_for_pool(
     let info = SWAP_BALANCE_INFO.load(storage, pool_id)?;
     return Ok(info);
 }
+
+// Entry fee refunded per team, less its share of the rake already paid out of the pool
+pub fn pool_refund_amount(storage: &dyn Storage, pool: &PoolDetails) -> StdResult<Uint128> {
+    let entry_fee = pool_entry_fee(storage, pool)?;
+    let pool_collection = entry_fee
+        .checked_mul(Uint128::from(pool.current_teams_count))
+        .unwrap_or_default();
+    match SWAP_BALANCE_INFO.may_load(storage, pool.pool_id.clone())? {
+        Some(swap) if !swap.exchange_rate.is_zero() && !pool_collection.is_zero() => {
+            let held_amount = swap.ust_amount_swapped + swap.ust_for_rake;
+            return Ok(entry_fee.multiply_ratio(held_amount, pool_collection));
+        }
+        _ => return Ok(entry_fee),
+    }
+}
+
+// Private pools can have their own entry fee, all other pools use the pool type fee
+pub fn pool_entry_fee(storage: &dyn Storage, pool: &PoolDetails) -> StdResult<Uint128> {
+    match pool.entry_fee {
+        Some(entry_fee) => {
+            return Ok(entry_fee);
+        }
+        None => {}
+    }
+    let ptd = POOL_TYPE_DETAILS.may_load(storage, pool.pool_type.clone())?;
+    match ptd {
+        Some(ptd) => {
+            return Ok(ptd.pool_fee);
+        }
+        None => return Err(StdError::generic_err("No pool type details found")),
+    }
+}
+
+pub fn query_private_pool_settings(storage: &dyn Storage) -> StdResult<PrivatePoolSettings> {
+    let private_pool_settings = PRIVATE_POOL_SETTINGS.may_load(storage)?;
+    return Ok(private_pool_settings.unwrap_or_default());
+}
+
+pub fn query_results_providers(storage: &dyn Storage) -> StdResult<ResultsProviders> {
+    let results_providers = RESULTS_PROVIDERS.may_load(storage)?;
+    return Ok(results_providers.unwrap_or_default());
+}
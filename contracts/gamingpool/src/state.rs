@@ -1,4 +1,4 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Binary, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -31,6 +31,20 @@ pub struct GameDetails {
 
     /// Current status of the game - open, close, canceled
     pub game_status: u64,
+
+    /// Time in seconds after which no more bids are accepted,
+    /// 0 when the game has no deadline and is only locked by the admin
+    #[serde(default)]
+    pub open_until: u64,
+
+    /// Time in seconds at which the game starts
+    #[serde(default)]
+    pub start_time: u64,
+
+    /// Time in seconds by which the results must be distributed, after which
+    /// the gamers of pools without results can claim a refund. 0 when not set
+    #[serde(default)]
+    pub result_deadline: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
@@ -49,6 +63,44 @@ pub struct WalletPercentage {
     pub percentage: u32,
 }
 
+/// How the prize of a pool is split across the final ranking
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum PrizeStructure {
+    /// The whole prize goes to the first placed team
+    WinnerTakesAll,
+
+    /// Percentage of the prize for each place starting from the first,
+    /// in the same units as HUNDRED_PERCENT
+    TopN { percentages: Vec<u32> },
+
+    /// The top half of the teams share the prize equally
+    FiftyFifty,
+}
+
+impl Default for PrizeStructure {
+    fn default() -> Self {
+        PrizeStructure::WinnerTakesAll
+    }
+}
+
+/// The token a pool takes its entry fee in. Rewards and refunds are paid in the same token
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum EntryCurrency {
+    /// FURY sent with TransferFrom and swapped to USDC when the bid is placed
+    Fury,
+
+    /// Native USDC kept as is
+    Usdc,
+}
+
+impl Default for EntryCurrency {
+    fn default() -> Self {
+        EntryCurrency::Fury
+    }
+}
+
 /// This is used for saving various vesting details
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 #[serde(rename_all = "snake_case")]
@@ -72,6 +124,10 @@ pub struct PoolTypeDetails {
 
     /// Rake distribution
     pub rake_list: Vec<WalletPercentage>,
+
+    /// How the prize is split across the final ranking
+    #[serde(default)]
+    pub prize_structure: PrizeStructure,
 }
 
 /// This is used for saving various vesting details
@@ -96,6 +152,30 @@ pub struct PoolDetails {
     pub pool_refund_status: bool,
 
     pub pool_reward_status: bool,
+
+    /// Time in seconds at which the final batch of results was posted, 0 until then
+    #[serde(default)]
+    pub results_finalized_at: u64,
+
+    /// The gamer who created this private pool, None for pools created by the admin
+    #[serde(default)]
+    pub creator: Option<Addr>,
+
+    /// Sha256 hash of the invite code needed to bid in this private pool
+    #[serde(default)]
+    pub invite_code_hash: Option<Binary>,
+
+    /// Entry fee chosen by the creator, the pool type fee applies when not set
+    #[serde(default)]
+    pub entry_fee: Option<Uint128>,
+
+    /// The token gamers pay the entry fee in
+    #[serde(default)]
+    pub entry_currency: EntryCurrency,
+
+    /// Bumped every time the results are revoked so old rankings can not be finalized again
+    #[serde(default)]
+    pub results_revision: u32,
 }
 
 /// This is used for saving various vesting details
@@ -145,6 +225,15 @@ pub struct GameResult {
     pub reward_amount: Uint128,
 }
 
+/// This is used for submitting the final ranking of a team in a pool
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct TeamRank {
+    pub team_id: String,
+    /// 1 is the best rank, tied teams share the same rank
+    pub rank: u64,
+}
+
 /// This is used for transferring tokens to multiple wallets
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
 #[serde(rename_all = "snake_case")]
@@ -172,6 +261,14 @@ pub const POOL_DETAILS: Map<String, PoolDetails> = Map::new("pool_details");
 pub const POOL_TEAM_DETAILS: Map<(&str, &str), Vec<PoolTeamDetails>> =
     Map::new("pool_team_details");
 
+/// Pools of a game, the key is (game id, pool id)
+pub const GAME_POOLS: Map<(&str, &str), ()> = Map::new("pools_by_game");
+
+/// Public pools that still have room for teams, used to allocate pools for auto bids.
+/// The key is (game id, pool type, pool id) and the value is the entry currency of the pool
+pub const OPEN_POOLS: Map<(&str, &str, u128), EntryCurrency> =
+    Map::new("open_pools_by_game_and_type");
+
 pub const CONTRACT_POOL_COUNT: Map<&Addr, Uint128> = Map::new("contract_pool_count");
 
 pub const GAME_RESULT_DUMMY: Map<&Addr, GameResult> = Map::new("game_result");
@@ -194,5 +291,68 @@ pub struct SwapBalanceDetails {
 // This is a simple store we use to save the balance of the contact
 // pre swap and use it to compute the amount of CW20 tokens gained
 // In the swap
-pub const SWAP_BALANCE_INFO: Map<String, SwapBalanceDetails> = Map::new("current_reward_for_pool");
+pub const SWAP_BALANCE_INFO: Map<String, SwapBalanceDetails> = Map::new("swap_balance_info");
+// Swap details used to share the namespace of CURRENT_REWARD_FOR_POOL,
+// they are only read by migrate to move them into SWAP_BALANCE_INFO
+pub const LEGACY_SWAP_BALANCE_INFO: Map<String, SwapBalanceDetails> =
+    Map::new("current_reward_for_pool");
 pub const FEE_WALLET: Item<String> = Item::new("config_new_fee_wallet");
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum SignerKeyType {
+    Secp256k1,
+    Ed25519,
+}
+
+/// A key whose signature over the ranking hash finalizes the results on its own
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug)]
+#[serde(rename_all = "snake_case")]
+pub struct ResultsSignerKey {
+    pub key_type: SignerKeyType,
+    pub public_key: Binary,
+}
+
+/// The parties trusted to report the results of the pools
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct ResultsProviders {
+    /// Addresses that can submit ranking hashes
+    pub providers: Vec<Addr>,
+
+    /// Number of matching ranking hashes needed to finalize the results
+    pub threshold: u32,
+
+    /// Keys whose signature over the ranking hash finalizes the results
+    pub signer_keys: Vec<ResultsSignerKey>,
+}
+
+pub const RESULTS_PROVIDERS: Item<ResultsProviders> = Item::new("results_providers");
+
+/// Ranking hash submitted by each results provider. The key is (pool id, provider)
+pub const RANKING_HASH_VOTES: Map<(&str, &Addr), Binary> = Map::new("ranking_hash_votes");
+
+/// Results can be disputed for challenge_period seconds after they are final
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct DisputeSettings {
+    pub challenge_period: u64,
+
+    /// Can revoke results alongside the admin
+    pub arbiter: Option<Addr>,
+}
+
+pub const DISPUTE_SETTINGS: Item<DisputeSettings> = Item::new("dispute_settings");
+
+/// Lets gamers create private pools. The creation fee is paid in native USDC
+#[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema, Debug, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct PrivatePoolSettings {
+    pub creation_fee: Uint128,
+
+    /// Bounds for the custom entry fee of a private pool
+    pub min_entry_fee: Uint128,
+    pub max_entry_fee: Uint128,
+}
+
+pub const PRIVATE_POOL_SETTINGS: Item<PrivatePoolSettings> = Item::new("private_pool_settings");
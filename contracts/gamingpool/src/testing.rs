@@ -4,24 +4,34 @@
 mod tests {
     use cosmwasm_std::testing::{mock_dependencies, mock_env, mock_info};
     use cosmwasm_std::Addr;
-    use cosmwasm_std::{coin, Uint128};
+    use cosmwasm_std::Binary;
+    use cosmwasm_std::{coin, BankMsg, CosmosMsg, StdError, Uint128};
+    use sha2::{Digest, Sha256};
 
     use crate::contract::{
-        instantiate, CLAIMED_REWARD, GAME_CANCELLED, GAME_COMPLETED, GAME_POOL_OPEN,
-        INITIAL_REFUND_AMOUNT,
+        instantiate, migrate, CLAIMED_REWARD, GAME_CANCELLED, GAME_COMPLETED, GAME_POOL_CLOSED,
+        GAME_POOL_OPEN, INITIAL_REFUND_AMOUNT,
     };
+    use crate::error::ContractError;
     use crate::execute::{
-        cancel_game, claim_refund, claim_reward, create_pool, game_pool_bid_submit,
-        game_pool_reward_distribute, lock_game, save_team_details, set_platform_fee_wallets,
-        set_pool_type_params,
+        allocate_pool, cancel_game, change_fee_wallet, claim_refund, claim_reward, create_game,
+        create_pool, create_private_pool, finalize_ranking, game_pool_auto_bid_submit,
+        game_pool_bid_submit, game_pool_ranking_submit, game_pool_reward_distribute,
+        is_in_dispute_window, is_pool_abandoned, lock, lock_game, ranking_hash, revoke_results,
+        save_team_details, set_dispute_settings, set_platform_fee_wallets, set_pool_type_params,
+        set_private_pool_settings, set_results_providers, submit_ranking_hash, swap,
     };
-    use crate::msg::InstantiateMsg;
+    use crate::msg::{InstantiateMsg, MigrateMsg};
     use crate::query::{
-        get_team_count_for_user_in_pool_type, query_game_details, query_pool_details,
-        query_team_details,
+        get_team_count_for_user_in_pool_type, query_all_games, query_all_pools_in_game,
+        query_game_details, query_game_result, query_pool_collection, query_pool_details,
+        query_refund, query_team_details,
     };
     use crate::state::{
-        GameResult, WalletPercentage, PLATFORM_WALLET_PERCENTAGES, POOL_TEAM_DETAILS,
+        EntryCurrency, GameResult, PrizeStructure, ResultsSignerKey, SignerKeyType,
+        SwapBalanceDetails, TeamRank, WalletPercentage, CURRENT_REWARD_FOR_POOL,
+        LEGACY_SWAP_BALANCE_INFO, OPEN_POOLS, PLATFORM_WALLET_PERCENTAGES, POOL_TEAM_DETAILS,
+        SWAP_BALANCE_INFO,
     };
 
     #[test]
@@ -48,7 +58,7 @@ mod tests {
             instantiate_msg,
         );
 
-        let queryRes = query_game_details(&mut deps.storage);
+        let queryRes = query_game_details(&mut deps.storage, None);
         match queryRes {
             Ok(gameDetail) => {
                 assert_eq!(gameDetail.game_id, "Game001".to_string());
@@ -91,6 +101,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         let mut poolId = String::new();
 
@@ -147,6 +159,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         let mut poolId = String::new();
 
@@ -264,6 +278,7 @@ mod tests {
             10,
             2,
             rake_list,
+            PrizeStructure::WinnerTakesAll,
         );
 
         let rsp = create_pool(
@@ -271,6 +286,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         let mut poolId = String::new();
 
@@ -296,6 +313,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         let queryRes = query_pool_details(&mut deps.storage, "1".to_string());
         match queryRes {
@@ -367,6 +385,7 @@ mod tests {
             1,
             1,
             rake_list,
+            PrizeStructure::WinnerTakesAll,
         );
 
         let rsp = create_pool(
@@ -374,6 +393,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         let mut poolId = String::new();
 
@@ -399,6 +420,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -411,6 +433,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         let queryRes = query_pool_details(&mut deps.storage, "2".to_string());
         match queryRes {
@@ -483,6 +506,7 @@ mod tests {
             10,
             10,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
         set_pool_type_params(
             deps.as_mut(),
@@ -494,6 +518,7 @@ mod tests {
             10,
             10,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -503,6 +528,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -520,6 +547,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "multiple".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_2 {
             Ok(rsp_2) => {
@@ -538,6 +567,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_3 {
             Ok(rsp_3) => {
@@ -564,6 +595,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -576,6 +608,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -588,6 +621,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -600,6 +634,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -625,6 +660,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -637,6 +673,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -649,6 +686,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_2 = query_pool_details(&mut deps.storage, pool_id_2.to_string());
@@ -674,8 +712,10 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
-        game_pool_bid_submit(
+        // A bid naming another pool type is rejected
+        let rsp = game_pool_bid_submit(
             deps.as_mut(),
             mock_env(),
             ownerXInfo.clone(),
@@ -686,11 +726,17 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Pool type does not match the pool".to_string()
         );
         let query_pool_details_3 = query_pool_details(&mut deps.storage, pool_id_3.to_string());
         match query_pool_details_3 {
             Ok(pool_detail_3) => {
-                assert_eq!(pool_detail_3.current_teams_count, 2u32);
+                assert_eq!(pool_detail_3.current_teams_count, 1u32);
+                assert_eq!(pool_detail_3.pool_type, "oneToOne".to_string());
             }
             Err(e) => {
                 println!("error parsing header: {:?}", e);
@@ -756,6 +802,7 @@ mod tests {
             10,
             2,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -765,6 +812,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -789,6 +838,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -801,6 +851,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -813,6 +864,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -885,6 +937,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -894,6 +947,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -918,6 +973,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -930,6 +986,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -942,6 +999,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -976,7 +1034,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -996,12 +1059,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -1012,7 +1074,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1087,6 +1149,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -1096,6 +1159,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -1120,10 +1185,16 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let cancelInfo = mock_info("cancelInfo", &[]);
-        let cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let cancel_rsp = cancel_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
 
         /*
                 23 Mar 2022, commenting this out because call to proxy cannot be made
@@ -1202,6 +1273,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -1211,6 +1283,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -1235,6 +1309,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1247,6 +1322,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1259,6 +1335,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1293,7 +1370,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1310,8 +1392,12 @@ mod tests {
         }
 
         let cancelInfo = mock_info("cancelInfo", &[]);
-        let game_pool_reward_distribute_rsp =
-            cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let game_pool_reward_distribute_rsp = cancel_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
 
         match game_pool_reward_distribute_rsp {
             Ok(game_pool_reward_distribute_rsp) => {}
@@ -1321,7 +1407,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_CANCELLED);
@@ -1397,6 +1483,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -1406,6 +1493,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -1449,6 +1538,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1461,6 +1551,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1473,6 +1564,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1507,7 +1599,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1527,12 +1624,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -1543,7 +1639,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1583,7 +1679,7 @@ mod tests {
                 }
         */
 
-        query_game_status_res = query_game_details(&mut deps.storage);
+        query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1666,6 +1762,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -1675,6 +1772,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -1699,6 +1798,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1711,6 +1811,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1723,6 +1824,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -1757,7 +1859,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -1777,12 +1884,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -1793,7 +1899,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1832,7 +1938,7 @@ mod tests {
                     }
                 }
         */
-        query_game_status_res = query_game_details(&mut deps.storage);
+        query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -1935,6 +2041,7 @@ mod tests {
             20,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -1944,6 +2051,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -1981,6 +2090,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -1993,6 +2103,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2005,6 +2116,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2039,7 +2151,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2128,6 +2245,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -2137,6 +2255,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -2162,6 +2282,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2174,6 +2295,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2186,6 +2308,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2223,7 +2346,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2243,12 +2371,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -2259,7 +2386,7 @@ mod tests {
             }
         }
 
-        let mut query_game_status_res = query_game_details(&mut deps.storage);
+        let mut query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -2270,7 +2397,12 @@ mod tests {
             }
         }
 
-        let game_cancel_rsp = cancel_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let game_cancel_rsp = cancel_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
 
         match game_cancel_rsp {
             Ok(game_cancel_rsp) => {
@@ -2343,6 +2475,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -2352,6 +2485,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -2387,12 +2522,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results.clone(),
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -2423,6 +2557,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2435,6 +2570,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2447,6 +2583,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2465,12 +2602,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -2545,6 +2681,7 @@ mod tests {
             10,
             5,
             rake_list.clone(),
+            PrizeStructure::WinnerTakesAll,
         );
 
         // create multiple pool
@@ -2554,6 +2691,8 @@ mod tests {
             mock_env(),
             adminInfo.clone(),
             "oneToTwo".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
         );
         match rsp_1 {
             Ok(rsp_1) => {
@@ -2591,6 +2730,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2603,6 +2743,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
         game_pool_bid_submit(
             deps.as_mut(),
@@ -2615,6 +2756,7 @@ mod tests {
             Uint128::from(144262u128) + platform_fee,
             true,
             None,
+            None,
         );
 
         let query_pool_details_1 = query_pool_details(&mut deps.storage, pool_id_1.to_string());
@@ -2649,7 +2791,12 @@ mod tests {
         game_results.push(game_result_2);
         game_results.push(game_result_3);
 
-        let lock_game_rsp = lock_game(deps.as_mut(), mock_env(), adminInfo.clone());
+        let lock_game_rsp = lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        );
         match lock_game_rsp {
             Ok(lock_game_rsp) => {
                 //Since max allowed team for gamer under this pooltype is 2 so it will not allow 3rd team creation under this pooltype.
@@ -2669,12 +2816,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results.clone(),
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp {
@@ -2685,7 +2831,7 @@ mod tests {
             }
         }
 
-        let query_game_status_res = query_game_details(&mut deps.storage);
+        let query_game_status_res = query_game_details(&mut deps.storage, None);
         match query_game_status_res {
             Ok(query_game_status_res) => {
                 assert_eq!(query_game_status_res.game_status, GAME_COMPLETED);
@@ -2707,12 +2853,11 @@ mod tests {
             deps.as_mut(),
             mock_env(),
             adminInfo.clone(),
-            "GAME0001".to_string(),
+            "Game001".to_string(),
             pool_id_1.to_string(),
             game_results,
             true,
             true,
-            Uint128::zero(),
         );
 
         match game_pool_reward_distribute_rsp_2 {
@@ -2779,4 +2924,2262 @@ mod tests {
             assert_eq!(wallet.wallet_name, "rake_3".to_string());
         }
     }
+
+    #[test]
+    fn test_multiple_games_in_one_contract() {
+        let mut deps = mock_dependencies();
+        let platform_fee = Uint128::from(300000u128);
+        let transaction_fee = Uint128::from(100000u128);
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: platform_fee,
+            transaction_fee: transaction_fee,
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+
+        let open_until = mock_env().block.time.seconds() + 1000u64;
+        let start_time = mock_env().block.time.seconds() + 2000u64;
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            start_time,
+            start_time + 1000u64,
+        )
+        .unwrap();
+        let duplicate_rsp = create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            start_time,
+            start_time + 1000u64,
+        );
+        assert_eq!(
+            duplicate_rsp.unwrap_err().to_string(),
+            "Generic error: Game already exists".to_string()
+        );
+
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            0,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp_1 = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
+        )
+        .unwrap();
+        let pool_id_1 = rsp_1.attributes[0].value.clone();
+        let rsp_2 = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game002".to_string(),
+            EntryCurrency::Fury,
+        )
+        .unwrap();
+        let pool_id_2 = rsp_2.attributes[0].value.clone();
+
+        let pools_in_game_2 =
+            query_all_pools_in_game(&mut deps.storage, Some("Game002".to_string())).unwrap();
+        assert_eq!(pools_in_game_2.len(), 1);
+        assert_eq!(pools_in_game_2[0].pool_id, pool_id_2);
+        let pools_in_game_1 = query_all_pools_in_game(&mut deps.storage, None).unwrap();
+        assert_eq!(pools_in_game_1.len(), 1);
+        assert_eq!(pools_in_game_1[0].pool_id, pool_id_1);
+        assert_eq!(query_all_games(&mut deps.storage).unwrap().len(), 2);
+
+        // Locking one game leaves the other open for bidding
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+        )
+        .unwrap();
+        let game_1 = query_game_details(&mut deps.storage, None).unwrap();
+        assert_eq!(game_1.game_status, GAME_POOL_OPEN);
+        let game_2 = query_game_details(&mut deps.storage, Some("Game002".to_string())).unwrap();
+        assert_eq!(game_2.game_status, GAME_POOL_CLOSED);
+        assert_eq!(game_2.open_until, open_until);
+        assert_eq!(game_2.start_time, start_time);
+
+        // Pools can only be settled against the game they belong to
+        let wrong_game_rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            pool_id_1.clone(),
+            vec![],
+            true,
+            true,
+        );
+        assert_eq!(
+            wrong_game_rsp.unwrap_err().to_string(),
+            "Generic error: Pool does not belong to this game".to_string()
+        );
+
+        // Cancelling one game only marks its own pools for refund
+        cancel_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+        let pool_1 = query_pool_details(&mut deps.storage, pool_id_1).unwrap();
+        assert_eq!(pool_1.pool_refund_status, true);
+        let pool_2 = query_pool_details(&mut deps.storage, pool_id_2).unwrap();
+        assert_eq!(pool_2.pool_refund_status, false);
+    }
+
+    #[test]
+    fn test_reward_distribute_payout_invariants() {
+        let mut deps = mock_dependencies();
+        let platform_fee = Uint128::from(300000u128);
+        let transaction_fee = Uint128::from(100000u128);
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: platform_fee,
+            transaction_fee: transaction_fee,
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(144262u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+
+        let gamerInfo = mock_info("gamer002", &[]);
+        for team_id in ["Team001", "Team002"] {
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                gamerInfo.clone(),
+                "gamer002".to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                team_id.to_string(),
+                Uint128::from(144262u128),
+                true,
+                None,
+                None,
+            );
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+
+        let duplicate_in_batch_rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![
+                GameResult {
+                    gamer_address: "gamer002".to_string(),
+                    team_id: "Team001".to_string(),
+                    reward_amount: Uint128::from(100u128),
+                },
+                GameResult {
+                    gamer_address: "gamer002".to_string(),
+                    team_id: "Team001".to_string(),
+                    reward_amount: Uint128::from(100u128),
+                },
+            ],
+            false,
+            true,
+        );
+        assert_eq!(
+            duplicate_in_batch_rsp.unwrap_err().to_string(),
+            "Generic error: Duplicate winner in the batch".to_string()
+        );
+
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer002".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(100u128),
+            }],
+            false,
+            true,
+        )
+        .unwrap();
+
+        // The same team cannot be paid again in a later batch
+        let duplicate_across_batches_rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer002".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(100u128),
+            }],
+            false,
+            true,
+        );
+        assert_eq!(
+            duplicate_across_batches_rsp.unwrap_err().to_string(),
+            "Generic error: Reward already assigned to this team".to_string()
+        );
+
+        // Pool collection is 2 * 144262, of which 100 is already assigned
+        let overpaid_rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer002".to_string(),
+                team_id: "Team002".to_string(),
+                reward_amount: Uint128::from(288425u128),
+            }],
+            true,
+            true,
+        );
+        assert_eq!(
+            overpaid_rsp.unwrap_err().to_string(),
+            "Generic error: Rewards exceed the distributable amount for this pool".to_string()
+        );
+    }
+
+    #[test]
+    fn test_ranking_submit_computes_rewards_from_prize_structure() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300000u128),
+            transaction_fee: Uint128::from(100000u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+
+        let invalid_rsp = set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "topThree".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::TopN {
+                percentages: vec![6000, 3000],
+            },
+        );
+        assert_eq!(
+            invalid_rsp.unwrap_err().to_string(),
+            "Generic error: Prize percentages must add up to hundred percent".to_string()
+        );
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "topThree".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::TopN {
+                percentages: vec![6000, 3000, 1000],
+            },
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "topThree".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Fury,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+
+        for (gamer, team_id) in [
+            ("gamer001", "Team001"),
+            ("gamer002", "Team002"),
+            ("gamer003", "Team003"),
+            ("gamer004", "Team004"),
+        ] {
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[]),
+                gamer.to_string(),
+                "topThree".to_string(),
+                pool_id.clone(),
+                team_id.to_string(),
+                Uint128::from(1000u128),
+                true,
+                None,
+                None,
+            );
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+
+        // Team002 and Team003 tie for the second place
+        game_pool_ranking_submit(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![
+                TeamRank {
+                    team_id: "Team003".to_string(),
+                    rank: 2,
+                },
+                TeamRank {
+                    team_id: "Team001".to_string(),
+                    rank: 1,
+                },
+                TeamRank {
+                    team_id: "Team004".to_string(),
+                    rank: 4,
+                },
+                TeamRank {
+                    team_id: "Team002".to_string(),
+                    rank: 2,
+                },
+            ],
+            true,
+        )
+        .unwrap();
+
+        // Pool collection is 4 * 1000
+        let expected = [
+            ("gamer001", "Team001", 2400u128, 1u64),
+            ("gamer002", "Team002", 800u128, 2u64),
+            ("gamer003", "Team003", 800u128, 2u64),
+            ("gamer004", "Team004", 0u128, 4u64),
+        ];
+        for (gamer, team_id, reward, rank) in expected {
+            let team = query_team_details(
+                &mut deps.storage,
+                pool_id.clone(),
+                team_id.to_string(),
+                gamer.to_string(),
+            )
+            .unwrap();
+            assert_eq!(team.reward_amount, Uint128::from(reward));
+            assert_eq!(team.team_rank, rank);
+        }
+        let game = query_game_details(&mut deps.storage, None).unwrap();
+        assert_eq!(game.game_status, GAME_COMPLETED);
+    }
+
+    #[test]
+    fn test_bidding_deadline_and_permissionless_lock() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        let open_until = mock_env().block.time.seconds() + 1000u64;
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            open_until + 1000u64,
+            open_until + 2000u64,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game002".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+
+        let gamerInfo = mock_info("gamer001", &[coin(1040, "uusd")]);
+        game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            gamerInfo.clone(),
+            "gamer001".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(1000u128),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        let pool = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(pool.current_teams_count, 1u32);
+
+        let early_lock_rsp = lock(
+            deps.as_mut(),
+            mock_env(),
+            gamerInfo.clone(),
+            "Game002".to_string(),
+        );
+        assert_eq!(
+            early_lock_rsp.unwrap_err().to_string(),
+            "Generic error: Cant lock this game before the bidding deadline".to_string()
+        );
+
+        // The game is still open but bids are rejected after the deadline
+        let mut late_env = mock_env();
+        late_env.block.time = late_env.block.time.plus_seconds(1000u64);
+        let late_bid_rsp = game_pool_bid_submit(
+            deps.as_mut(),
+            late_env.clone(),
+            gamerInfo.clone(),
+            "gamer001".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team002".to_string(),
+            Uint128::from(1000u128),
+            true,
+            None,
+            None,
+        );
+        assert_eq!(
+            late_bid_rsp.unwrap_err().to_string(),
+            "Generic error: Bidding deadline for this game has passed".to_string()
+        );
+
+        // Anyone can lock the game now
+        lock(
+            deps.as_mut(),
+            late_env.clone(),
+            gamerInfo.clone(),
+            "Game002".to_string(),
+        )
+        .unwrap();
+        let game = query_game_details(&mut deps.storage, Some("Game002".to_string())).unwrap();
+        assert_eq!(game.game_status, GAME_POOL_CLOSED);
+        let pool = query_pool_details(&mut deps.storage, pool_id).unwrap();
+        assert_eq!(pool.pool_refund_status, true);
+    }
+
+    #[test]
+    fn test_refund_after_result_deadline() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        let open_until = mock_env().block.time.seconds() + 1000u64;
+        let result_deadline = open_until + 5000u64;
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            open_until + 1000u64,
+            result_deadline,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            1,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game002".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer001", &[coin(1040, "uusd")]),
+            "gamer001".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(1000u128),
+            false,
+            None,
+            None,
+        )
+        .unwrap();
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+        )
+        .unwrap();
+
+        let refund = query_refund(
+            &mut deps.storage,
+            mock_env(),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+        )
+        .unwrap();
+        assert_eq!(refund, Uint128::zero());
+
+        // The admin never distributes the results
+        let mut late_env = mock_env();
+        late_env.block.time = late_env.block.time.plus_seconds(6000u64);
+        let refund = query_refund(
+            &mut deps.storage,
+            late_env.clone(),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+        )
+        .unwrap();
+        assert_eq!(refund, Uint128::from(1000u128));
+
+        let late_distribute_rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            late_env.clone(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1000u128),
+            }],
+            true,
+            true,
+        );
+        assert_eq!(
+            late_distribute_rsp.unwrap_err().to_string(),
+            "Generic error: Result deadline for this game has passed".to_string()
+        );
+
+        let rsp = claim_refund(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+            late_env.clone(),
+            Some(true),
+            None,
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[1].value, "1000".to_string());
+        let pool = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(pool.pool_refund_status, true);
+        let refund = query_refund(
+            &mut deps.storage,
+            late_env.clone(),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+        )
+        .unwrap();
+        assert_eq!(refund, Uint128::zero());
+    }
+
+    #[test]
+    fn test_results_finalized_by_results_providers() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let mut pool_ids = Vec::new();
+        for _ in 0..2 {
+            let rsp = create_pool(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                "oneToOne".to_string(),
+                "Game001".to_string(),
+                EntryCurrency::Usdc,
+            )
+            .unwrap();
+            let pool_id = rsp.attributes[0].value.clone();
+            for (gamer, team_id) in [("gamer001", "Team001"), ("gamer002", "Team002")] {
+                game_pool_bid_submit(
+                    deps.as_mut(),
+                    mock_env(),
+                    mock_info(gamer, &[coin(1040, "uusd")]),
+                    gamer.to_string(),
+                    "oneToOne".to_string(),
+                    pool_id.clone(),
+                    team_id.to_string(),
+                    Uint128::from(1000u128),
+                    false,
+                    None,
+                    None,
+                )
+                .unwrap();
+            }
+            pool_ids.push(pool_id);
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+
+        let signing_key = ed25519_zebra::SigningKey::from([7u8; 32]);
+        let verification_key = ed25519_zebra::VerificationKey::from(&signing_key);
+        let invalid_threshold_rsp = set_results_providers(
+            deps.as_mut(),
+            adminInfo.clone(),
+            vec!["provider1".to_string(), "provider2".to_string()],
+            3,
+            vec![],
+        );
+        assert_eq!(
+            invalid_threshold_rsp.unwrap_err().to_string(),
+            "Generic error: Threshold must be between 1 and the number of providers".to_string()
+        );
+        set_results_providers(
+            deps.as_mut(),
+            adminInfo.clone(),
+            vec![
+                "provider1".to_string(),
+                "provider2".to_string(),
+                "provider3".to_string(),
+            ],
+            2,
+            vec![ResultsSignerKey {
+                key_type: SignerKeyType::Ed25519,
+                public_key: Binary::from(<[u8; 32]>::from(verification_key).to_vec()),
+            }],
+        )
+        .unwrap();
+
+        let ranking = vec![
+            TeamRank {
+                team_id: "Team002".to_string(),
+                rank: 1,
+            },
+            TeamRank {
+                team_id: "Team001".to_string(),
+                rank: 2,
+            },
+        ];
+
+        // The admin alone can no longer report results
+        let admin_rsp = game_pool_ranking_submit(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_ids[0].clone(),
+            ranking.clone(),
+            true,
+        );
+        assert_eq!(
+            admin_rsp.unwrap_err().to_string(),
+            "Generic error: Results must be reported by the results providers".to_string()
+        );
+
+        let hash = ranking_hash(
+            &mock_env(),
+            "Game001".to_string(),
+            pool_ids[0].clone(),
+            0,
+            ranking.clone(),
+        )
+        .unwrap();
+        let outsider_rsp = submit_ranking_hash(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            pool_ids[0].clone(),
+            hash.clone(),
+        );
+        assert!(outsider_rsp.is_err());
+        submit_ranking_hash(
+            deps.as_mut(),
+            mock_info("provider1", &[]),
+            pool_ids[0].clone(),
+            hash.clone(),
+        )
+        .unwrap();
+        submit_ranking_hash(
+            deps.as_mut(),
+            mock_info("provider2", &[]),
+            pool_ids[0].clone(),
+            Binary::from(vec![1u8; 32]),
+        )
+        .unwrap();
+        let not_enough_rsp = finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[0].clone(),
+            ranking.clone(),
+            None,
+            true,
+        );
+        assert_eq!(
+            not_enough_rsp.unwrap_err().to_string(),
+            "Generic error: Not enough matching ranking hashes to finalize".to_string()
+        );
+        submit_ranking_hash(
+            deps.as_mut(),
+            mock_info("provider3", &[]),
+            pool_ids[0].clone(),
+            hash.clone(),
+        )
+        .unwrap();
+        finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[0].clone(),
+            ranking.clone(),
+            None,
+            true,
+        )
+        .unwrap();
+        let winner = query_team_details(
+            &mut deps.storage,
+            pool_ids[0].clone(),
+            "Team002".to_string(),
+            "gamer002".to_string(),
+        )
+        .unwrap();
+        assert_eq!(winner.reward_amount, Uint128::from(2000u128));
+
+        // A signature from the registered key finalizes the second pool on its own
+        let hash = ranking_hash(
+            &mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            0,
+            ranking.clone(),
+        )
+        .unwrap();
+        // A ranking signed for another contract can not be replayed here
+        let mut other_env = mock_env();
+        other_env.contract.address = Addr::unchecked("othercontract");
+        let other_hash = ranking_hash(
+            &other_env,
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            0,
+            ranking.clone(),
+        )
+        .unwrap();
+        assert_ne!(other_hash, hash);
+        let replayed_signature = signing_key.sign(other_hash.as_slice());
+        let replayed_rsp = finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            ranking.clone(),
+            Some(Binary::from(<[u8; 64]>::from(replayed_signature).to_vec())),
+            true,
+        );
+        assert_eq!(
+            replayed_rsp.unwrap_err().to_string(),
+            "Generic error: Signature does not match any results signer key".to_string()
+        );
+        let wrong_signature = signing_key.sign(&[0u8; 32]);
+        let wrong_signature_rsp = finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            ranking.clone(),
+            Some(Binary::from(<[u8; 64]>::from(wrong_signature).to_vec())),
+            true,
+        );
+        assert_eq!(
+            wrong_signature_rsp.unwrap_err().to_string(),
+            "Generic error: Signature does not match any results signer key".to_string()
+        );
+        let signature = signing_key.sign(hash.as_slice());
+        finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            ranking.clone(),
+            Some(Binary::from(<[u8; 64]>::from(signature).to_vec())),
+            true,
+        )
+        .unwrap();
+        let winner = query_team_details(
+            &mut deps.storage,
+            pool_ids[1].clone(),
+            "Team002".to_string(),
+            "gamer002".to_string(),
+        )
+        .unwrap();
+        assert_eq!(winner.reward_amount, Uint128::from(2000u128));
+
+        // Revoked results can not be finalized again with the same signature
+        set_dispute_settings(deps.as_mut(), adminInfo.clone(), 3600u64, None).unwrap();
+        revoke_results(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            pool_ids[1].clone(),
+        )
+        .unwrap();
+        let pool = query_pool_details(&mut deps.storage, pool_ids[1].clone()).unwrap();
+        assert_eq!(pool.results_revision, 1u32);
+        let replayed_rsp = finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            ranking.clone(),
+            Some(Binary::from(<[u8; 64]>::from(signature).to_vec())),
+            true,
+        );
+        assert_eq!(
+            replayed_rsp.unwrap_err().to_string(),
+            "Generic error: Signature does not match any results signer key".to_string()
+        );
+        let hash = ranking_hash(
+            &mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            1,
+            ranking.clone(),
+        )
+        .unwrap();
+        let signature = signing_key.sign(hash.as_slice());
+        finalize_ranking(
+            deps.as_mut(),
+            mock_env(),
+            "Game001".to_string(),
+            pool_ids[1].clone(),
+            ranking.clone(),
+            Some(Binary::from(<[u8; 64]>::from(signature).to_vec())),
+            true,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_dispute_window_blocks_claims_and_allows_revoke() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        for gamer in vec!["gamer001", "gamer002"] {
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[coin(1040, "uusd")]),
+                gamer.to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+
+        set_dispute_settings(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            3600u64,
+            Some("arbiter001".to_string()),
+        )
+        .unwrap_err();
+        set_dispute_settings(
+            deps.as_mut(),
+            adminInfo.clone(),
+            3600u64,
+            Some("arbiter001".to_string()),
+        )
+        .unwrap();
+
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1000u128),
+            }],
+            true,
+            true,
+        )
+        .unwrap();
+        let now = mock_env().block.time.seconds();
+        let result = query_game_result(
+            deps.as_ref(),
+            "gamer001".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(result.reward_amount, Uint128::from(1000u128));
+        assert_eq!(result.results_finalized_at, now);
+        assert_eq!(result.claimable_from, now + 3600u64);
+
+        // Claims wait for the dispute window to close
+        let claim_rsp = claim_reward(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            None,
+            mock_env(),
+        );
+        assert_eq!(
+            claim_rsp.unwrap_err().to_string(),
+            "Generic error: No reward for this user".to_string()
+        );
+
+        revoke_results(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer002", &[]),
+            pool_id.clone(),
+        )
+        .unwrap_err();
+        revoke_results(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("arbiter001", &[]),
+            pool_id.clone(),
+        )
+        .unwrap();
+        let result = query_game_result(
+            deps.as_ref(),
+            "gamer001".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(result.reward_amount, Uint128::zero());
+        assert_eq!(result.results_finalized_at, 0u64);
+        assert_eq!(result.claimable_from, 0u64);
+
+        // The corrected results can be submitted again
+        let mut later_env = mock_env();
+        later_env.block.time = later_env.block.time.plus_seconds(100u64);
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            later_env.clone(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer002".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1000u128),
+            }],
+            true,
+            true,
+        )
+        .unwrap();
+        let result = query_game_result(
+            deps.as_ref(),
+            "gamer002".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(result.reward_amount, Uint128::from(1000u128));
+        assert_eq!(result.claimable_from, now + 100u64 + 3600u64);
+
+        let mut closed_env = mock_env();
+        closed_env.block.time = closed_env.block.time.plus_seconds(100u64 + 3600u64);
+        let pool_details = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert!(is_in_dispute_window(&deps.storage, &pool_details, &later_env).unwrap());
+        assert!(!is_in_dispute_window(&deps.storage, &pool_details, &closed_env).unwrap());
+        let revoke_rsp = revoke_results(
+            deps.as_mut(),
+            closed_env.clone(),
+            adminInfo.clone(),
+            pool_id.clone(),
+        );
+        assert_eq!(
+            revoke_rsp.unwrap_err().to_string(),
+            "Generic error: Dispute window for this pool has closed".to_string()
+        );
+    }
+
+    #[test]
+    fn test_private_pool_with_invite_code() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let invite_code_hash = Binary::from(Sha256::digest(b"friends-only").to_vec());
+        let creatorInfo = mock_info("gamer001", &[coin(100, "uusd")]);
+
+        let rsp = create_private_pool(
+            deps.as_mut(),
+            mock_env(),
+            creatorInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            invite_code_hash.clone(),
+            None,
+            EntryCurrency::Usdc,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Private pools are not enabled".to_string()
+        );
+        set_private_pool_settings(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            Uint128::from(100u128),
+            Uint128::from(500u128),
+            Uint128::from(5000u128),
+        )
+        .unwrap_err();
+        set_private_pool_settings(
+            deps.as_mut(),
+            adminInfo.clone(),
+            Uint128::from(100u128),
+            Uint128::from(500u128),
+            Uint128::from(5000u128),
+        )
+        .unwrap();
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "feewallet".to_string(),
+        )
+        .unwrap();
+
+        let rsp = create_private_pool(
+            deps.as_mut(),
+            mock_env(),
+            creatorInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            invite_code_hash.clone(),
+            Some(Uint128::from(10000u128)),
+            EntryCurrency::Usdc,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Entry fee is out of the allowed bounds".to_string()
+        );
+        create_private_pool(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer001", &[coin(50, "uusd")]),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            invite_code_hash.clone(),
+            Some(Uint128::from(2000u128)),
+            EntryCurrency::Usdc,
+        )
+        .unwrap_err();
+        let rsp = create_private_pool(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer001", &[coin(150, "uusd")]),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            invite_code_hash.clone(),
+            Some(Uint128::from(2000u128)),
+            EntryCurrency::Usdc,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Amount sent exceeds the creation fee".to_string()
+        );
+        let rsp = create_private_pool(
+            deps.as_mut(),
+            mock_env(),
+            creatorInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            invite_code_hash.clone(),
+            Some(Uint128::from(2000u128)),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        // The creation fee is forwarded to the fee wallet
+        assert_eq!(
+            rsp.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "feewallet".to_string(),
+                amount: vec![coin(100, "uusd")],
+            })
+        );
+        let pool_details = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(pool_details.creator, Some(Addr::unchecked("gamer001")));
+        assert_eq!(
+            pool_details.invite_code_hash,
+            Some(invite_code_hash.clone())
+        );
+        assert_eq!(pool_details.entry_fee, Some(Uint128::from(2000u128)));
+
+        // Bids name the pool type of the pool
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "tenToOne".to_string(),
+            Uint128::from(100u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer002", &[coin(2080, "uusd")]),
+            "gamer002".to_string(),
+            "tenToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(2000u128),
+            false,
+            None,
+            Some("friends-only".to_string()),
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Pool type does not match the pool".to_string()
+        );
+
+        for invite_code in vec![None, Some("wrong-code".to_string())] {
+            let rsp = game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info("gamer002", &[]),
+                "gamer002".to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                "Team001".to_string(),
+                Uint128::from(2000u128),
+                true,
+                None,
+                invite_code,
+            );
+            assert_eq!(
+                rsp.unwrap_err().to_string(),
+                "Generic error: Invalid invite code for this private pool".to_string()
+            );
+        }
+        game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer002", &[coin(2080, "uusd")]),
+            "gamer002".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(2000u128),
+            false,
+            None,
+            Some("friends-only".to_string()),
+        )
+        .unwrap();
+        let pool_details = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(pool_details.current_teams_count, 1u32);
+        assert_eq!(pool_details.pool_type, "oneToOne".to_string());
+        // The pool collects the custom entry fee instead of the pool type fee
+        let collection = query_pool_collection(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(collection, Uint128::from(2000u128));
+    }
+
+    #[test]
+    fn test_auto_bid_allocates_pools() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            2,
+            1,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+
+        for gamer in vec!["gamer001", "gamer002", "gamer003"] {
+            game_pool_auto_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[coin(1040, "uusd")]),
+                gamer.to_string(),
+                "oneToOne".to_string(),
+                "Game001".to_string(),
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                EntryCurrency::Usdc,
+            )
+            .unwrap();
+        }
+        // The first pool filled up so the third team went to a new pool
+        let pool_details = query_pool_details(&mut deps.storage, "1".to_string()).unwrap();
+        assert_eq!(pool_details.current_teams_count, 2u32);
+        let pool_details = query_pool_details(&mut deps.storage, "2".to_string()).unwrap();
+        assert_eq!(pool_details.current_teams_count, 1u32);
+        assert_eq!(pool_details.game_id, "Game001".to_string());
+        assert_eq!(pool_details.pool_type, "oneToOne".to_string());
+        let team = query_team_details(
+            &mut deps.storage,
+            "2".to_string(),
+            "Team001".to_string(),
+            "gamer003".to_string(),
+        )
+        .unwrap();
+        assert_eq!(team.pool_id, "2".to_string());
+
+        // Pool 2 still has room but gamer003 already has a team in it
+        let pool_id = allocate_pool(
+            deps.as_mut(),
+            mock_env(),
+            "gamer004".to_string(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        assert_eq!(pool_id, "2".to_string());
+        let pool_id = allocate_pool(
+            deps.as_mut(),
+            mock_env(),
+            "gamer003".to_string(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        assert_eq!(pool_id, "3".to_string());
+
+        // Full pools are dropped from the index of open pools
+        assert!(!OPEN_POOLS.has(&deps.storage, ("Game001", "oneToOne", 1u128)));
+        assert!(OPEN_POOLS.has(&deps.storage, ("Game001", "oneToOne", 2u128)));
+
+        // Pools created before the index existed are indexed on migrate
+        OPEN_POOLS.remove(&mut deps.storage, ("Game001", "oneToOne", 2u128));
+        OPEN_POOLS.remove(&mut deps.storage, ("Game001", "oneToOne", 3u128));
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+        assert!(!OPEN_POOLS.has(&deps.storage, ("Game001", "oneToOne", 1u128)));
+        assert_eq!(
+            OPEN_POOLS
+                .load(&deps.storage, ("Game001", "oneToOne", 2u128))
+                .unwrap(),
+            EntryCurrency::Usdc
+        );
+        assert!(OPEN_POOLS.has(&deps.storage, ("Game001", "oneToOne", 3u128)));
+    }
+
+    #[test]
+    fn test_usdc_entry_pool_pays_out_in_usdc() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        // The rake wallets cannot take more than the whole collection
+        let rsp = set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![
+                WalletPercentage {
+                    wallet_address: "rake_1".to_string(),
+                    wallet_name: "rake_1".to_string(),
+                    percentage: 6000u32,
+                },
+                WalletPercentage {
+                    wallet_address: "rake_2".to_string(),
+                    wallet_name: "rake_2".to_string(),
+                    percentage: 4001u32,
+                },
+            ],
+            PrizeStructure::WinnerTakesAll,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Rake percentages cannot exceed hundred percent".to_string()
+        );
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![WalletPercentage {
+                wallet_address: "rake_1".to_string(),
+                wallet_name: "rake_1".to_string(),
+                percentage: 1000u32,
+            }],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game001".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        let pool_details = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(pool_details.entry_currency, EntryCurrency::Usdc);
+
+        // USDC entries are neither transferred with TransferFrom nor swapped
+        for gamer in vec!["gamer001", "gamer002"] {
+            let rsp = game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[coin(1040, "uusd")]),
+                gamer.to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+            assert_eq!(rsp.messages.len(), 0);
+        }
+        game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer003", &[coin(1000, "uusd")]),
+            "gamer003".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(1000u128),
+            false,
+            None,
+            None,
+        )
+        .unwrap_err();
+        // USDC entries pay exactly the entry fee and the fees
+        let rsp = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer003", &[coin(1041, "uusd")]),
+            "gamer003".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(1000u128),
+            false,
+            None,
+            None,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Amount sent exceeds the entry fee and the platform fee".to_string()
+        );
+        let rsp = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer003", &[coin(1041, "uusd")]),
+            "gamer003".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(1001u128),
+            false,
+            None,
+            None,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Amount being bid does not match the pool fee and the platform fee"
+                .to_string()
+        );
+        let rsp = game_pool_bid_submit(
+            deps.as_mut(),
+            mock_env(),
+            mock_info("gamer003", &[coin(1039, "uusd")]),
+            "gamer003".to_string(),
+            "oneToOne".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+            Uint128::from(999u128),
+            false,
+            None,
+            None,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Amount being bid does not match the pool fee and the platform fee"
+                .to_string()
+        );
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+        )
+        .unwrap();
+
+        // Only the collection less the rake can be swapped
+        let rsp = swap(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            Uint128::from(1900u128),
+            pool_id.clone(),
+            None,
+        );
+        assert_eq!(
+            rsp.unwrap_err().to_string(),
+            "Generic error: Swap amount must be the pool collection less the rake".to_string()
+        );
+        // The reward part is set aside at a 1:1 rate and the rest is the rake
+        let rsp = swap(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            Uint128::from(1800u128),
+            pool_id.clone(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(rsp.messages.len(), 0);
+        let rsp = game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game001".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1800u128),
+            }],
+            true,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            rsp.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "rake_1".to_string(),
+                amount: vec![coin(200, "uusd")],
+            })
+        );
+        let result = query_game_result(
+            deps.as_ref(),
+            "gamer001".to_string(),
+            pool_id.clone(),
+            "Team001".to_string(),
+        )
+        .unwrap();
+        assert_eq!(result.entry_currency, EntryCurrency::Usdc);
+
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "feewallet".to_string(),
+        )
+        .unwrap();
+        let rsp = claim_reward(
+            deps.as_mut(),
+            mock_info("gamer001", &[coin(100, "uusd")]),
+            "gamer001".to_string(),
+            None,
+            mock_env(),
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[0].value, "0".to_string());
+        assert_eq!(rsp.attributes[1].value, "1800".to_string());
+        assert_eq!(
+            rsp.messages[1].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "gamer001".to_string(),
+                amount: vec![coin(1800, "uusd")],
+            })
+        );
+    }
+
+    #[test]
+    fn test_claim_refund_across_games() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let now = mock_env().block.time.seconds();
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            now + 100,
+            now + 200,
+            now + 300,
+        )
+        .unwrap();
+
+        let gamerInfo = mock_info("gamer001", &[coin(1040, "uusd")]);
+        for game_id in vec!["Game001", "Game002"] {
+            let rsp = create_pool(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                "oneToOne".to_string(),
+                game_id.to_string(),
+                EntryCurrency::Usdc,
+            )
+            .unwrap();
+            let pool_id = rsp.attributes[0].value.clone();
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                gamerInfo.clone(),
+                "gamer001".to_string(),
+                "oneToOne".to_string(),
+                pool_id,
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+            cancel_game(
+                deps.as_mut(),
+                mock_env(),
+                adminInfo.clone(),
+                game_id.to_string(),
+            )
+            .unwrap();
+
+            // Only the refund of the newly cancelled game is paid
+            let rsp = claim_refund(
+                deps.as_mut(),
+                mock_info("gamer001", &[]),
+                "gamer001".to_string(),
+                Some(game_id.to_string()),
+                mock_env(),
+                Some(true),
+                None,
+            )
+            .unwrap();
+            assert_eq!(rsp.attributes[1].value, "1000".to_string());
+        }
+
+        let err = claim_refund(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+            mock_env(),
+            Some(true),
+            None,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("No refund for this user"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_migrate_moves_swap_balance_info() {
+        let mut deps = mock_dependencies();
+        let swap_info = SwapBalanceDetails {
+            balance_pre_swap: Uint128::from(100u128),
+            balance_post_swap: Uint128::from(300u128),
+            exchange_rate: Uint128::from(2u128),
+            ust_amount_swapped: Uint128::from(100u128),
+            ust_for_rake: Uint128::from(10u128),
+        };
+        LEGACY_SWAP_BALANCE_INFO
+            .save(&mut deps.storage, "1".to_string(), &swap_info)
+            .unwrap();
+        CURRENT_REWARD_FOR_POOL
+            .save(&mut deps.storage, "2".to_string(), &Uint128::from(50u128))
+            .unwrap();
+
+        migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+        assert_eq!(
+            SWAP_BALANCE_INFO
+                .load(&deps.storage, "1".to_string())
+                .unwrap(),
+            swap_info
+        );
+        assert!(!CURRENT_REWARD_FOR_POOL.has(&deps.storage, "1".to_string()));
+        assert_eq!(
+            CURRENT_REWARD_FOR_POOL
+                .load(&deps.storage, "2".to_string())
+                .unwrap(),
+            Uint128::from(50u128)
+        );
+        assert!(!SWAP_BALANCE_INFO.has(&deps.storage, "2".to_string()));
+    }
+
+    #[test]
+    fn test_partial_results_are_refunded_after_result_deadline() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        let open_until = mock_env().block.time.seconds() + 1000u64;
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            open_until + 1000u64,
+            open_until + 5000u64,
+        )
+        .unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game002".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        for gamer in vec!["gamer001", "gamer002"] {
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[coin(1040, "uusd")]),
+                gamer.to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+        )
+        .unwrap();
+
+        // Only the first batch makes it in before the result deadline
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1000u128),
+            }],
+            false,
+            true,
+        )
+        .unwrap();
+
+        // Winners of a partial batch can not claim until the final batch is in
+        let err = claim_reward(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+            mock_env(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("No reward for this user"),
+            })
+        );
+
+        // Without the final batch the pool is refunded after the result deadline
+        let mut late_env = mock_env();
+        late_env.block.time = late_env.block.time.plus_seconds(7000u64);
+        let pool = query_pool_details(&mut deps.storage, pool_id.clone()).unwrap();
+        assert_eq!(
+            is_pool_abandoned(&deps.storage, &pool, &late_env).unwrap(),
+            true
+        );
+        let refund = query_refund(
+            &mut deps.storage,
+            late_env.clone(),
+            "gamer002".to_string(),
+            Some("Game002".to_string()),
+        )
+        .unwrap();
+        assert_eq!(refund, Uint128::from(1000u128));
+        for gamer in vec!["gamer001", "gamer002"] {
+            let rsp = claim_refund(
+                deps.as_mut(),
+                mock_info(gamer, &[]),
+                gamer.to_string(),
+                Some("Game002".to_string()),
+                late_env.clone(),
+                Some(true),
+                None,
+            )
+            .unwrap();
+            assert_eq!(rsp.attributes[1].value, "1000".to_string());
+        }
+        let err = claim_reward(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+            late_env.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("No reward for this user"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_revoked_results_refund_what_the_pool_holds() {
+        let mut deps = mock_dependencies();
+        let instantiate_msg = InstantiateMsg {
+            fury_token_address: "cwtoken11111".to_string(),
+            platform_fees_collector_wallet: "feewallet".to_string(),
+            pool_address: "astroport".to_string(),
+            admin_address: "admin11111".to_string(),
+            platform_fee: Uint128::from(300u128),
+            transaction_fee: Uint128::from(100u128),
+            game_id: "Game001".to_string(),
+            usdc_ibc_symbol: "uusd".to_string(),
+        };
+        let adminInfo = mock_info("admin11111", &[]);
+        instantiate(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            instantiate_msg,
+        )
+        .unwrap();
+        change_fee_wallet(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "feewallet".to_string(),
+        )
+        .unwrap();
+        let open_until = mock_env().block.time.seconds() + 1000u64;
+        create_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            open_until,
+            open_until + 1000u64,
+            open_until + 5000u64,
+        )
+        .unwrap();
+        set_dispute_settings(deps.as_mut(), adminInfo.clone(), 10000u64, None).unwrap();
+        set_pool_type_params(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            Uint128::from(1000u128),
+            2,
+            10,
+            10,
+            vec![WalletPercentage {
+                wallet_address: "rake_1".to_string(),
+                wallet_name: "rake_1".to_string(),
+                percentage: 1000u32,
+            }],
+            PrizeStructure::WinnerTakesAll,
+        )
+        .unwrap();
+        let rsp = create_pool(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "oneToOne".to_string(),
+            "Game002".to_string(),
+            EntryCurrency::Usdc,
+        )
+        .unwrap();
+        let pool_id = rsp.attributes[0].value.clone();
+        for gamer in vec!["gamer001", "gamer002"] {
+            game_pool_bid_submit(
+                deps.as_mut(),
+                mock_env(),
+                mock_info(gamer, &[coin(1040, "uusd")]),
+                gamer.to_string(),
+                "oneToOne".to_string(),
+                pool_id.clone(),
+                "Team001".to_string(),
+                Uint128::from(1000u128),
+                false,
+                None,
+                None,
+            )
+            .unwrap();
+        }
+        lock_game(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+        )
+        .unwrap();
+        swap(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            Uint128::from(1800u128),
+            pool_id.clone(),
+            None,
+        )
+        .unwrap();
+        // The final batch pays the rake
+        game_pool_reward_distribute(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            "Game002".to_string(),
+            pool_id.clone(),
+            vec![GameResult {
+                gamer_address: "gamer001".to_string(),
+                team_id: "Team001".to_string(),
+                reward_amount: Uint128::from(1800u128),
+            }],
+            true,
+            false,
+        )
+        .unwrap();
+
+        // Results can not be revoked once the result deadline has passed
+        let mut late_env = mock_env();
+        late_env.block.time = late_env.block.time.plus_seconds(7000u64);
+        let err = revoke_results(
+            deps.as_mut(),
+            late_env.clone(),
+            adminInfo.clone(),
+            pool_id.clone(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::Std(StdError::GenericErr {
+                msg: String::from("Result deadline for this game has passed"),
+            })
+        );
+
+        // Results revoked in time and never resubmitted are refunded without the paid rake
+        revoke_results(
+            deps.as_mut(),
+            mock_env(),
+            adminInfo.clone(),
+            pool_id.clone(),
+        )
+        .unwrap();
+        let refund = query_refund(
+            &mut deps.storage,
+            late_env.clone(),
+            "gamer002".to_string(),
+            Some("Game002".to_string()),
+        )
+        .unwrap();
+        assert_eq!(refund, Uint128::from(900u128));
+        let rsp = claim_refund(
+            deps.as_mut(),
+            mock_info("gamer001", &[]),
+            "gamer001".to_string(),
+            Some("Game002".to_string()),
+            late_env.clone(),
+            Some(true),
+            None,
+        )
+        .unwrap();
+        assert_eq!(rsp.attributes[1].value, "900".to_string());
+        assert_eq!(
+            rsp.messages[0].msg,
+            CosmosMsg::Bank(BankMsg::Send {
+                to_address: "gamer001".to_string(),
+                amount: vec![coin(940, "uusd")],
+            })
+        );
+    }
 }